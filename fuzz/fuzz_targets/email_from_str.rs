@@ -0,0 +1,16 @@
+#![no_main]
+
+use std::str::FromStr;
+
+use email_pass::Email;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(email) = Email::from_str(input) {
+        // The parsed emails must be printed as valid emails
+        let printed = email.to_string();
+        assert_eq!(Email::from_str(&printed).as_ref(), Ok(&email));
+        let _ = format!("{email:#}");
+        let _ = email.canonical();
+    }
+});
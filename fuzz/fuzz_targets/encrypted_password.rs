@@ -0,0 +1,15 @@
+#![no_main]
+
+use email_pass::{Bcrypt, HashAlgorithm, Password, RehashPolicy};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = HashAlgorithm::detect(input);
+
+    if let Ok(password) = Password::from_encrypt(input) {
+        assert_eq!(password.as_str(), input);
+        let _ = format!("{password:#}");
+        let _ = RehashPolicy::new().requires_rehash(&password);
+        let _ = password.downcast::<Bcrypt>();
+    }
+});
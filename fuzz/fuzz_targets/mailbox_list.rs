@@ -0,0 +1,14 @@
+#![no_main]
+
+use email_pass::{fuzzing, Email, NamedEmail};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    for mailbox in fuzzing::split_mailboxes(input) {
+        let _ = fuzzing::mailbox_address(mailbox);
+        let _ = mailbox.parse::<NamedEmail>();
+    }
+
+    let _ = fuzzing::decode_encoded_words(input);
+    let _ = Email::parse_list(input);
+});
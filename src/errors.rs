@@ -1,11 +1,13 @@
-use std::fmt::Debug;
+#[cfg(any(feature = "email", feature = "password"))]
 use thiserror::Error;
+#[cfg(feature = "zxcvbn")]
 use zxcvbn::ZxcvbnError;
 
-#[cfg(not(feature = "legacy"))]
+#[cfg(all(feature = "password", not(feature = "legacy")))]
 use crate::typed::password_checker::PasswordStrength;
 
-#[derive(Debug, Copy, Clone, Error, PartialEq, Eq)]
+#[cfg(feature = "email")]
+#[derive(Debug, Copy, Clone, Error, PartialEq, Eq, Hash)]
 pub enum EmailError {
     #[error("invalid email format")]
     Format,
@@ -18,8 +20,21 @@ pub enum EmailError {
 
     #[error("invalid email username format")]
     Username,
+
+    #[cfg(not(feature = "legacy"))]
+    #[error("the email contains a control character")]
+    ControlCharacter,
+
+    #[cfg(not(feature = "legacy"))]
+    #[error("the email is in the suppression list")]
+    Suppressed,
+
+    #[cfg(not(feature = "legacy"))]
+    #[error("the email was rejected by a validator")]
+    Rejected,
 }
 
+#[cfg(feature = "password")]
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum PasswordError {
     #[error("invalid password length, use a value with at least {0} characters")]
@@ -40,6 +55,18 @@ pub enum PasswordError {
     #[error("the password is not strong enough, expected password with {0} strength")]
     UnsafePassword(PasswordStrength),
 
+    #[cfg(not(feature = "legacy"))]
+    #[error("the password contains a keyboard walk or repeated keys")]
+    KeyboardPattern,
+
+    #[cfg(not(feature = "legacy"))]
+    #[error("the password is below the required strength level")]
+    StrengthLevel,
+
+    #[cfg(not(feature = "legacy"))]
+    #[error("the required strength level {0} is not a level of the meter")]
+    UnknownStrengthLevel(String),
+
     #[cfg(feature = "legacy")]
     #[error("the password is not strong enough")]
     NotEnoughStrongPassword,
@@ -47,19 +74,96 @@ pub enum PasswordError {
     #[error("the password provided is not encrypted")]
     PasswordNotEncrypted,
 
-    #[cfg(feature = "legacy")]
+    #[cfg(not(feature = "legacy"))]
+    #[error("the hash parts are invalid for the algorithm")]
+    InvalidHashParts,
+
+    #[cfg(not(feature = "legacy"))]
+    #[error("the argon2 parameters are below the OWASP recommendations or invalid")]
+    UnsafeArgon2Params,
+
+    #[cfg(not(feature = "legacy"))]
+    #[error("unknown bcrypt version ${0}$, expected $2a$, $2b$, $2x$ or $2y$")]
+    UnknownBcryptVersion(String),
+
     #[error("error encrypting password")]
     PasswordEncryption,
 
-    #[cfg(feature = "legacy")]
+    #[cfg(not(feature = "legacy"))]
+    #[error("the password is too long for bcrypt, use at most {0} bytes")]
+    TooLongForBcrypt(usize),
+
+    #[cfg(not(feature = "legacy"))]
+    #[error("invalid cost {given}, use a value between {min} and {max}")]
+    InvalidCost { given: u32, min: u32, max: u32 },
+
+    #[cfg(not(feature = "legacy"))]
+    #[error("the default cost was already set to {0}")]
+    DefaultCostAlreadySet(u32),
+
+    #[cfg(not(feature = "legacy"))]
+    #[error("the environment variable {0} is not a valid cost")]
+    InvalidCostEnv(String),
+
     #[error("error during verification procress")]
     PasswordVerification,
 
+    #[cfg(not(feature = "legacy"))]
+    #[error("the hash is invalid for its algorithm")]
+    InvalidHash,
+
+    #[cfg(not(feature = "legacy"))]
+    #[error("the password doesn't match the hash")]
+    PasswordMismatch,
+
+    #[cfg(not(feature = "legacy"))]
+    #[error("the pepper is too short, use at least {0} bytes")]
+    PepperLength(usize),
+
+    #[cfg(not(feature = "legacy"))]
+    #[error("the hash uses the pepper key {0}, not found in the key ring")]
+    UnknownPepperKey(u32),
+
+    #[cfg(not(feature = "legacy"))]
+    #[error("the temporary password has expired")]
+    ExpiredPassword,
+
+    #[cfg(not(feature = "legacy"))]
+    #[error("the temporary password was already used")]
+    UsedPassword,
+
     #[cfg(feature = "legacy")]
     #[error("the raw password don't match with encrypted")]
     WrongPassword,
 }
 
+#[cfg(feature = "otp")]
+#[derive(Debug, Copy, Clone, Error, PartialEq, Eq)]
+pub enum OtpError {
+    #[error("the secret is not valid base32")]
+    Encoding,
+
+    #[error("the secret is too short, use at least {0} bytes")]
+    SecretLength(usize),
+}
+
+#[cfg(feature = "magic-link")]
+#[derive(Debug, Copy, Clone, Error, PartialEq, Eq)]
+pub enum MagicLinkError {
+    #[error("the magic link token is malformed")]
+    Malformed,
+
+    #[error("the magic link token signature is invalid")]
+    Signature,
+
+    #[error("the magic link token expired")]
+    Expired,
+
+    #[error("the key is too short, use at least {0} bytes")]
+    KeyLength(usize),
+}
+
+#[cfg(feature = "zxcvbn")]
 impl From<ZxcvbnError> for PasswordError {
     fn from(err: ZxcvbnError) -> Self {
         match err {
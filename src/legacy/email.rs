@@ -1,15 +1,16 @@
 use crate::errors::EmailError;
 
-use once_cell::sync::Lazy;
 use regex::Regex;
 
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::LazyLock;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-static EMAIL_REGEX: Lazy<Regex> = Lazy::new(|| {
+static EMAIL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"(?P<local>[a-zA-Z0-9_.+-]+)@(?P<domain>[a-zA-Z0-9-]+\.[a-zA-Z0-9-.]+)").unwrap()
 });
 
@@ -31,6 +32,19 @@ impl Email {
 
         Ok(Self(email.to_string()))
     }
+
+    /// Creates a new [`Email`] from the username and the domain, like the typed `Email::build`.
+    pub fn build(username: &str, domain: &str) -> Result<Self, EmailError> {
+        Self::new(&format!("{username}@{domain}"))
+    }
+}
+
+impl FromStr for Email {
+    type Err = EmailError;
+
+    fn from_str(email: &str) -> Result<Self, Self::Err> {
+        Self::new(email)
+    }
 }
 
 impl Display for Email {
@@ -17,6 +17,15 @@ fn email_constructor_works() {
     assert!(incorrect_email.is_err());
 }
 
+#[test]
+fn email_constructors_match_typed_names() {
+    let email = Email::new("example@example.com").unwrap();
+
+    assert_eq!(Email::build("example", "example.com"), Ok(email.clone()));
+    assert_eq!(Email::from_str("example@example.com"), Ok(email));
+    assert!(Email::build("example", "").is_err());
+}
+
 #[test]
 fn legacy_password_constructor_works() {
     let unsafe_password = Password::new("01234".to_string());
@@ -9,11 +9,110 @@ mod typed;
 #[cfg(feature = "legacy")]
 pub use legacy::{email::Email, password::Password};
 
-#[cfg(not(feature = "legacy"))]
+#[cfg(all(feature = "password", not(feature = "legacy")))]
+pub use typed::recovery_codes::RecoveryCodes;
+
+#[cfg(all(feature = "email", not(feature = "legacy")))]
 pub use typed::{
+    canonical::{
+        register_normalization_rule, CanonicalDedup, CanonicalGroup, DedupCanonical,
+        NormalizationRule,
+    },
+    domain::Domain,
     email::Email,
-    password::{Encrypt, Password, Raw},
-    password_checker::{PasswordStrength, PasswordStrengthChecker},
+    email_set::EmailSet,
+    importer::{EmailImporter, ImportReport},
+    named_email::NamedEmail,
+    static_email::{is_valid_email, StaticEmail},
+    stats::EmailStats,
+    suppression::{AsyncSuppressionList, SuppressionList},
+    validator::{AsyncEmailValidator, EmailValidatorPipeline},
+};
+
+#[cfg(all(feature = "password", not(feature = "legacy")))]
+pub use typed::{
+    api_key::ApiKey,
+    hash_report::HashStoreReport,
+    keyboard::KeyboardLayout,
+    lockout::{AttemptTracker, LockoutDecision, LockoutPolicy},
+    observer::{set_verification_observer, VerificationObserver},
+    password::{
+        default_cost, set_default_cost, set_default_cost_from_env, Any, Argon2, Bcrypt, Encrypt,
+        HashFamily, Password, PasswordHasher, Raw, DEFAULT_COST, MAX_BCRYPT_LEN, MAX_COST,
+        MIN_COST,
+    },
+    password_checker::{
+        set_default_password_policy, DictionaryChecker, Entropy, PasswordStrength,
+        PasswordStrengthChecker,
+    },
+    rehash::{HashAlgorithm, RehashPolicy, UpgradingVerifier},
+    scheme::HashScheme,
+    strength_meter::{StrengthLevel, StrengthMeter},
+    temporary_password::TemporaryPassword,
+    timed::Timed,
+    wordlist::Language,
 };
 
-pub use errors::{EmailError, PasswordError};
+#[cfg(all(feature = "email", feature = "password", not(feature = "legacy")))]
+pub use typed::audit::{AuditEvent, AuditOperation, AuditOutcome, AuditSink};
+
+#[cfg(all(
+    feature = "password",
+    not(target_arch = "wasm32"),
+    not(feature = "legacy")
+))]
+pub use typed::hashing_pool::HashingPool;
+
+#[cfg(all(feature = "serde", not(feature = "legacy")))]
+pub use typed::serde_feature as serde_helpers;
+
+#[cfg(all(feature = "serde", not(feature = "legacy")))]
+pub use typed::redacted::Redacted;
+
+#[cfg(all(feature = "fixtures", not(feature = "legacy")))]
+pub use typed::fixtures;
+
+/// Helpers for the test suites of the downstream projects.
+#[cfg(all(feature = "test-util", not(feature = "legacy")))]
+pub use typed::test_util;
+
+#[cfg(all(feature = "parallel", not(feature = "legacy")))]
+pub use typed::parallel::set_thread_pool;
+
+#[cfg(all(feature = "heapless", not(feature = "legacy")))]
+pub use typed::fixed_email::FixedEmail;
+
+#[cfg(all(feature = "i18n", not(feature = "legacy")))]
+pub use typed::i18n::LocalizedFeedback;
+
+#[cfg(all(feature = "otp", not(feature = "legacy")))]
+pub use typed::otp::{HotpSecret, TotpSecret, DRIFT_WINDOW, LOOK_AHEAD_WINDOW, RESYNC_WINDOW};
+
+#[cfg(all(feature = "magic-link", not(feature = "legacy")))]
+pub use typed::magic_link::{MagicLinkKey, MagicLinkToken};
+
+#[cfg(all(feature = "pepper", not(feature = "legacy")))]
+pub use typed::pepper::{HashingConfig, KeyRing, Pepper};
+
+#[cfg(all(feature = "argon2", not(feature = "legacy")))]
+pub use typed::argon2_params::Argon2Params;
+
+#[cfg(all(feature = "migration", not(feature = "legacy")))]
+pub use typed::migration::{MigratingVerifier, UnsaltedDigest};
+
+/// Entry points of the internal parsers, used by the fuzz targets in `fuzz/`.
+#[cfg(all(fuzzing, feature = "email", not(feature = "legacy")))]
+#[doc(hidden)]
+pub use typed::fuzzing;
+
+#[cfg(feature = "email")]
+pub use errors::EmailError;
+
+#[cfg(feature = "password")]
+pub use errors::PasswordError;
+
+#[cfg(feature = "otp")]
+pub use errors::OtpError;
+
+#[cfg(feature = "magic-link")]
+pub use errors::MagicLinkError;
@@ -0,0 +1,74 @@
+use crate::errors::EmailError;
+use crate::typed::email::Email;
+
+/// Splits an address list by the top level `,` and `;` separators, discarding the
+/// group names (`Team:`). Separators inside quoted strings, comments or angle brackets
+/// are ignored.
+pub(crate) fn split_mailboxes(list: &str) -> Vec<&str> {
+    let mut mailboxes = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    let mut in_angle = false;
+    let mut comment_depth = 0;
+    let mut escaped = false;
+
+    for (index, char) in list.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+
+        let is_top_level = !in_quotes && !in_angle && comment_depth == 0;
+        match char {
+            '\\' if in_quotes || comment_depth > 0 => escaped = true,
+            '"' if comment_depth == 0 => in_quotes = !in_quotes,
+            '(' if !in_quotes => comment_depth += 1,
+            ')' if !in_quotes && comment_depth > 0 => comment_depth -= 1,
+            '<' if !in_quotes && comment_depth == 0 => in_angle = true,
+            '>' if !in_quotes && comment_depth == 0 => in_angle = false,
+            ':' if is_top_level => start = index + 1,
+            ',' | ';' if is_top_level => {
+                mailboxes.push(&list[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    mailboxes.push(&list[start..]);
+
+    mailboxes
+        .into_iter()
+        .map(str::trim)
+        .filter(|mailbox| !mailbox.is_empty())
+        .collect()
+}
+
+/// Extracts the address of a mailbox, with the form `addr` or `Display Name <addr>`.
+pub(crate) fn mailbox_address(mailbox: &str) -> Result<&str, EmailError> {
+    match mailbox.rfind('<') {
+        Some(start) => {
+            let end = mailbox[start..].find('>').ok_or(EmailError::Format)?;
+            Ok(&mailbox[start + 1..start + end])
+        }
+        None => Ok(mailbox),
+    }
+}
+
+impl Email {
+    /// Parses an RFC 5322 address list, like the value of the `To` header.
+    ///
+    /// The mailboxes can include a display name (`John <john@example.com>`) and can be
+    /// part of a group (`Team: a@example.com, b@example.com;`), the group members are
+    /// returned flattened.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`Result`] with the first [`EmailError`] found in the list.
+    ///
+    pub fn parse_list(list: &str) -> Result<Vec<Self>, EmailError> {
+        split_mailboxes(list)
+            .into_iter()
+            .map(|mailbox| Self::parse_with_comments(mailbox_address(mailbox)?))
+            .collect()
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use subtle::ConstantTimeEq;
+
+use crate::errors::PasswordError;
+use crate::typed::digest::sha256_hex;
+use crate::typed::generator::random_string;
+use crate::typed::password::{Encrypt, Raw, MAX_COST, MIN_COST};
+use crate::typed::rehash::HashAlgorithm;
+
+/// Characters of the random part of the generated keys.
+const KEY_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+/// Length of the random part of the generated keys.
+const KEY_LEN: usize = 32;
+/// Prefix of the SHA-256 hashes of the keys.
+const SHA256_PREFIX: &str = "$sha256$";
+
+/// API key credential, differentiating the raw key from the hashed keys like [`Password`].
+///
+/// Only the prefix of the raw key can be printed, and [`ApiKey<Raw>::reveal`] consumes
+/// it, so it's shown only once. The keys have high entropy, so the fast SHA-256 hashes are
+/// enough, but bcrypt is available too.
+///
+/// ```
+/// use email_pass::ApiKey;
+///
+/// let raw_key = ApiKey::generate("ep_live_");
+/// let stored_key = raw_key.to_sha256();
+/// let shown_key = raw_key.reveal();
+/// assert!(shown_key.starts_with("ep_live_"));
+///
+/// // Later, with the key received in a request
+/// assert_eq!(stored_key.verify(&ApiKey::from_raw(&shown_key)), Ok(true));
+/// ```
+///
+/// [`Password`]: crate::Password
+#[derive(Eq, PartialEq)]
+pub struct ApiKey<State = Encrypt> {
+    value: Arc<str>,
+    state: PhantomData<State>,
+}
+
+/// Only the hashed keys can be cloned, the raw key is revealed once.
+///
+/// ```compile_fail
+/// let raw_key = email_pass::ApiKey::generate("ep_live_");
+/// let copy = raw_key.clone();
+/// ```
+impl Clone for ApiKey<Encrypt> {
+    fn clone(&self) -> Self {
+        Self {
+            value: Arc::clone(&self.value),
+            state: PhantomData,
+        }
+    }
+}
+
+impl ApiKey {
+    /// Generates a random key starting with a recognizable prefix, like `ep_live_`,
+    /// using the OS secure random generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS random generator is not available.
+    pub fn generate(prefix: &str) -> ApiKey<Raw> {
+        Self::from_raw(&format!("{prefix}{}", random_string(KEY_LEN, KEY_ALPHABET)))
+    }
+
+    /// Creates a raw key, like the key received in a request.
+    pub fn from_raw(raw_key: &str) -> ApiKey<Raw> {
+        ApiKey {
+            value: Arc::from(raw_key),
+            state: PhantomData,
+        }
+    }
+
+    /// Creates a hashed key, check if the key is really hashed with SHA-256 or bcrypt.
+    pub fn from_hash(hashed_key: &str) -> Result<ApiKey<Encrypt>, PasswordError> {
+        let is_sha256 = hashed_key.strip_prefix(SHA256_PREFIX).is_some_and(|hex| {
+            hex.len() == 64
+                && hex
+                    .bytes()
+                    .all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f'))
+        });
+        if !is_sha256 && HashAlgorithm::detect(hashed_key) != Some(HashAlgorithm::Bcrypt) {
+            return Err(PasswordError::PasswordNotEncrypted);
+        }
+
+        Ok(ApiKey {
+            value: Arc::from(hashed_key),
+            state: PhantomData,
+        })
+    }
+}
+
+impl ApiKey<Raw> {
+    /// Returns the prefix of the key, the characters until the last `_`.
+    pub fn prefix(&self) -> &str {
+        self.value
+            .rfind('_')
+            .map_or("", |index| &self.value[..=index])
+    }
+
+    /// Hashes the key with SHA-256.
+    pub fn to_sha256(&self) -> ApiKey<Encrypt> {
+        ApiKey {
+            value: Arc::from(format!(
+                "{SHA256_PREFIX}{}",
+                sha256_hex(self.value.as_bytes())
+            )),
+            state: PhantomData,
+        }
+    }
+
+    /// Hashes the key with bcrypt.
+    ///
+    /// # Errors
+    ///
+    /// * `PasswordError::InvalidCost` - If the cost is not between [`MIN_COST`] and [`MAX_COST`].
+    /// * `PasswordError::PasswordEncryption` - If bcrypt fails.
+    pub fn to_bcrypt(&self, cost: u32) -> Result<ApiKey<Encrypt>, PasswordError> {
+        if !(MIN_COST..=MAX_COST).contains(&cost) {
+            return Err(PasswordError::InvalidCost {
+                given: cost,
+                min: MIN_COST,
+                max: MAX_COST,
+            });
+        }
+
+        let hashed_key =
+            bcrypt::hash(&*self.value, cost).map_err(|_| PasswordError::PasswordEncryption)?;
+
+        Ok(ApiKey {
+            value: Arc::from(hashed_key),
+            state: PhantomData,
+        })
+    }
+
+    /// Consumes the raw key, returning its value to be shown to the user.
+    pub fn reveal(self) -> String {
+        self.value.to_string()
+    }
+}
+
+impl ApiKey<Encrypt> {
+    /// Verifies the raw key. The SHA-256 hashes are compared in constant time.
+    ///
+    /// # Errors
+    ///
+    /// * `PasswordError::PasswordVerification` - If bcrypt fails.
+    pub fn verify(&self, raw_key: &ApiKey<Raw>) -> Result<bool, PasswordError> {
+        match self.value.strip_prefix(SHA256_PREFIX) {
+            Some(hex) => Ok(hex
+                .as_bytes()
+                .ct_eq(sha256_hex(raw_key.value.as_bytes()).as_bytes())
+                .into()),
+            None => bcrypt::verify(&*raw_key.value, &self.value)
+                .map_err(|_| PasswordError::PasswordVerification),
+        }
+    }
+
+    /// Extracts the inner value from [`ApiKey<Encrypt>`].
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl Display for ApiKey<Encrypt> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.value, f)
+    }
+}
+
+impl Debug for ApiKey<Encrypt> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ApiKey(\"{}\")", self.as_str())
+    }
+}
+
+/// Only the prefix of the raw key is printed.
+impl Debug for ApiKey<Raw> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ApiKey(\"{}***\")", self.prefix())
+    }
+}
@@ -0,0 +1,180 @@
+use argon2::Params;
+
+use crate::errors::PasswordError;
+use crate::typed::scheme::HashScheme;
+
+/// Minimum memory in KiB accepted by [`Argon2Params::new`], of the weakest OWASP
+/// configuration, 7 MiB with 5 iterations.
+const MIN_MEMORY_KIB: u32 = 7 * 1024;
+/// Minimum product of the memory in KiB and the iterations accepted by
+/// [`Argon2Params::new`], of the weakest OWASP configuration.
+const MIN_MEMORY_ITERATIONS: u64 = 7 * 1024 * 5;
+/// Default length in bytes of the hashes.
+const DEFAULT_OUTPUT_LEN: usize = Params::DEFAULT_OUTPUT_LEN;
+/// Minimum length in bytes of the hashes accepted, 128 bits.
+const MIN_OUTPUT_LEN: usize = 16;
+/// Maximum length in bytes of the hashes accepted, the longer hashes don't add security.
+const MAX_OUTPUT_LEN: usize = 64;
+
+/// Validated parameters of Argon2id, to hash with [`HashScheme::Argon2`].
+///
+/// The presets follow the OWASP Password Storage Cheat Sheet and the RFC 9106, and
+/// the custom parameters are rejected below the weakest OWASP configuration.
+///
+/// ```
+/// use email_pass::{Argon2Params, HashScheme, Password};
+///
+/// let params = Argon2Params::new(47_104, 1, 1)?;
+/// let encrypt_password = Password::new("ThisIsAPassPhrase.And.Secure.Password")
+///     .to_encrypt_scheme(params.into())?;
+/// assert!(encrypt_password.as_str().starts_with("$argon2id$v=19$m=47104,t=1,p=1$"));
+///
+/// assert!(Argon2Params::new(1024, 1, 1).is_err());
+/// assert!(params.with_output_len(8).is_err());
+/// # Ok::<(), email_pass::PasswordError>(())
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Argon2Params {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    output_len: usize,
+}
+
+impl Argon2Params {
+    /// Creates custom parameters, with the memory in KiB and hashes of 32 bytes.
+    ///
+    /// # Errors
+    ///
+    /// * `PasswordError::UnsafeArgon2Params` - If the memory is below 7 MiB, the memory
+    ///   times the iterations is below 7 MiB times 5, of the weakest OWASP configuration,
+    ///   or the parameters are not accepted by Argon2, like a parallelism of zero.
+    pub fn new(memory_kib: u32, iterations: u32, parallelism: u32) -> Result<Self, PasswordError> {
+        Self {
+            memory_kib,
+            iterations,
+            parallelism,
+            output_len: DEFAULT_OUTPUT_LEN,
+        }
+        .validate()
+    }
+
+    /// Sets the length in bytes of the hashes.
+    ///
+    /// # Errors
+    ///
+    /// * `PasswordError::UnsafeArgon2Params` - If the length is not between 16 and 64
+    ///   bytes.
+    pub fn with_output_len(mut self, output_len: usize) -> Result<Self, PasswordError> {
+        self.output_len = output_len;
+        self.validate()
+    }
+
+    fn validate(self) -> Result<Self, PasswordError> {
+        let memory_iterations = u64::from(self.memory_kib) * u64::from(self.iterations);
+        if self.memory_kib < MIN_MEMORY_KIB
+            || memory_iterations < MIN_MEMORY_ITERATIONS
+            || !(MIN_OUTPUT_LEN..=MAX_OUTPUT_LEN).contains(&self.output_len)
+        {
+            return Err(PasswordError::UnsafeArgon2Params);
+        }
+        Params::new(
+            self.memory_kib,
+            self.iterations,
+            self.parallelism,
+            Some(self.output_len),
+        )
+        .map_err(|_| PasswordError::UnsafeArgon2Params)?;
+
+        Ok(self)
+    }
+
+    /// The minimum configuration recommended by OWASP, 19 MiB of memory, 2 iterations
+    /// and 1 degree of parallelism. Same as [`HashScheme::argon2`].
+    pub fn owasp_minimum() -> Self {
+        Self {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+            output_len: DEFAULT_OUTPUT_LEN,
+        }
+    }
+
+    /// The interactive limits of libsodium, for the logins: 64 MiB of memory, 2
+    /// iterations and 1 degree of parallelism.
+    pub fn interactive() -> Self {
+        Self {
+            memory_kib: 64 * 1024,
+            iterations: 2,
+            parallelism: 1,
+            output_len: DEFAULT_OUTPUT_LEN,
+        }
+    }
+
+    /// The sensitive limits of libsodium, for the secrets hashed rarely, like the keys
+    /// of the administrators or the encryption keys: 1 GiB of memory, 4 iterations and
+    /// 1 degree of parallelism. It takes seconds, don't use it for the logins.
+    pub fn high_security() -> Self {
+        Self {
+            memory_kib: 1024 * 1024,
+            iterations: 4,
+            parallelism: 1,
+            output_len: DEFAULT_OUTPUT_LEN,
+        }
+    }
+
+    /// The first recommendation of the RFC 9106, 2 GiB of memory, 1 iteration and 4
+    /// lanes.
+    pub fn rfc9106() -> Self {
+        Self {
+            memory_kib: 2 * 1024 * 1024,
+            iterations: 1,
+            parallelism: 4,
+            output_len: DEFAULT_OUTPUT_LEN,
+        }
+    }
+
+    /// The second recommendation of the RFC 9106, for the memory-constrained
+    /// environments, 64 MiB of memory, 3 iterations and 4 lanes.
+    pub fn rfc9106_low_memory() -> Self {
+        Self {
+            memory_kib: 64 * 1024,
+            iterations: 3,
+            parallelism: 4,
+            output_len: DEFAULT_OUTPUT_LEN,
+        }
+    }
+
+    pub fn memory_kib(&self) -> u32 {
+        self.memory_kib
+    }
+
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    pub fn parallelism(&self) -> u32 {
+        self.parallelism
+    }
+
+    pub fn output_len(&self) -> usize {
+        self.output_len
+    }
+}
+
+impl Default for Argon2Params {
+    fn default() -> Self {
+        Self::owasp_minimum()
+    }
+}
+
+impl From<Argon2Params> for HashScheme {
+    fn from(params: Argon2Params) -> Self {
+        HashScheme::Argon2 {
+            memory_kib: params.memory_kib,
+            iterations: params.iterations,
+            parallelism: params.parallelism,
+            output_len: params.output_len,
+        }
+    }
+}
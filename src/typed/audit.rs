@@ -0,0 +1,207 @@
+#[cfg(feature = "email")]
+use std::time::{Duration, SystemTime};
+
+#[cfg(feature = "magic-link")]
+use crate::errors::MagicLinkError;
+#[cfg(feature = "email")]
+use crate::errors::PasswordError;
+#[cfg(feature = "magic-link")]
+use crate::typed::magic_link::{MagicLinkKey, MagicLinkToken};
+#[cfg(feature = "email")]
+use crate::typed::{
+    digest::sha256_hex,
+    email::Email,
+    password::{Encrypt, HashFamily, Password, Raw},
+    temporary_password::TemporaryPassword,
+};
+
+/// Operation registered in an [`AuditEvent`].
+#[cfg(feature = "email")]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum AuditOperation {
+    /// Verification of a raw password against the stored encrypted password.
+    Verify,
+    /// Generation of a [`TemporaryPassword`].
+    ///
+    /// [`TemporaryPassword`]: crate::TemporaryPassword
+    TemporaryPasswordIssue,
+    /// Verification of a [`TemporaryPassword`].
+    ///
+    /// [`TemporaryPassword`]: crate::TemporaryPassword
+    TemporaryPasswordVerify,
+    /// Issue of a `MagicLinkToken`, with the `magic-link` feature.
+    MagicLinkIssue,
+    /// Verification of a `MagicLinkToken`, with the `magic-link` feature.
+    MagicLinkVerify,
+}
+
+/// Result of the operation registered in an [`AuditEvent`].
+#[cfg(feature = "email")]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AuditOutcome {
+    Success,
+    Failure,
+}
+
+/// Redacted record of an authentication operation.
+///
+/// The event never contains the email address or the password, only the
+/// SHA-256 hex digest of the lowercased email address. The digest is empty if the
+/// email is unknown, like for the malformed magic link tokens.
+#[cfg(feature = "email")]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AuditEvent {
+    pub email_hash: String,
+    pub operation: AuditOperation,
+    pub outcome: AuditOutcome,
+    pub timestamp: SystemTime,
+}
+
+#[cfg(feature = "email")]
+impl AuditEvent {
+    pub fn new(email: &Email, operation: AuditOperation, outcome: AuditOutcome) -> Self {
+        Self {
+            email_hash: email_hash(email),
+            operation,
+            outcome,
+            timestamp: SystemTime::now(),
+        }
+    }
+}
+
+/// Destination of the [`AuditEvent`]s, used to retain authentication audit trails.
+#[cfg(feature = "email")]
+pub trait AuditSink {
+    fn record(&self, event: AuditEvent);
+}
+
+#[cfg(feature = "email")]
+fn email_hash(email: &Email) -> String {
+    sha256_hex(email.to_string().to_lowercase().as_bytes())
+}
+
+#[cfg(feature = "email")]
+impl<Family: HashFamily> Password<Encrypt<Family>> {
+    /// Verifies the raw password and records the outcome in the [`AuditSink`].
+    /// Any verification error is recorded as [`AuditOutcome::Failure`].
+    pub fn verify_audited<S>(
+        &self,
+        email: &Email,
+        raw_password: &Password<Raw>,
+        sink: &S,
+    ) -> Result<bool, PasswordError>
+    where
+        S: AuditSink + ?Sized,
+    {
+        let result = self.verify(raw_password);
+        let outcome = match result {
+            Ok(true) => AuditOutcome::Success,
+            _ => AuditOutcome::Failure,
+        };
+        sink.record(AuditEvent::new(email, AuditOperation::Verify, outcome));
+
+        result
+    }
+}
+
+#[cfg(feature = "email")]
+impl TemporaryPassword {
+    /// Generates a temporary password of the email, like [`TemporaryPassword::generate`],
+    /// and records the outcome in the [`AuditSink`].
+    pub fn generate_audited<S>(
+        email: &Email,
+        ttl: Duration,
+        sink: &S,
+    ) -> Result<(Password<Raw>, Self), PasswordError>
+    where
+        S: AuditSink + ?Sized,
+    {
+        let result = Self::generate(ttl);
+        let outcome = match result {
+            Ok(_) => AuditOutcome::Success,
+            Err(_) => AuditOutcome::Failure,
+        };
+        sink.record(AuditEvent::new(
+            email,
+            AuditOperation::TemporaryPasswordIssue,
+            outcome,
+        ));
+
+        result
+    }
+
+    /// Verifies the raw password like [`TemporaryPassword::verify_once`], and records the
+    /// outcome in the [`AuditSink`]. Any error is recorded as [`AuditOutcome::Failure`].
+    pub fn verify_once_audited<S>(
+        &mut self,
+        email: &Email,
+        raw_password: &Password<Raw>,
+        sink: &S,
+    ) -> Result<bool, PasswordError>
+    where
+        S: AuditSink + ?Sized,
+    {
+        let result = self.verify_once(raw_password);
+        let outcome = match result {
+            Ok(true) => AuditOutcome::Success,
+            _ => AuditOutcome::Failure,
+        };
+        sink.record(AuditEvent::new(
+            email,
+            AuditOperation::TemporaryPasswordVerify,
+            outcome,
+        ));
+
+        result
+    }
+}
+
+#[cfg(feature = "magic-link")]
+impl MagicLinkToken {
+    /// Issues a token of the email like [`MagicLinkToken::issue`], and records it in the
+    /// [`AuditSink`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS random generator is not available.
+    pub fn issue_audited<S>(key: &MagicLinkKey, email: &Email, ttl: Duration, sink: &S) -> Self
+    where
+        S: AuditSink + ?Sized,
+    {
+        let token = Self::issue(key, email, ttl);
+        sink.record(AuditEvent::new(
+            email,
+            AuditOperation::MagicLinkIssue,
+            AuditOutcome::Success,
+        ));
+
+        token
+    }
+
+    /// Verifies the token like [`MagicLinkToken::verify`], and records the outcome in the
+    /// [`AuditSink`]. The rejected tokens are recorded with the email they claim, not
+    /// verified, or without email if they are malformed.
+    pub fn verify_audited<S>(&self, key: &MagicLinkKey, sink: &S) -> Result<Email, MagicLinkError>
+    where
+        S: AuditSink + ?Sized,
+    {
+        let result = self.verify(key);
+        let (email_hash, outcome) = match &result {
+            Ok(email) => (email_hash(email), AuditOutcome::Success),
+            Err(_) => (
+                self.claimed_email()
+                    .map_or_else(String::new, |email| email_hash(&email)),
+                AuditOutcome::Failure,
+            ),
+        };
+        sink.record(AuditEvent {
+            email_hash,
+            operation: AuditOperation::MagicLinkVerify,
+            outcome,
+            timestamp: SystemTime::now(),
+        });
+
+        result
+    }
+}
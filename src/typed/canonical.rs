@@ -0,0 +1,202 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, LazyLock, RwLock};
+
+use crate::typed::email::Email;
+
+/// Domains where the dots of the username are ignored by the provider.
+const DOTLESS_DOMAINS: [&str; 2] = ["gmail.com", "googlemail.com"];
+
+/// Registered rules. The registration replaces the list, so the snapshots taken before
+/// keep their rules.
+static RULES: LazyLock<RwLock<RuleSet>> = LazyLock::new(|| RwLock::new(RuleSet(Arc::from([]))));
+
+/// Canonicalization rule applied by [`Email::canonical`].
+///
+/// The rules receive the lowercased username and domain, and can modify both.
+pub trait NormalizationRule: Send + Sync {
+    fn normalize(&self, username: &mut String, domain: &mut String);
+}
+
+/// Removes the sub-address: `john+news@example.com` becomes `john@example.com`.
+struct SubAddressRule;
+
+impl NormalizationRule for SubAddressRule {
+    fn normalize(&self, username: &mut String, _domain: &mut String) {
+        if let Some(index) = username.find('+').filter(|index| *index > 0) {
+            username.truncate(index);
+        }
+    }
+}
+
+/// Removes the dots of the Gmail usernames, and replaces `googlemail.com` with `gmail.com`.
+struct GmailRule;
+
+impl NormalizationRule for GmailRule {
+    fn normalize(&self, username: &mut String, domain: &mut String) {
+        if DOTLESS_DOMAINS.contains(&domain.as_str()) {
+            username.retain(|char| char != '.');
+            *domain = DOTLESS_DOMAINS[0].to_string();
+        }
+    }
+}
+
+/// Snapshot of the registered [`NormalizationRule`]s, so the canonical forms of a
+/// collection don't change when a rule is registered after its creation.
+#[derive(Clone)]
+pub(crate) struct RuleSet(Arc<[Arc<dyn NormalizationRule>]>);
+
+impl RuleSet {
+    /// Returns the rules registered until now.
+    pub(crate) fn current() -> Self {
+        RULES.read().unwrap_or_else(|err| err.into_inner()).clone()
+    }
+}
+
+impl Debug for RuleSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RuleSet({} rules)", self.0.len())
+    }
+}
+
+/// Registers a [`NormalizationRule`] applied by [`Email::canonical`] after the built-in rules,
+/// in the registration order.
+///
+/// The collections keep the rules registered at their creation, like [`EmailSet`], so
+/// register the rules at startup, before creating them.
+///
+/// [`EmailSet`]: crate::EmailSet
+pub fn register_normalization_rule<R>(rule: R)
+where
+    R: NormalizationRule + 'static,
+{
+    let mut rules = RULES.write().unwrap_or_else(|err| err.into_inner());
+    let mut updated = rules.0.to_vec();
+    updated.push(Arc::new(rule));
+    *rules = RuleSet(Arc::from(updated));
+}
+
+impl Email {
+    /// Returns the canonical form of the email, used to detect aliases of the same mailbox:
+    ///
+    /// * The username and the domain are lowercased.
+    /// * The sub-address is removed (`john+news@example.com` becomes `john@example.com`).
+    /// * For Gmail, the dots of the username are removed and `googlemail.com`
+    ///   becomes `gmail.com`.
+    /// * The rules added with [`register_normalization_rule`] are applied.
+    pub fn canonical(&self) -> Email {
+        self.canonical_with(&RuleSet::current())
+    }
+
+    /// Returns the canonical form of the email with the snapshot of the rules.
+    pub(crate) fn canonical_with(&self, rules: &RuleSet) -> Email {
+        let mut username = self.username().to_lowercase();
+        let mut domain = self.domain().to_lowercase();
+
+        SubAddressRule.normalize(&mut username, &mut domain);
+        GmailRule.normalize(&mut username, &mut domain);
+        for rule in rules.0.iter() {
+            rule.normalize(&mut username, &mut domain);
+        }
+
+        // The lowercased email is always valid, so it's used when the rules
+        // produce an invalid email, like a too short one
+        Email::build(&username, &domain)
+            .or_else(|_| {
+                Email::build(
+                    &self.username().to_lowercase(),
+                    &self.domain().to_lowercase(),
+                )
+            })
+            .map(|canonical| canonical.with_original(&self.original()))
+            .unwrap_or_else(|_| self.clone())
+    }
+
+    /// Checks if both emails have the same canonical form. See [`Email::canonical`].
+    pub fn eq_canonical(&self, other: &Email) -> bool {
+        self.canonical() == other.canonical()
+    }
+}
+
+/// Inputs that collapsed into the same canonical email.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalGroup {
+    pub canonical: Email,
+    /// Positions of the collapsed emails in the input, in order.
+    pub indexes: Vec<usize>,
+}
+
+/// Result of [`DedupCanonical::dedup_canonical`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalDedup {
+    /// The first occurrence of every canonical email, in the input order.
+    pub emails: Vec<Email>,
+    /// The groups with more than one input.
+    pub duplicates: Vec<CanonicalGroup>,
+}
+
+/// Removes the duplicated emails according to [`Email::canonical`].
+///
+/// Implemented for the iterators of owned and borrowed emails, like `Vec<Email>` and
+/// `&[Email]`. The borrowed emails kept in the result are cloned.
+pub trait DedupCanonical {
+    fn dedup_canonical(self) -> CanonicalDedup;
+}
+
+mod item {
+    use crate::typed::email::Email;
+
+    /// Emails accepted by [`DedupCanonical`](super::DedupCanonical).
+    pub trait DedupItem: std::borrow::Borrow<Email> {
+        fn into_email(self) -> Email;
+    }
+
+    impl DedupItem for Email {
+        fn into_email(self) -> Email {
+            self
+        }
+    }
+
+    impl DedupItem for &Email {
+        fn into_email(self) -> Email {
+            self.clone()
+        }
+    }
+}
+
+impl<I> DedupCanonical for I
+where
+    I: IntoIterator,
+    I::Item: item::DedupItem,
+{
+    fn dedup_canonical(self) -> CanonicalDedup {
+        let mut emails = Vec::new();
+        let mut groups: Vec<CanonicalGroup> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        let rules = RuleSet::current();
+
+        for (index, email) in self.into_iter().enumerate() {
+            let canonical = email.borrow().canonical_with(&rules);
+            match positions.get(&canonical.to_string()) {
+                Some(&position) => groups[position].indexes.push(index),
+                None => {
+                    positions.insert(canonical.to_string(), groups.len());
+                    groups.push(CanonicalGroup {
+                        canonical,
+                        indexes: vec![index],
+                    });
+                    emails.push(item::DedupItem::into_email(email));
+                }
+            }
+        }
+
+        CanonicalDedup {
+            emails,
+            duplicates: groups
+                .into_iter()
+                .filter(|group| group.indexes.len() > 1)
+                .collect(),
+        }
+    }
+}
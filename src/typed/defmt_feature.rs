@@ -0,0 +1,36 @@
+use defmt::{Format, Formatter};
+
+#[cfg(feature = "email")]
+use crate::typed::email::Email;
+#[cfg(feature = "password")]
+use crate::typed::password::{Encrypt, Password, Raw};
+
+/// The username is masked, keeping only its first character: `j***@example.com`.
+#[cfg(feature = "email")]
+impl Format for Email {
+    fn format(&self, f: Formatter<'_>) {
+        let first_len = self.username().chars().next().map_or(0, char::len_utf8);
+        defmt::write!(
+            f,
+            "{=str}***@{=str}",
+            &self.username()[..first_len],
+            self.domain()
+        );
+    }
+}
+
+/// The password is always `***`.
+#[cfg(feature = "password")]
+impl Format for Password<Raw> {
+    fn format(&self, f: Formatter<'_>) {
+        defmt::write!(f, "***");
+    }
+}
+
+/// The hash is always `***`.
+#[cfg(feature = "password")]
+impl<Family> Format for Password<Encrypt<Family>> {
+    fn format(&self, f: Formatter<'_>) {
+        defmt::write!(f, "***");
+    }
+}
@@ -0,0 +1,18 @@
+use std::fmt::Write;
+
+use sha2::{Digest, Sha256};
+
+/// Returns the SHA-256 hex digest of the value.
+pub(crate) fn sha256_hex(value: &[u8]) -> String {
+    to_hex(&Sha256::digest(value))
+}
+
+/// Returns the lowercase hex of the bytes.
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
@@ -0,0 +1,180 @@
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+
+use crate::typed::email::Email;
+
+/// Common second-level labels of the country code TLDs, like `co.uk` or `com.br`.
+const SECOND_LEVEL_LABELS: [&str; 10] = [
+    "ac", "co", "com", "edu", "gob", "gov", "ne", "net", "or", "org",
+];
+
+/// Borrowed view of an email domain, to inspect its labels.
+///
+/// The comparisons between domains are case-insensitive and ignore the trailing dot.
+#[derive(Debug, Copy, Clone)]
+pub struct Domain<'a>(&'a str);
+
+impl<'a> Domain<'a> {
+    pub fn new(domain: &'a str) -> Self {
+        Self(domain.strip_suffix('.').unwrap_or(domain))
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    /// Iterates over the labels, from the left to the right.
+    /// For `mail.example.com` yields `mail`, `example` and `com`.
+    pub fn labels(&self) -> impl DoubleEndedIterator<Item = &'a str> {
+        self.0.split('.')
+    }
+
+    /// Returns the domain without the leftmost label, or [`None`] for a single label domain.
+    /// For `mail.example.com` returns `example.com`.
+    pub fn parent(&self) -> Option<Domain<'a>> {
+        self.0
+            .split_once('.')
+            .map(|(_, parent)| Domain(parent))
+            .filter(|parent| !parent.0.is_empty())
+    }
+
+    /// Returns the top-level domain, the rightmost label.
+    /// For `mail.example.com` returns `com`.
+    pub fn tld(&self) -> &'a str {
+        self.0.rsplit('.').next().unwrap_or(self.0)
+    }
+
+    /// Returns the registrable domain, the domain bought from a registrar.
+    /// For `mail.example.com` returns `example.com`, and for `mail.example.co.uk`
+    /// returns `example.co.uk`.
+    ///
+    /// With the `addr` feature, the registrable domain is found with the public suffix
+    /// list. Without it, the result is an approximation: the last two labels, or the last
+    /// three when the TLD is a country code and the second label is a common second-level
+    /// label, like `co`, `com` or `org`.
+    pub fn registrable_domain(&self) -> Domain<'a> {
+        #[cfg(feature = "addr")]
+        if let Some(root) = addr::parse_domain_name(self.0)
+            .ok()
+            .and_then(|name| name.root())
+        {
+            return Domain(root);
+        }
+
+        let mut labels = self.labels().rev();
+        let tld = labels.next().unwrap_or_default();
+        let second = labels.next().unwrap_or_default();
+        let len = if tld.len() == 2
+            && SECOND_LEVEL_LABELS
+                .iter()
+                .any(|label| label.eq_ignore_ascii_case(second))
+        {
+            3
+        } else {
+            2
+        };
+
+        let mut domain = *self;
+        while domain.labels().count() > len {
+            domain = domain.parent().unwrap_or(domain);
+        }
+
+        domain
+    }
+
+    /// Checks if the domain is `domain` or is under `domain`, respecting the label boundaries:
+    /// `mail.example.com` is a subdomain of `example.com`, but `mail.badexample.com` is not.
+    pub fn is_subdomain_of(&self, domain: &str) -> bool {
+        let domain = Domain::new(domain).0;
+        let Some(index) = self.0.len().checked_sub(domain.len()) else {
+            return false;
+        };
+        if !self.0.is_char_boundary(index) {
+            return false;
+        }
+
+        let (prefix, suffix) = self.0.split_at(index);
+        suffix.eq_ignore_ascii_case(domain) && (prefix.is_empty() || prefix.ends_with('.'))
+    }
+
+    /// Checks if the domain matches a wildcard pattern, comparing label by label:
+    ///
+    /// * A `*` as the leftmost label matches one or more labels: `*.edu` matches
+    ///   `mit.edu` and `cs.mit.edu`, but not `edu`.
+    /// * A `*` in any other position matches exactly one label: `mail.*.com` matches
+    ///   `mail.example.com`.
+    /// * Any other label must be equal, ignoring the case.
+    pub fn matches(&self, pattern: &str) -> bool {
+        let pattern = Domain::new(pattern);
+        let mut labels = self.labels().rev();
+        let mut pattern_labels = pattern.labels().rev().peekable();
+
+        while let Some(pattern_label) = pattern_labels.next() {
+            let is_leftmost = pattern_labels.peek().is_none();
+            match (pattern_label, labels.next()) {
+                (_, None) => return false,
+                ("*", Some(_)) if is_leftmost => return true,
+                ("*", Some(_)) => {}
+                (pattern_label, Some(label)) if pattern_label.eq_ignore_ascii_case(label) => {}
+                _ => return false,
+            }
+        }
+
+        labels.next().is_none()
+    }
+}
+
+impl Domain<'_> {
+    /// Returns the domain without the trailing dot, the form compared and hashed.
+    #[inline]
+    fn trimmed(&self) -> &str {
+        self.0.strip_suffix('.').unwrap_or(self.0)
+    }
+}
+
+impl PartialEq for Domain<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.trimmed().eq_ignore_ascii_case(other.trimmed())
+    }
+}
+
+impl Eq for Domain<'_> {}
+
+/// Consistent with [`PartialEq`]: hashes the lowercased domain without the trailing dot.
+impl Hash for Domain<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for byte in self.trimmed().bytes() {
+            state.write_u8(byte.to_ascii_lowercase());
+        }
+        state.write_u8(0xff);
+    }
+}
+
+impl Display for Domain<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.0, f)
+    }
+}
+
+impl Email {
+    /// Returns the [`Domain`] of the email address.
+    #[inline]
+    pub fn domain_name(&self) -> Domain<'_> {
+        Domain::new(self.domain())
+    }
+
+    /// Checks if the email domain is `domain` or is under `domain`.
+    /// See [`Domain::is_subdomain_of`].
+    #[inline]
+    pub fn is_subdomain_of(&self, domain: &str) -> bool {
+        self.domain_name().is_subdomain_of(domain)
+    }
+
+    /// Checks if the email domain matches a wildcard pattern, like `*.edu`.
+    /// See [`Domain::matches`].
+    #[inline]
+    pub fn domain_matches(&self, pattern: &str) -> bool {
+        self.domain_name().matches(pattern)
+    }
+}
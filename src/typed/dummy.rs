@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+use crate::typed::password::{Encrypt, Password, Raw, DEFAULT_COST};
+use crate::typed::scheme::HashScheme;
+
+/// Baked-in hash of [`DUMMY_PASSWORD`] with bcrypt and [`DEFAULT_COST`], so the first
+/// dummy verification doesn't pay the hash of the dummy.
+#[cfg(not(target_arch = "wasm32"))]
+const DEFAULT_DUMMY_HASH: &str = "$2b$12$3DovuNZ4eU7HEPzPyOBpeunXyXUoky7vq6Abm822zVeHXmwRYoEu.";
+#[cfg(target_arch = "wasm32")]
+const DEFAULT_DUMMY_HASH: &str = "$2b$10$I13uBK7mwqmKSuB76tE3MeRy7mAdF21JwlF35Cy1tlV3aBjaIzVZi";
+
+/// Value of the dummy hashes created for the schemes without a baked-in hash.
+const DUMMY_PASSWORD: &str = "email_pass dummy password";
+
+/// Dummy hashes by scheme.
+static DUMMY_HASHES: LazyLock<Mutex<HashMap<HashScheme, Password<Encrypt>>>> =
+    LazyLock::new(|| {
+        let default_hash = Password::from_encrypt(DEFAULT_DUMMY_HASH).expect("valid dummy hash");
+        Mutex::new(HashMap::from([(
+            HashScheme::bcrypt(DEFAULT_COST),
+            default_hash,
+        )]))
+    });
+
+impl Password {
+    /// Verifies the raw password with a dummy hash of [`HashScheme::auto`], the scheme
+    /// of the new hashes, and discards the result. Call it when the user doesn't exist,
+    /// so the login takes the same time for the existing and the unknown emails, and
+    /// can't be used to enumerate the users.
+    ///
+    /// ```
+    /// use email_pass::Password;
+    ///
+    /// # let stored_password: Option<Password> = None;
+    /// let raw_password = Password::new("ThisIsAPassPhrase.And.Secure.Password");
+    /// let Some(stored_password) = stored_password else {
+    ///     Password::verify_dummy(&raw_password);
+    ///     return; // Invalid email or password
+    /// };
+    /// ```
+    ///
+    /// The dummy hash of bcrypt with [`DEFAULT_COST`] is baked-in; the dummy hash of
+    /// other schemes is created by the first call with the scheme, register the cost with
+    /// [`set_default_cost`] at startup.
+    ///
+    /// [`set_default_cost`]: crate::set_default_cost
+    pub fn verify_dummy(raw_password: &Password<Raw>) {
+        Self::verify_dummy_with_scheme(raw_password, HashScheme::auto());
+    }
+
+    /// Verifies the raw password with a dummy bcrypt hash of the cost, like
+    /// [`Password::verify_dummy`], for the applications that pass the cost explicitly.
+    /// An invalid cost is replaced by [`DEFAULT_COST`].
+    pub fn verify_dummy_with_cost(raw_password: &Password<Raw>, cost: u32) {
+        Self::verify_dummy_with_scheme(raw_password, HashScheme::bcrypt(cost));
+    }
+
+    /// Verifies the raw password with a dummy hash of the scheme, like
+    /// [`Password::verify_dummy`], for the applications that hash with other scheme than
+    /// [`HashScheme::auto`]. An invalid scheme is replaced by bcrypt with [`DEFAULT_COST`].
+    pub fn verify_dummy_with_scheme(raw_password: &Password<Raw>, scheme: HashScheme) {
+        let _ = dummy_hash(scheme).verify(raw_password);
+    }
+}
+
+fn dummy_hashes() -> MutexGuard<'static, HashMap<HashScheme, Password<Encrypt>>> {
+    DUMMY_HASHES.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Returns the dummy hash of the scheme, creating it if it's not cached. The hash is
+/// created without the lock, so the concurrent dummy verifications don't wait for it.
+fn dummy_hash(scheme: HashScheme) -> Password<Encrypt> {
+    if let Some(hash) = dummy_hashes().get(&scheme) {
+        return hash.clone();
+    }
+
+    match scheme.hash(&Password::new(DUMMY_PASSWORD)) {
+        Ok(hash) => dummy_hashes().entry(scheme).or_insert(hash).clone(),
+        Err(_) => dummy_hashes()[&HashScheme::bcrypt(DEFAULT_COST)].clone(),
+    }
+}
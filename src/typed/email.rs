@@ -1,35 +1,82 @@
 use std::{
+    borrow::Cow,
     fmt::{Display, Formatter},
     str::FromStr,
-    sync::Arc,
+    sync::{LazyLock, OnceLock},
 };
 
-use once_cell::sync::Lazy;
+use std::sync::Arc;
+
 use regex::Regex;
 
 use crate::errors::EmailError;
+use crate::typed::static_email::is_valid_email;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-static EMAIL_REGEX: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(?P<local>[a-zA-Z0-9_.+-]+)@(?P<domain>[a-zA-Z0-9-]+\.[a-zA-Z0-9-.]+)").unwrap()
-});
+pub const EMAIL_REGEX_VALUE: &str =
+    r"(?P<local>[a-zA-Z0-9_.+-]+)@(?P<domain>[a-zA-Z0-9-]+\.[a-zA-Z0-9-.]+)";
+
+static EMAIL_REGEX: OnceLock<Regex> = OnceLock::new();
+
+static EMAIL_USERNAME_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"([a-zA-Z0-9_.+-]+)").unwrap());
+#[cfg(not(feature = "idna"))]
+static EMAIL_DOMAIN_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"([a-zA-Z0-9-]+\.[a-zA-Z0-9-.]+)").unwrap());
 
-static EMAIL_USERNAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"([a-zA-Z0-9_.+-]+)").unwrap());
-static EMAIL_DOMAIN_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"([a-zA-Z0-9-]+\.[a-zA-Z0-9-.]+)").unwrap());
+/// Storage used by [`Email`] parts. With the `compact_str` feature, short values are
+/// stored inline without heap allocation.
+#[cfg(not(feature = "compact_str"))]
+type EmailStr = Arc<str>;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg(feature = "compact_str")]
+type EmailStr = compact_str::CompactString;
+
+#[inline]
+pub(crate) fn email_regex() -> &'static Regex {
+    EMAIL_REGEX.get_or_init(|| Regex::new(EMAIL_REGEX_VALUE).unwrap())
+}
+
+/// Validated email address.
+///
+/// The constructors reject the control characters anywhere in the input, like CR
+/// and LF, so the emails are safe to interpolate into SMTP and MIME headers.
+///
+/// Cloning is cheap: the address is a shared [`Arc<str>`], so a clone only increments
+/// its reference count. With the `compact_str` feature, the short addresses are copied
+/// inline instead. To key many maps by the same address, convert it once with
+/// [`Email::into_arc`] and clone the [`Arc<str>`].
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde", serde(try_from = "String"))]
 #[cfg_attr(feature = "serde", serde(into = "String"))]
 pub struct Email {
-    local: Arc<str>,
-    domain: Arc<str>,
+    /// The full address, `username@domain`.
+    address: EmailStr,
+    /// Position of the `@` in the address.
+    at: usize,
+    /// The parsed input, only retained if it differs from the email representation.
+    original: Option<EmailStr>,
 }
 
 impl Email {
+    /// Rejects the control characters, to prevent header injections.
+    #[inline]
+    pub(crate) fn check_control_chars(value: &str) -> Result<(), EmailError> {
+        let has_control = if value.is_ascii() {
+            value.bytes().any(|byte| byte.is_ascii_control())
+        } else {
+            value.chars().any(char::is_control)
+        };
+        if has_control {
+            Err(EmailError::ControlCharacter)?
+        }
+
+        Ok(())
+    }
+
     #[inline]
     fn check_len(len: usize) -> Result<(), EmailError> {
         if !(6..=254).contains(&len) {
@@ -45,9 +92,20 @@ impl Email {
             Err(EmailError::Username)?
         }
 
+        Self::check_dots(username)
+    }
+
+    /// Rejects leading, trailing and consecutive dots, following the RFC 5321 dot-atom rules.
+    #[inline]
+    fn check_dots(username: &str) -> Result<(), EmailError> {
+        if username.starts_with('.') || username.ends_with('.') || username.contains("..") {
+            Err(EmailError::Username)?
+        }
+
         Ok(())
     }
 
+    #[cfg(not(feature = "idna"))]
     #[inline]
     fn check_domain(domain: &str) -> Result<(), EmailError> {
         if !EMAIL_DOMAIN_REGEX.is_match(domain) {
@@ -57,6 +115,70 @@ impl Email {
         Ok(())
     }
 
+    /// Validates the domain with the UTS-46 processing, including the hyphen, bidi
+    /// and DNS length rules, as browsers do.
+    #[cfg(feature = "idna")]
+    #[inline]
+    fn check_domain(domain: &str) -> Result<(), EmailError> {
+        if !domain.contains('.') || idna::domain_to_ascii_strict(domain).is_err() {
+            Err(EmailError::Domain)?
+        }
+
+        Ok(())
+    }
+
+    /// Converts an internationalized domain to punycode, like [`Email::from_str`], so
+    /// the constructors store the same domain for the same input.
+    #[cfg(feature = "idna")]
+    #[inline]
+    fn ascii_domain(domain: &str) -> Result<Cow<'_, str>, EmailError> {
+        if domain.is_ascii() {
+            return Ok(Cow::Borrowed(domain));
+        }
+
+        idna::domain_to_ascii_strict(domain)
+            .map(Cow::Owned)
+            .map_err(|_| EmailError::Domain)
+    }
+
+    #[cfg(not(feature = "idna"))]
+    #[inline]
+    fn ascii_domain(domain: &str) -> Result<Cow<'_, str>, EmailError> {
+        Ok(Cow::Borrowed(domain))
+    }
+
+    /// Validates the labels with the hostname rules of the [`addr`] crate: the labels
+    /// can't start or end with a hyphen, and the TLD can't be numeric.
+    #[cfg(feature = "addr")]
+    #[inline]
+    fn check_hostname(domain: &str) -> Result<(), EmailError> {
+        if addr::parse_domain_name(domain).is_err() {
+            Err(EmailError::Domain)?
+        }
+
+        Ok(())
+    }
+
+    /// Installs a precompiled pattern to be used by [`Email::from_str`] instead of
+    /// [`EMAIL_REGEX_VALUE`].
+    ///
+    /// The pattern is compiled once per process, so this method must be called before
+    /// the first parse. The regex must define the `local` and `domain` named groups.
+    ///
+    /// # Returns
+    ///
+    /// Returns the given regex back if the pattern was already initialized or if it
+    /// misses one of the required groups.
+    ///
+    pub fn set_pattern(regex: Regex) -> Result<(), Regex> {
+        let has_group = |name: &str| regex.capture_names().flatten().any(|group| group == name);
+        if !has_group("local") || !has_group("domain") {
+            return Err(regex);
+        }
+
+        EMAIL_REGEX.set(regex)
+    }
+
     /// Creates a new [`Email`] instance.
     ///
     /// # Parameters
@@ -64,34 +186,209 @@ impl Email {
     /// * `username` - The username of the email address.
     /// * `domain` - The domain of the email address.
     ///
+    /// With the `idna` feature, the internationalized domains are stored in punycode,
+    /// like [`Email::from_str`].
+    ///
     /// # Returns
     ///
     /// Returns a [`Result`] with a [`EmailError`] if the username or domain is not valid.
     ///
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, err)
+    )]
     pub fn build(username: &str, domain: &str) -> Result<Self, EmailError> {
+        Self::check_control_chars(username)?;
+        Self::check_control_chars(domain)?;
+        let domain = &*Self::ascii_domain(domain)?;
         Self::check_len(username.len() + domain.len())?;
         Self::check_username(username)?;
         Self::check_domain(domain)?;
+        #[cfg(feature = "addr")]
+        Self::check_hostname(domain)?;
 
-        Ok(Self {
-            local: Arc::from(username),
-            domain: Arc::from(domain),
-        })
+        Ok(Self::from_parts(username, domain))
+    }
+
+    /// Creates an [`Email`] from a validated username and domain.
+    fn from_parts(username: &str, domain: &str) -> Self {
+        let mut address = String::with_capacity(username.len() + 1 + domain.len());
+        address.push_str(username);
+        address.push('@');
+        address.push_str(domain);
+
+        Self {
+            address: EmailStr::from(address),
+            at: username.len(),
+            original: None,
+        }
+    }
+
+    /// Creates a new [`Email`] instance from the full address, like [`Email::from_str`].
+    /// Also available with the `legacy` feature, so toggling it doesn't change the call sites.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`Result`] with a [`EmailError`] if the email is not valid.
+    ///
+    pub fn new(email: &str) -> Result<Self, EmailError> {
+        Self::from_str(email)
+    }
+
+    /// Parses an email after the usual cleanup of user input: trims the surrounding
+    /// whitespace, strips an enclosing `<...>`, removes a trailing period and
+    /// lowercases the domain.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`Result`] with a [`EmailError`] if the cleaned email is not valid.
+    ///
+    pub fn parse_lossy(input: &str) -> Result<Self, EmailError> {
+        let email = input.trim();
+        let email = email
+            .strip_prefix('<')
+            .and_then(|email| email.strip_suffix('>'))
+            .unwrap_or(email)
+            .trim();
+        let email = email.strip_suffix('.').unwrap_or(email);
+
+        let (username, domain) = email.rsplit_once('@').ok_or(EmailError::Format)?;
+        Self::from_str(&format!("{username}@{}", domain.to_lowercase()))
+            .map(|email| email.with_original(input))
+    }
+
+    /// Parses an email containing RFC 5322 comments, like `john(work)@example.com` or
+    /// `(note) john@example.com`. The comments are discarded.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`Result`] with a [`EmailError`] if the comments are unbalanced or
+    /// the email without comments is not valid.
+    ///
+    pub fn parse_with_comments(email: &str) -> Result<Self, EmailError> {
+        let mut uncommented = String::with_capacity(email.len());
+        let mut depth = 0usize;
+        let mut chars = email.chars();
+
+        while let Some(char) = chars.next() {
+            match char {
+                '(' => depth += 1,
+                ')' => depth = depth.checked_sub(1).ok_or(EmailError::Format)?,
+                '\\' if depth > 0 => {
+                    chars.next();
+                }
+                _ if depth > 0 => {}
+                _ => uncommented.push(char),
+            }
+        }
+
+        if depth > 0 {
+            Err(EmailError::Format)?
+        }
+
+        Self::from_str(uncommented.trim()).map(|parsed| parsed.with_original(email))
+    }
+
+    /// Parses every input, collecting the failures instead of aborting on the first one.
+    ///
+    /// # Returns
+    ///
+    /// Returns the valid emails, and the index, the original input and the [`EmailError`]
+    /// of every invalid input.
+    ///
+    pub fn parse_many<I, S>(inputs: I) -> (Vec<Self>, Vec<(usize, String, EmailError)>)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut emails = Vec::new();
+        let mut errors = Vec::new();
+
+        for (index, input) in inputs.into_iter().enumerate() {
+            let input = input.as_ref();
+            match Self::from_str(input) {
+                Ok(email) => emails.push(email),
+                Err(err) => errors.push((index, input.to_string(), err)),
+            }
+        }
+
+        (emails, errors)
+    }
+
+    /// Returns the exact input used to parse the email, preserving the capitalization
+    /// and the formatting removed by the parsing methods or by [`Email::canonical`].
+    /// Returns the email representation if the email was not parsed.
+    pub fn original(&self) -> Cow<'_, str> {
+        match &self.original {
+            Some(original) => Cow::Borrowed(original),
+            None => Cow::Owned(self.to_string()),
+        }
+    }
+
+    /// Retains `original` as the input of the email, if it differs from the email
+    /// representation.
+    pub(crate) fn with_original(mut self, original: &str) -> Self {
+        self.original = (self != original).then(|| EmailStr::from(original));
+        self
     }
 
     #[inline]
     pub fn username(&self) -> &str {
-        &self.local
+        &self.address[..self.at]
     }
 
     #[inline]
     pub fn local(&self) -> &str {
-        &self.local
+        self.username()
     }
 
     #[inline]
     pub fn domain(&self) -> &str {
-        &self.domain
+        &self.address[self.at + 1..]
+    }
+
+    /// Returns the full address in an [`Arc<str>`], to be shared between threads without
+    /// cloning the email. The address is already stored in an [`Arc<str>`], so it's
+    /// returned without copying; with the `compact_str` feature, it's copied once.
+    ///
+    /// The parsed input is not used, it can differ from the address, like the comments
+    /// or the brackets removed by the parsing.
+    #[cfg(not(feature = "compact_str"))]
+    pub fn into_arc(self) -> Arc<str> {
+        self.address
+    }
+
+    /// Returns the full address in an [`Arc<str>`], to be shared between threads without
+    /// cloning the email. The address is copied once to the allocation of the `Arc`.
+    ///
+    /// The parsed input is not used, it can differ from the address, like the comments
+    /// or the brackets removed by the parsing.
+    #[cfg(feature = "compact_str")]
+    pub fn into_arc(self) -> Arc<str> {
+        Arc::from(self.address.as_str())
+    }
+
+    /// Compares the email with a string, ignoring the case of the username and the domain,
+    /// without allocating.
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use email_pass::Email;
+    ///
+    /// let email = Email::from_str("John@Example.com")?;
+    /// assert!(email.eq_ignore_case("john@example.com"));
+    /// assert!(email != "john@example.com");
+    /// # Ok::<(), email_pass::EmailError>(())
+    /// ```
+    pub fn eq_ignore_case(&self, other: &str) -> bool {
+        fn lowercase(value: &str) -> impl Iterator<Item = char> + '_ {
+            value.chars().flat_map(char::to_lowercase)
+        }
+
+        other.rsplit_once('@').is_some_and(|(local, domain)| {
+            lowercase(self.username()).eq(lowercase(local))
+                && lowercase(self.domain()).eq(lowercase(domain))
+        })
     }
 
     /// Sets the username of the email address.
@@ -105,14 +402,16 @@ impl Email {
     /// Returns a [`Result`] with a [`EmailError`] if the username is not valid.
     ///
     pub fn set_username(&mut self, username: &str) -> Result<(), EmailError> {
+        Self::check_control_chars(username)?;
         Self::check_username(username)?;
 
-        self.local = Arc::from(username);
+        *self = Self::from_parts(username, self.domain());
 
         Ok(())
     }
 
-    /// Sets the domain of the email address.
+    /// Sets the domain of the email address. With the `idna` feature, the
+    /// internationalized domains are stored in punycode, like [`Email::build`].
     ///
     /// # Parameters
     ///
@@ -123,9 +422,13 @@ impl Email {
     /// Returns a [`Result`] with a [`EmailError`] if the domain is not valid.
     ///
     pub fn set_domain(&mut self, domain: &str) -> Result<(), EmailError> {
+        Self::check_control_chars(domain)?;
+        let domain = &*Self::ascii_domain(domain)?;
         Self::check_domain(domain)?;
+        #[cfg(feature = "addr")]
+        Self::check_hostname(domain)?;
 
-        self.domain = Arc::from(domain);
+        *self = Self::from_parts(self.username(), domain);
 
         Ok(())
     }
@@ -134,20 +437,86 @@ impl Email {
 impl FromStr for Email {
     type Err = EmailError;
 
+    /// Parses and validates the email address. With the `idna` feature, the
+    /// internationalized domains are accepted like [`Email::parse_idn`].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, err)
+    )]
     fn from_str(email: &str) -> Result<Self, Self::Err> {
+        Self::check_control_chars(email)?;
+        // The internationalized domains are converted to punycode before the validation
+        #[cfg(feature = "idna")]
+        if email
+            .rsplit_once('@')
+            .is_some_and(|(_, domain)| !domain.is_ascii())
+        {
+            return Self::parse_idn(email);
+        }
         Self::check_len(email.len())?;
 
-        let captures = EMAIL_REGEX.captures(email).ok_or(EmailError::Format)?;
+        let regex = email_regex();
+        // Fast path of the default pattern: the inputs without `@` never match, and the
+        // plain emails are validated with a byte scan, without running the regex
+        if regex.as_str() == EMAIL_REGEX_VALUE {
+            let at = memchr::memchr(b'@', email.as_bytes()).ok_or(EmailError::Format)?;
+            if is_valid_email(email) {
+                #[cfg(any(feature = "idna", feature = "addr"))]
+                let domain = &email[at + 1..];
+                #[cfg(feature = "idna")]
+                Self::check_domain(domain)?;
+                #[cfg(feature = "addr")]
+                Self::check_hostname(domain)?;
+
+                return Ok(Self {
+                    address: EmailStr::from(email),
+                    at,
+                    original: None,
+                });
+            }
+        }
+
+        let captures = regex.captures(email).ok_or(EmailError::Format)?;
         let local = captures.name("local").unwrap().as_str();
         let domain = captures.name("domain").unwrap().as_str();
+        Self::check_dots(local)?;
+        #[cfg(feature = "idna")]
+        Self::check_domain(domain)?;
+        #[cfg(feature = "addr")]
+        Self::check_hostname(domain)?;
+
+        let matched = captures.get(0).unwrap();
+        let is_verbatim = matched.start() == 0 && matched.end() == email.len();
 
         Ok(Self {
-            local: Arc::from(local),
-            domain: Arc::from(domain),
+            original: (!is_verbatim).then(|| EmailStr::from(email)),
+            ..Self::from_parts(local, domain)
         })
     }
 }
 
+/// The emails are compared by username and domain, the original input is ignored.
+impl PartialEq for Email {
+    fn eq(&self, other: &Self) -> bool {
+        self.address == other.address
+    }
+}
+
+impl Eq for Email {}
+
+/// Compares the email representation with the string, like `john@example.com`.
+impl PartialEq<str> for Email {
+    fn eq(&self, other: &str) -> bool {
+        *self.address == *other
+    }
+}
+
+impl PartialEq<&str> for Email {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
 impl TryFrom<String> for Email {
     type Error = EmailError;
 
@@ -156,9 +525,17 @@ impl TryFrom<String> for Email {
     }
 }
 
+/// The alternate flag (`{:#}`) masks the username, keeping only its first
+/// character: `j***@example.com`.
 impl Display for Email {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}@{}", self.local, self.domain)
+        if f.alternate() {
+            let username = self.username();
+            let first_len = username.chars().next().map_or(0, char::len_utf8);
+            return write!(f, "{}***@{}", &username[..first_len], self.domain());
+        }
+
+        f.write_str(&self.address)
     }
 }
 
@@ -167,3 +544,9 @@ impl From<Email> for String {
         email.to_string()
     }
 }
+
+impl From<Email> for Arc<str> {
+    fn from(email: Email) -> Self {
+        email.into_arc()
+    }
+}
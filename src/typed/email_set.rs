@@ -0,0 +1,174 @@
+use std::collections::{btree_map, BTreeMap};
+
+use crate::typed::canonical::RuleSet;
+use crate::typed::email::Email;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Set of emails unique by their canonical form, so aliases of the same mailbox
+/// like `john+news@example.com` and `John@example.com` are stored once.
+/// See [`Email::canonical`].
+///
+/// The set keeps the first inserted email of every mailbox, and iterates in the
+/// order of the canonical emails.
+///
+/// The set uses the normalization rules registered at its creation, so the rules
+/// registered later don't change the canonical forms of the stored emails. The sets
+/// created from other sets, like [`EmailSet::union`], keep the rules of `self`.
+#[derive(Debug, Clone)]
+pub struct EmailSet {
+    emails: BTreeMap<String, Email>,
+    rules: RuleSet,
+}
+
+impl EmailSet {
+    pub fn new() -> Self {
+        Self {
+            emails: BTreeMap::new(),
+            rules: RuleSet::current(),
+        }
+    }
+
+    fn key(&self, email: &Email) -> String {
+        email.canonical_with(&self.rules).to_string()
+    }
+
+    /// Adds an email to the set.
+    ///
+    /// # Returns
+    ///
+    /// Returns `false` if the set already contains an alias of the email.
+    pub fn insert(&mut self, email: Email) -> bool {
+        match self.emails.entry(self.key(&email)) {
+            btree_map::Entry::Occupied(_) => false,
+            btree_map::Entry::Vacant(entry) => {
+                entry.insert(email);
+                true
+            }
+        }
+    }
+
+    /// Checks if the set contains the email or an alias of the email.
+    pub fn contains(&self, email: &Email) -> bool {
+        self.emails.contains_key(&self.key(email))
+    }
+
+    /// Removes the email or its alias from the set, returning the stored email.
+    pub fn remove(&mut self, email: &Email) -> Option<Email> {
+        let key = self.key(email);
+        self.emails.remove(&key)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.emails.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.emails.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Email> {
+        self.emails.values()
+    }
+
+    /// Returns the emails in `self` or in `other`. For the mailboxes in both
+    /// sets, the email of `self` is kept.
+    ///
+    /// The emails of `other` are compared with the rules of `self`.
+    pub fn union(&self, other: &EmailSet) -> EmailSet {
+        let mut union = self.clone();
+        union.extend(other.iter().cloned());
+        union
+    }
+
+    /// Returns the emails in `self` that are not in `other`, according to the
+    /// rules of `other`.
+    pub fn difference(&self, other: &EmailSet) -> EmailSet {
+        self.filter(|email| !other.contains(email))
+    }
+
+    /// Returns the emails in `self` that are also in `other`, according to the
+    /// rules of `other`.
+    pub fn intersection(&self, other: &EmailSet) -> EmailSet {
+        self.filter(|email| other.contains(email))
+    }
+
+    fn filter<F>(&self, predicate: F) -> EmailSet
+    where
+        F: Fn(&Email) -> bool,
+    {
+        EmailSet {
+            emails: self
+                .emails
+                .iter()
+                .filter(|(_, email)| predicate(email))
+                .map(|(canonical, email)| (canonical.clone(), email.clone()))
+                .collect(),
+            rules: self.rules.clone(),
+        }
+    }
+}
+
+impl Default for EmailSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The sets are compared by their emails, the rules are ignored.
+impl PartialEq for EmailSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.emails == other.emails
+    }
+}
+
+impl Eq for EmailSet {}
+
+impl Extend<Email> for EmailSet {
+    fn extend<I: IntoIterator<Item = Email>>(&mut self, emails: I) {
+        for email in emails {
+            self.insert(email);
+        }
+    }
+}
+
+impl FromIterator<Email> for EmailSet {
+    fn from_iter<I: IntoIterator<Item = Email>>(emails: I) -> Self {
+        let mut set = EmailSet::new();
+        set.extend(emails);
+        set
+    }
+}
+
+impl IntoIterator for EmailSet {
+    type Item = Email;
+    type IntoIter = btree_map::IntoValues<String, Email>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.emails.into_values()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for EmailSet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+/// Deserializes a sequence of emails, discarding the aliases.
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for EmailSet {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::<Email>::deserialize(deserializer).map(EmailSet::from_iter)
+    }
+}
@@ -0,0 +1,33 @@
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::typed::email::{email_regex, Email};
+
+impl Email {
+    /// Scans free text, like support tickets or logs, and yields the valid emails with
+    /// their byte ranges in the text, in order.
+    ///
+    /// The candidates are found with the installed pattern, without the dots of the
+    /// sentence punctuation, and validated like [`Email::from_str`].
+    ///
+    /// ```
+    /// use email_pass::Email;
+    ///
+    /// let text = "Write to john@example.com or jane@example.org.";
+    /// let emails: Vec<_> = Email::extract_all(text).collect();
+    ///
+    /// assert_eq!(emails.len(), 2);
+    /// assert_eq!(emails[0].0, 9..25);
+    /// assert_eq!(emails[1].1.to_string(), "jane@example.org");
+    /// ```
+    pub fn extract_all(text: &str) -> impl Iterator<Item = (Range<usize>, Email)> + '_ {
+        email_regex().find_iter(text).filter_map(move |matched| {
+            let candidate = matched.as_str().trim_start_matches('.');
+            let start = matched.end() - candidate.len();
+            let candidate = candidate.trim_end_matches('.');
+            let range = start..start + candidate.len();
+
+            Email::from_str(candidate).ok().map(|email| (range, email))
+        })
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::errors::EmailError;
+use crate::typed::email::Email;
+use crate::typed::static_email::is_valid_email;
+
+/// Email stored in a fixed buffer of `N` bytes, validated without allocations, to keep
+/// the emails inline in other structs or on the stack. The default capacity fits the
+/// longest valid email.
+///
+/// Applies the same rules as [`is_valid_email`].
+///
+/// ```
+/// use email_pass::FixedEmail;
+///
+/// let email: FixedEmail<32> = FixedEmail::parse("john@example.com")?;
+/// assert_eq!(email.username(), "john");
+/// assert_eq!(email.domain(), "example.com");
+///
+/// assert!(FixedEmail::<8>::parse("john@example.com").is_err());
+/// # Ok::<(), email_pass::EmailError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FixedEmail<const N: usize = 254> {
+    value: heapless::String<N>,
+    at: usize,
+}
+
+impl<const N: usize> FixedEmail<N> {
+    /// Validates the email and copies it to the buffer.
+    ///
+    /// # Errors
+    ///
+    /// * `EmailError::Length` - If the email is longer than `N` bytes, or its length is
+    ///   not between 6 and 254.
+    /// * `EmailError::Format` - If the email syntax is not valid.
+    pub fn parse(email: &str) -> Result<Self, EmailError> {
+        if email.len() > N || !(6..=254).contains(&email.len()) {
+            Err(EmailError::Length)?
+        }
+        if !is_valid_email(email) {
+            Err(EmailError::Format)?
+        }
+
+        let value = heapless::String::try_from(email).map_err(|_| EmailError::Length)?;
+        let at = email.find('@').ok_or(EmailError::Format)?;
+
+        Ok(Self { value, at })
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    #[inline]
+    pub fn username(&self) -> &str {
+        &self.value[..self.at]
+    }
+
+    #[inline]
+    pub fn domain(&self) -> &str {
+        &self.value[self.at + 1..]
+    }
+
+    /// Parses the email with [`Email::from_str`], applying the rules of [`Email`].
+    ///
+    /// # Errors
+    ///
+    /// * The errors of [`Email::from_str`], if the email is rejected by its rules.
+    pub fn to_email(&self) -> Result<Email, EmailError> {
+        Email::from_str(self.as_str())
+    }
+}
+
+impl<const N: usize> FromStr for FixedEmail<N> {
+    type Err = EmailError;
+
+    fn from_str(email: &str) -> Result<Self, Self::Err> {
+        Self::parse(email)
+    }
+}
+
+impl<const N: usize> Display for FixedEmail<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> TryFrom<FixedEmail<N>> for Email {
+    type Error = EmailError;
+
+    fn try_from(email: FixedEmail<N>) -> Result<Self, Self::Error> {
+        email.to_email()
+    }
+}
@@ -0,0 +1,86 @@
+//! Curated corpora of emails and passwords, checked by the crate tests. Useful to
+//! test the integrations against the same inputs the crate guarantees.
+
+/// Emails accepted by [`Email::from_str`](crate::Email).
+pub const VALID_EMAILS: &[&str] = &[
+    "john@example.com",
+    "john.doe@example.com",
+    "john+newsletter@example.com",
+    "john_doe@example.co.uk",
+    "john-doe@mail.example.org",
+    "j.o.h.n@example.io",
+    "john123@example123.com",
+    "JOHN@EXAMPLE.COM",
+    "john@my-domain.com",
+    "a@b.co",
+];
+
+/// Emails rejected by [`Email::from_str`](crate::Email).
+pub const INVALID_EMAILS: &[&str] = &[
+    "",
+    "john",
+    "john@",
+    "@example.com",
+    "john@example",
+    "john@@example.com",
+    ".john@example.com",
+    "john.@example.com",
+    "jo..hn@example.com",
+    "a@b.c",
+];
+
+/// Unusual emails, with the expected result of [`Email::from_str`](crate::Email).
+/// Some are accepted because the parser extracts the first email of the input.
+pub const EDGE_CASE_EMAILS: &[(&str, bool)] = &[
+    ("x@y.z", false),
+    ("john doe@example.com", true),
+    // The `idna` and `addr` features validate the domain labels
+    (
+        "john@-example.com",
+        !cfg!(any(feature = "idna", feature = "addr")),
+    ),
+    (
+        "john@example..com",
+        !cfg!(any(feature = "idna", feature = "addr")),
+    ),
+    ("john@localhost", false),
+    // The `addr` feature rejects the numeric TLDs
+    ("john@192.168.0.1", !cfg!(feature = "addr")),
+    ("john@[192.168.0.1]", false),
+    ("\"john doe\"@example.com", false),
+    ("john@example.com.", !cfg!(feature = "idna")),
+    ("jöhn@example.com", true),
+    // The `idna` feature converts the internationalized domains to punycode
+    ("john@exämple.com", cfg!(feature = "idna")),
+    ("john!#$%&'*/=?^`{|}~@example.com", false),
+    (
+        "very.long.username.with.many.dots.and.plus+tag@example.com",
+        true,
+    ),
+    ("john@sub.sub.sub.example.com", true),
+    ("1234567890@example.com", true),
+];
+
+/// Passwords rejected by the default [`PasswordStrengthChecker`](crate::PasswordStrengthChecker).
+pub const WEAK_PASSWORDS: &[&str] = &[
+    "password",
+    "123456789",
+    "qwertyuiop",
+    "iloveyou",
+    "Password1",
+    "P@ssw0rd",
+    "letmein123",
+    "aaaaaaaaaaaa",
+    "abcdefghij",
+    "john1990",
+];
+
+/// Passwords accepted by the default [`PasswordStrengthChecker`](crate::PasswordStrengthChecker).
+pub const STRONG_PASSWORDS: &[&str] = &[
+    "ThisIsAPassPhrase.And.Secure.Password",
+    "correct.horse.battery.staple",
+    "Tr0ub4dor&3-mango-Velvet",
+    "u8#Kq2!vLz@9mWx",
+    "purple-monkey-dishwasher-42",
+    "Gl4ss.Tiger.Orbit.Lantern",
+];
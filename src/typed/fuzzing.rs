@@ -0,0 +1,17 @@
+use crate::errors::EmailError;
+use crate::typed::{address_list, named_email};
+
+/// See [`Email::parse_list`](crate::Email::parse_list).
+pub fn split_mailboxes(list: &str) -> Vec<&str> {
+    address_list::split_mailboxes(list)
+}
+
+/// Extracts the address of a mailbox, with the form `addr` or `Display Name <addr>`.
+pub fn mailbox_address(mailbox: &str) -> Result<&str, EmailError> {
+    address_list::mailbox_address(mailbox)
+}
+
+/// Decodes the RFC 2047 encoded-words of a display name.
+pub fn decode_encoded_words(text: &str) -> String {
+    named_email::decode_encoded_words(text)
+}
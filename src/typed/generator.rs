@@ -0,0 +1,58 @@
+#[cfg(feature = "password")]
+use crate::typed::password::{Password, Raw};
+
+/// Characters of the generated passwords, without the ambiguous `0`, `O`, `1`, `l` and `I`.
+#[cfg(feature = "password")]
+const PASSWORD_ALPHABET: &[u8] =
+    b"abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23456789!#$%&*+-.=?@_";
+
+/// Fills the buffer with bytes of the OS secure random generator.
+///
+/// # Panics
+///
+/// Panics if the OS random generator is not available.
+pub(crate) fn fill_random(buffer: &mut [u8]) {
+    getrandom::getrandom(buffer).expect("the OS random generator is not available");
+}
+
+/// Generates a random string with characters of the alphabet, without modulo bias.
+#[cfg(feature = "password")]
+pub(crate) fn random_string(len: usize, alphabet: &[u8]) -> String {
+    // Greatest multiple of the alphabet length that fits in a byte
+    let limit = 256 - 256 % alphabet.len();
+    let mut value = String::with_capacity(len);
+    let mut buffer = [0u8; 64];
+
+    while value.len() < len {
+        fill_random(&mut buffer);
+        value.extend(
+            buffer
+                .iter()
+                .filter(|byte| (**byte as usize) < limit)
+                .map(|byte| alphabet[*byte as usize % alphabet.len()] as char)
+                .take(len - value.len()),
+        );
+    }
+
+    value
+}
+
+#[cfg(feature = "password")]
+impl Password {
+    /// Generates a random password with letters, digits and symbols, using the
+    /// OS secure random generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS random generator is not available.
+    ///
+    /// ```
+    /// use email_pass::Password;
+    ///
+    /// let password = Password::generate(20).check();
+    /// assert!(password.is_ok());
+    /// ```
+    pub fn generate(len: usize) -> Password<Raw> {
+        Password::new(&random_string(len, PASSWORD_ALPHABET))
+    }
+}
@@ -0,0 +1,295 @@
+use std::borrow::Cow;
+
+use base64::alphabet::BCRYPT;
+use base64::engine::general_purpose::{
+    GeneralPurpose, GeneralPurposeConfig, STANDARD, STANDARD_NO_PAD,
+};
+use base64::engine::DecodePaddingMode;
+use base64::Engine;
+
+use crate::errors::PasswordError;
+use crate::typed::password::{Encrypt, Password, MAX_COST, MIN_COST};
+use crate::typed::rehash::{HashAlgorithm, DJANGO_PBKDF2_PREFIX};
+
+/// Length of the salt of the bcrypt hashes, in bcrypt base64 characters.
+const BCRYPT_SALT_LEN: usize = 22;
+/// Length of the salt and the hash of the bcrypt hashes, in bcrypt base64 characters.
+const BCRYPT_SALT_HASH_LEN: usize = 53;
+/// Length of the decoded hash of the bcrypt hashes, in bytes.
+const BCRYPT_DIGEST_LEN: usize = 23;
+/// Prefix of the key id appended to the hashes peppered with a `KeyRing`, like
+/// `$2b$12$...$k=2`.
+pub(crate) const KEY_ID_PREFIX: &str = "k=";
+
+/// Base64 of the bcrypt hashes, with the `./A-Za-z0-9` alphabet and without padding.
+const BCRYPT_BASE64: GeneralPurpose = GeneralPurpose::new(
+    &BCRYPT,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(DecodePaddingMode::RequireNone)
+        .with_decode_allow_trailing_bits(true),
+);
+
+/// Fields of an encrypted password, parsed from its modular crypt format, like
+/// `$2b$12$...`, its PHC string, like `$argon2id$v=19$m=19456,t=2,p=1$...`, or its
+/// Django format, like `pbkdf2_sha256$600000$...`.
+///
+/// The cost is parsed from the header, so it's available even if the salt or the
+/// hash are malformed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HashParts<'a> {
+    pub(crate) algorithm: HashAlgorithm,
+    pub(crate) cost: Option<u32>,
+    pub(crate) salt: Option<&'a str>,
+    pub(crate) hash: Option<&'a str>,
+}
+
+impl<'a> HashParts<'a> {
+    /// Parses the hash of a recognized algorithm, ignoring the key id of a `KeyRing`.
+    pub(crate) fn parse(hash: &'a str) -> Option<Self> {
+        let (hash, _) = split_key_id(hash);
+        let algorithm = HashAlgorithm::detect(hash)?;
+        let parts = match algorithm {
+            HashAlgorithm::Bcrypt => Self::parse_bcrypt(hash),
+            HashAlgorithm::Argon2 => Self::parse_phc(hash, algorithm, "t="),
+            HashAlgorithm::Scrypt => Self::parse_phc(hash, algorithm, "ln="),
+            HashAlgorithm::Pbkdf2Sha256 => Self::parse_django(hash),
+        };
+
+        Some(parts)
+    }
+
+    /// Decodes the hash, with the base64 variant of the algorithm.
+    pub(crate) fn hash_bytes(&self) -> Option<Vec<u8>> {
+        let hash = self.hash?;
+        match self.algorithm {
+            HashAlgorithm::Bcrypt => BCRYPT_BASE64.decode(hash).ok(),
+            HashAlgorithm::Argon2 | HashAlgorithm::Scrypt => STANDARD_NO_PAD.decode(hash).ok(),
+            HashAlgorithm::Pbkdf2Sha256 => STANDARD.decode(hash).ok(),
+        }
+    }
+
+    fn empty(algorithm: HashAlgorithm) -> Self {
+        Self {
+            algorithm,
+            cost: None,
+            salt: None,
+            hash: None,
+        }
+    }
+
+    /// `$2b$12$` followed by 22 characters of salt and 31 characters of hash.
+    fn parse_bcrypt(hash: &'a str) -> Self {
+        let mut parts = Self::empty(HashAlgorithm::Bcrypt);
+        let mut segments = hash.splitn(4, '$').skip(2);
+        parts.cost = segments.next().and_then(|cost| cost.parse().ok());
+
+        let salt_hash = segments.next().unwrap_or_default();
+        let is_well_formed = salt_hash.len() == BCRYPT_SALT_HASH_LEN
+            && salt_hash
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'/'));
+        if is_well_formed {
+            let (salt, hash) = salt_hash.split_at(BCRYPT_SALT_LEN);
+            parts.salt = Some(salt);
+            parts.hash = Some(hash);
+        }
+
+        parts
+    }
+
+    /// `$id$v=19$params$salt$hash`, the version is optional.
+    fn parse_phc(hash: &'a str, algorithm: HashAlgorithm, cost_param: &str) -> Self {
+        let mut parts = Self::empty(algorithm);
+        let mut segments = hash.split('$').skip(2).peekable();
+        segments.next_if(|segment| segment.starts_with("v="));
+
+        let Some(params) = segments.next() else {
+            return parts;
+        };
+        parts.cost = params
+            .split(',')
+            .find_map(|param| param.strip_prefix(cost_param))
+            .and_then(|cost| cost.parse().ok());
+
+        let salt_hash: Vec<&str> = segments.collect();
+        if let [salt, hash] = salt_hash[..] {
+            parts.salt = Some(salt).filter(|salt| !salt.is_empty());
+            parts.hash = Some(hash).filter(|hash| !hash.is_empty());
+        }
+
+        parts
+    }
+
+    /// `pbkdf2_sha256$iterations$salt$hash`.
+    fn parse_django(hash: &'a str) -> Self {
+        let mut parts = Self::empty(HashAlgorithm::Pbkdf2Sha256);
+        let segments: Vec<&str> = hash[DJANGO_PBKDF2_PREFIX.len()..].split('$').collect();
+        parts.cost = segments.first().and_then(|cost| cost.parse().ok());
+
+        if let [_, salt, hash] = segments[..] {
+            parts.salt = Some(salt).filter(|salt| !salt.is_empty());
+            parts.hash = Some(hash).filter(|hash| !hash.is_empty());
+        }
+
+        parts
+    }
+}
+
+impl Password {
+    /// Assembles the hash stored in separate columns, like the salt and the digest, the
+    /// inverse of [`Password::cost`], [`Password::salt`] and [`Password::hash_bytes`].
+    ///
+    /// * Bcrypt: the cost, the 22 characters of the bcrypt base64 salt, and the 23 bytes
+    ///   of the digest. The hash has the `$2b$` version.
+    /// * PBKDF2-HMAC-SHA256: the iterations, the salt and the digest, with the Django
+    ///   format.
+    ///
+    /// The argon2 and scrypt hashes have more parameters than the cost, store the whole
+    /// PHC string and use [`Password::from_encrypt`].
+    ///
+    /// ```
+    /// use email_pass::{HashAlgorithm, Password};
+    ///
+    /// let stored = Password::from_encrypt("$2b$04$teRReyH3sVfCd8JA71Sm6xekdy6KhRIzYYERUEUC/.mS2Ms3ByM.q")?;
+    /// let (salt, digest) = (stored.salt().unwrap(), stored.hash_bytes().unwrap());
+    ///
+    /// let password = Password::from_parts(HashAlgorithm::Bcrypt, 4, salt, &digest)?;
+    /// assert_eq!(password, stored);
+    /// # Ok::<(), email_pass::PasswordError>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `PasswordError::InvalidCost` - If the bcrypt cost is not between [`MIN_COST`] and [`MAX_COST`].
+    /// * `PasswordError::InvalidHashParts` - If the salt or the digest are invalid for the
+    ///   algorithm, the iterations are zero, or the algorithm is argon2 or scrypt.
+    pub fn from_parts(
+        algorithm: HashAlgorithm,
+        cost: u32,
+        salt: &str,
+        digest: &[u8],
+    ) -> Result<Password<Encrypt>, PasswordError> {
+        let hash = match algorithm {
+            HashAlgorithm::Bcrypt => {
+                if !(MIN_COST..=MAX_COST).contains(&cost) {
+                    return Err(PasswordError::InvalidCost {
+                        given: cost,
+                        min: MIN_COST,
+                        max: MAX_COST,
+                    });
+                }
+                let is_valid_salt = salt.len() == BCRYPT_SALT_LEN
+                    && salt
+                        .bytes()
+                        .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'/'));
+                if !is_valid_salt || digest.len() != BCRYPT_DIGEST_LEN {
+                    return Err(PasswordError::InvalidHashParts);
+                }
+
+                format!("$2b${cost:02}${salt}{}", BCRYPT_BASE64.encode(digest))
+            }
+            HashAlgorithm::Pbkdf2Sha256 => {
+                if cost == 0 || salt.is_empty() || salt.contains('$') || digest.is_empty() {
+                    return Err(PasswordError::InvalidHashParts);
+                }
+
+                format!(
+                    "{DJANGO_PBKDF2_PREFIX}{cost}${salt}${}",
+                    STANDARD.encode(digest)
+                )
+            }
+            HashAlgorithm::Argon2 | HashAlgorithm::Scrypt => {
+                return Err(PasswordError::InvalidHashParts)
+            }
+        };
+
+        Password::from_encrypt(&hash).map_err(|_| PasswordError::InvalidHashParts)
+    }
+}
+
+/// Splits the hash and the key id appended by a `KeyRing`, if any. The hashes of every
+/// algorithm are recognized with the key id, even without the `pepper` feature.
+pub(crate) fn split_key_id(hash: &str) -> (&str, Option<u32>) {
+    let key_id = hash
+        .rsplit_once('$')
+        .and_then(|(hash, suffix)| Some((hash, suffix.strip_prefix(KEY_ID_PREFIX)?.parse().ok()?)));
+
+    match key_id {
+        Some((hash, id)) => (hash, Some(id)),
+        None => (hash, None),
+    }
+}
+
+/// Returns a numeric parameter of a PHC string, like the `m` of
+/// `$argon2id$v=19$m=19456,t=2,p=1$...`.
+#[cfg(feature = "argon2")]
+pub(crate) fn phc_param(hash: &str, name: &str) -> Option<u32> {
+    let (hash, _) = split_key_id(hash);
+    let mut segments = hash.split('$').skip(2).peekable();
+    segments.next_if(|segment| segment.starts_with("v="));
+
+    segments.next()?.split(',').find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        (key == name).then(|| value.parse().ok()).flatten()
+    })
+}
+
+/// Checks the version of the bcrypt hashes, the `2?` of `$2?$cost$...`, and normalizes
+/// the PHP `$2y$` hashes to `$2b$`, the same algorithm with the OpenBSD prefix. The
+/// `$2a$` and `$2x$` hashes are kept, their meaning depends on the implementation that
+/// created them. The hashes of other algorithms are not changed.
+///
+/// # Errors
+///
+/// * `PasswordError::UnknownBcryptVersion` - If the version is not `2a`, `2b`, `2x` or `2y`.
+pub(crate) fn normalize_bcrypt_version(hash: &str) -> Result<Cow<'_, str>, PasswordError> {
+    let version = hash
+        .strip_prefix('$')
+        .and_then(|hash| hash.split('$').next())
+        .unwrap_or_default();
+
+    match version {
+        "2a" | "2b" | "2x" => Ok(Cow::Borrowed(hash)),
+        "2y" => Ok(Cow::Owned(format!("$2b{}", &hash[3..]))),
+        version if version.starts_with('2') => {
+            Err(PasswordError::UnknownBcryptVersion(version.to_string()))
+        }
+        _ => Ok(Cow::Borrowed(hash)),
+    }
+}
+
+impl<Family> Password<Encrypt<Family>> {
+    /// Returns the algorithm of the hash, or `None` if it's not recognized.
+    pub fn algorithm(&self) -> Option<HashAlgorithm> {
+        HashAlgorithm::detect(self.as_ref())
+    }
+
+    /// Returns the cost of the hash: the cost of bcrypt, the iterations of argon2 and
+    /// PBKDF2, or the `log2(N)` of scrypt.
+    ///
+    /// ```
+    /// use email_pass::Password;
+    ///
+    /// let password = Password::from_encrypt("$2b$04$teRReyH3sVfCd8JA71Sm6xekdy6KhRIzYYERUEUC/.mS2Ms3ByM.q")?;
+    /// assert_eq!(password.cost(), Some(4));
+    /// assert_eq!(password.salt(), Some("teRReyH3sVfCd8JA71Sm6x"));
+    /// assert_eq!(password.hash_bytes().map(|hash| hash.len()), Some(23));
+    /// # Ok::<(), email_pass::PasswordError>(())
+    /// ```
+    pub fn cost(&self) -> Option<u32> {
+        HashParts::parse(self.as_ref())?.cost
+    }
+
+    /// Returns the encoded salt of the hash, or `None` if the hash is malformed or its
+    /// algorithm is not recognized.
+    pub fn salt(&self) -> Option<&str> {
+        HashParts::parse(self.as_ref())?.salt
+    }
+
+    /// Returns the decoded hash, without the salt and the parameters, or `None` if the
+    /// hash is malformed or its algorithm is not recognized.
+    pub fn hash_bytes(&self) -> Option<Vec<u8>> {
+        HashParts::parse(self.as_ref())?.hash_bytes()
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::typed::{
+    hash_parts::split_key_id,
+    password::Password,
+    rehash::{hash_cost, HashAlgorithm, RehashPolicy},
+};
+
+/// Length of the bcrypt hashes.
+const BCRYPT_HASH_LEN: usize = 60;
+
+/// Summary of a store of encrypted passwords, like the password column of a user
+/// table, used to plan a migration. See [`HashStoreReport::scan`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HashStoreReport {
+    pub total: usize,
+    /// Count of hashes by recognized algorithm.
+    pub algorithms: HashMap<HashAlgorithm, usize>,
+    /// Count of hashes by algorithm and cost, the costs of different algorithms are not
+    /// comparable.
+    pub costs: BTreeMap<(HashAlgorithm, u32), usize>,
+    /// Positions of the values that are not hashes, hashes of unrecognized algorithms,
+    /// or truncated hashes.
+    pub malformed: Vec<usize>,
+    /// Positions of the well-formed hashes that require a rehash according to the policy.
+    pub below_policy: Vec<usize>,
+}
+
+impl HashStoreReport {
+    /// Scans the stored hashes, in order.
+    ///
+    /// ```
+    /// use email_pass::{HashStoreReport, RehashPolicy};
+    ///
+    /// let hashes = [
+    ///     "$2b$04$teRReyH3sVfCd8JA71Sm6xekdy6KhRIzYYERUEUC/.mS2Ms3ByM.q",
+    ///     "plain-text",
+    /// ];
+    /// let report = HashStoreReport::scan(hashes, &RehashPolicy::new());
+    /// assert_eq!(report.malformed, [1]);
+    /// assert_eq!(report.below_policy, [0]);
+    /// ```
+    pub fn scan<I, S>(hashes: I, policy: &RehashPolicy) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut report = Self::default();
+
+        for (index, hash) in hashes.into_iter().enumerate() {
+            report.total += 1;
+
+            let Ok(password) = Password::from_encrypt(hash.as_ref()) else {
+                report.malformed.push(index);
+                continue;
+            };
+
+            let (hash, _) = split_key_id(password.as_str());
+            let algorithm = match HashAlgorithm::detect(hash) {
+                Some(HashAlgorithm::Bcrypt) if hash.len() != BCRYPT_HASH_LEN => None,
+                algorithm => algorithm,
+            };
+            let Some(algorithm) = algorithm else {
+                report.malformed.push(index);
+                continue;
+            };
+
+            *report.algorithms.entry(algorithm).or_default() += 1;
+            if let Some(cost) = hash_cost(hash) {
+                *report.costs.entry((algorithm, cost)).or_default() += 1;
+            }
+
+            if policy.requires_rehash(&password) {
+                report.below_policy.push(index);
+            }
+        }
+
+        report
+    }
+}
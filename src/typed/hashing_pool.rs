@@ -0,0 +1,133 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use crate::errors::PasswordError;
+use crate::typed::password::{Encrypt, Password, Raw};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Bounded set of worker threads for the hash and verify operations, so a burst
+/// of logins can't use every core with bcrypt work.
+///
+/// The jobs wait in a bounded queue; when it's full, the callers are blocked
+/// until a worker takes a job. Dropping the pool waits for the queued jobs.
+///
+/// ```
+/// use email_pass::{HashingPool, Password};
+///
+/// let pool = HashingPool::new(2, 16);
+///
+/// let raw_password = Password::new("ThisIsAPassPhrase.And.Secure.Password");
+/// let encrypt_password = pool.hash(raw_password.clone(), 4)?;
+/// assert_eq!(pool.verify(&encrypt_password, &raw_password), Ok(true));
+/// # Ok::<(), email_pass::PasswordError>(())
+/// ```
+pub struct HashingPool {
+    sender: Option<SyncSender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl HashingPool {
+    /// Creates a pool with `workers` threads and a queue of `queue_len` jobs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `workers` is zero.
+    pub fn new(workers: usize, queue_len: usize) -> Self {
+        assert!(workers > 0, "the pool needs at least one worker");
+
+        let (sender, receiver) = mpsc::sync_channel::<Job>(queue_len);
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..workers)
+            .map(|index| {
+                let receiver = Arc::clone(&receiver);
+                thread::Builder::new()
+                    .name(format!("email_pass-hashing-{index}"))
+                    .spawn(move || worker(&receiver))
+                    .expect("failed to spawn a hashing worker")
+            })
+            .collect();
+
+        Self {
+            sender: Some(sender),
+            workers,
+        }
+    }
+
+    /// Returns the count of worker threads.
+    pub fn workers(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Encrypts the raw password in a worker. See [`Password<Raw>::to_encrypt`].
+    pub fn hash(
+        &self,
+        raw_password: Password<Raw>,
+        cost: u32,
+    ) -> Result<Password<Encrypt>, PasswordError> {
+        self.run(move || raw_password.to_encrypt(cost))
+            .unwrap_or(Err(PasswordError::PasswordEncryption))
+    }
+
+    /// Verifies the raw password in a worker. See [`Password<Encrypt>::verify`].
+    ///
+    /// # Errors
+    ///
+    /// * `PasswordError::PasswordVerification` - If bcrypt fails.
+    pub fn verify(
+        &self,
+        encrypt_password: &Password<Encrypt>,
+        raw_password: &Password<Raw>,
+    ) -> Result<bool, PasswordError> {
+        let (encrypt_password, raw_password) = (encrypt_password.clone(), raw_password.clone());
+        self.run(move || encrypt_password.verify(&raw_password))
+            .and_then(Result::ok)
+            .ok_or(PasswordError::PasswordVerification)
+    }
+
+    /// Queues the job, blocking while the queue is full, and waits for its result.
+    /// Returns `None` if the job panicked; the worker keeps running the next jobs.
+    pub(crate) fn run<T, F>(&self, job: F) -> Option<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let (result_sender, result_receiver) = mpsc::channel();
+        let job: Job = Box::new(move || {
+            let _ = result_sender.send(job());
+        });
+
+        self.sender.as_ref()?.send(job).ok()?;
+        result_receiver.recv().ok()
+    }
+}
+
+fn worker(receiver: &Mutex<Receiver<Job>>) {
+    loop {
+        // The lock is released before running the job
+        let job = match receiver.lock() {
+            Ok(receiver) => receiver.recv(),
+            Err(_) => return,
+        };
+
+        match job {
+            // A panicking job drops its result sender, so the caller gets an error
+            Ok(job) => {
+                let _ = panic::catch_unwind(AssertUnwindSafe(job));
+            }
+            Err(_) => return,
+        }
+    }
+}
+
+impl Drop for HashingPool {
+    fn drop(&mut self) {
+        // Closing the queue stops the workers after the queued jobs
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use zxcvbn::feedback::{Feedback, Suggestion, Warning};
+
+use crate::typed::password_checker::PasswordStrengthChecker;
+use crate::typed::wordlist::Language;
+
+/// [`zxcvbn`] feedback translated to a [`Language`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocalizedFeedback {
+    /// Explains what's wrong with the password. Not always set.
+    pub warning: Option<&'static str>,
+    /// Suggestions to choose a less guessable password.
+    pub suggestions: Vec<&'static str>,
+}
+
+impl Language {
+    /// Translates the feedback of [`zxcvbn::Entropy::feedback`].
+    pub fn feedback(&self, feedback: &Feedback) -> LocalizedFeedback {
+        LocalizedFeedback {
+            warning: feedback.warning().map(|warning| self.warning(warning)),
+            suggestions: feedback
+                .suggestions()
+                .iter()
+                .map(|suggestion| self.suggestion(*suggestion))
+                .collect(),
+        }
+    }
+
+    /// Translates a [`zxcvbn`] warning.
+    pub fn warning(&self, warning: Warning) -> &'static str {
+        use Warning::*;
+
+        match self {
+            Language::Spanish => match warning {
+                StraightRowsOfKeysAreEasyToGuess => {
+                    "Las filas rectas de teclas son fáciles de adivinar."
+                }
+                ShortKeyboardPatternsAreEasyToGuess => {
+                    "Los patrones cortos de teclado son fáciles de adivinar."
+                }
+                RepeatsLikeAaaAreEasyToGuess => {
+                    "Las repeticiones como \"aaa\" son fáciles de adivinar."
+                }
+                RepeatsLikeAbcAbcAreOnlySlightlyHarderToGuess => {
+                    "Las repeticiones como \"abcabcabc\" son apenas más difíciles de adivinar que \"abc\"."
+                }
+                ThisIsATop10Password => "Esta es una de las 10 contraseñas más comunes.",
+                ThisIsATop100Password => "Esta es una de las 100 contraseñas más comunes.",
+                ThisIsACommonPassword => "Esta es una contraseña muy común.",
+                ThisIsSimilarToACommonlyUsedPassword => {
+                    "Esta contraseña es parecida a una contraseña muy usada."
+                }
+                SequencesLikeAbcAreEasyToGuess => {
+                    "Las secuencias como abc o 6543 son fáciles de adivinar."
+                }
+                RecentYearsAreEasyToGuess => "Los años recientes son fáciles de adivinar.",
+                AWordByItselfIsEasyToGuess => "Una palabra por sí sola es fácil de adivinar.",
+                DatesAreOftenEasyToGuess => "Las fechas suelen ser fáciles de adivinar.",
+                NamesAndSurnamesByThemselvesAreEasyToGuess => {
+                    "Los nombres y apellidos por sí solos son fáciles de adivinar."
+                }
+                CommonNamesAndSurnamesAreEasyToGuess => {
+                    "Los nombres y apellidos comunes son fáciles de adivinar."
+                }
+            },
+            Language::Portuguese => match warning {
+                StraightRowsOfKeysAreEasyToGuess => {
+                    "Sequências retas de teclas são fáceis de adivinhar."
+                }
+                ShortKeyboardPatternsAreEasyToGuess => {
+                    "Padrões curtos de teclado são fáceis de adivinhar."
+                }
+                RepeatsLikeAaaAreEasyToGuess => "Repetições como \"aaa\" são fáceis de adivinhar.",
+                RepeatsLikeAbcAbcAreOnlySlightlyHarderToGuess => {
+                    "Repetições como \"abcabcabc\" são só um pouco mais difíceis de adivinhar que \"abc\"."
+                }
+                ThisIsATop10Password => "Esta é uma das 10 senhas mais comuns.",
+                ThisIsATop100Password => "Esta é uma das 100 senhas mais comuns.",
+                ThisIsACommonPassword => "Esta é uma senha muito comum.",
+                ThisIsSimilarToACommonlyUsedPassword => {
+                    "Esta senha é parecida com uma senha muito usada."
+                }
+                SequencesLikeAbcAreEasyToGuess => {
+                    "Sequências como abc ou 6543 são fáceis de adivinhar."
+                }
+                RecentYearsAreEasyToGuess => "Anos recentes são fáceis de adivinhar.",
+                AWordByItselfIsEasyToGuess => "Uma palavra sozinha é fácil de adivinhar.",
+                DatesAreOftenEasyToGuess => "Datas costumam ser fáceis de adivinhar.",
+                NamesAndSurnamesByThemselvesAreEasyToGuess => {
+                    "Nomes e sobrenomes sozinhos são fáceis de adivinhar."
+                }
+                CommonNamesAndSurnamesAreEasyToGuess => {
+                    "Nomes e sobrenomes comuns são fáceis de adivinhar."
+                }
+            },
+            Language::German => match warning {
+                StraightRowsOfKeysAreEasyToGuess => "Gerade Tastenreihen sind leicht zu erraten.",
+                ShortKeyboardPatternsAreEasyToGuess => {
+                    "Kurze Tastaturmuster sind leicht zu erraten."
+                }
+                RepeatsLikeAaaAreEasyToGuess => {
+                    "Wiederholungen wie \"aaa\" sind leicht zu erraten."
+                }
+                RepeatsLikeAbcAbcAreOnlySlightlyHarderToGuess => {
+                    "Wiederholungen wie \"abcabcabc\" sind nur etwas schwerer zu erraten als \"abc\"."
+                }
+                ThisIsATop10Password => "Dies ist eines der 10 häufigsten Passwörter.",
+                ThisIsATop100Password => "Dies ist eines der 100 häufigsten Passwörter.",
+                ThisIsACommonPassword => "Dies ist ein sehr häufiges Passwort.",
+                ThisIsSimilarToACommonlyUsedPassword => {
+                    "Dies ähnelt einem häufig verwendeten Passwort."
+                }
+                SequencesLikeAbcAreEasyToGuess => {
+                    "Folgen wie abc oder 6543 sind leicht zu erraten."
+                }
+                RecentYearsAreEasyToGuess => {
+                    "Jahreszahlen der letzten Jahre sind leicht zu erraten."
+                }
+                AWordByItselfIsEasyToGuess => "Ein einzelnes Wort ist leicht zu erraten.",
+                DatesAreOftenEasyToGuess => "Datumsangaben sind oft leicht zu erraten.",
+                NamesAndSurnamesByThemselvesAreEasyToGuess => {
+                    "Vor- und Nachnamen allein sind leicht zu erraten."
+                }
+                CommonNamesAndSurnamesAreEasyToGuess => {
+                    "Häufige Vor- und Nachnamen sind leicht zu erraten."
+                }
+            },
+        }
+    }
+
+    /// Translates a [`zxcvbn`] suggestion.
+    pub fn suggestion(&self, suggestion: Suggestion) -> &'static str {
+        use Suggestion::*;
+
+        match self {
+            Language::Spanish => match suggestion {
+                UseAFewWordsAvoidCommonPhrases => "Usa varias palabras, evita las frases comunes.",
+                NoNeedForSymbolsDigitsOrUppercaseLetters => {
+                    "No hacen falta símbolos, dígitos ni mayúsculas."
+                }
+                AddAnotherWordOrTwo => {
+                    "Agrega una o dos palabras más. Las palabras poco comunes son mejores."
+                }
+                CapitalizationDoesntHelpVeryMuch => "Usar mayúsculas no ayuda mucho.",
+                AllUppercaseIsAlmostAsEasyToGuessAsAllLowercase => {
+                    "Todo en mayúsculas es casi tan fácil de adivinar como todo en minúsculas."
+                }
+                ReversedWordsArentMuchHarderToGuess => {
+                    "Las palabras al revés no son mucho más difíciles de adivinar."
+                }
+                PredictableSubstitutionsDontHelpVeryMuch => {
+                    "Las sustituciones predecibles como '@' en lugar de 'a' no ayudan mucho."
+                }
+                UseALongerKeyboardPatternWithMoreTurns => {
+                    "Usa un patrón de teclado más largo y con más giros."
+                }
+                AvoidRepeatedWordsAndCharacters => "Evita las palabras y los caracteres repetidos.",
+                AvoidSequences => "Evita las secuencias.",
+                AvoidRecentYears => "Evita los años recientes.",
+                AvoidYearsThatAreAssociatedWithYou => "Evita los años relacionados contigo.",
+                AvoidDatesAndYearsThatAreAssociatedWithYou => {
+                    "Evita las fechas y los años relacionados contigo."
+                }
+            },
+            Language::Portuguese => match suggestion {
+                UseAFewWordsAvoidCommonPhrases => "Use algumas palavras, evite frases comuns.",
+                NoNeedForSymbolsDigitsOrUppercaseLetters => {
+                    "Não são necessários símbolos, dígitos ou letras maiúsculas."
+                }
+                AddAnotherWordOrTwo => {
+                    "Adicione mais uma ou duas palavras. Palavras incomuns são melhores."
+                }
+                CapitalizationDoesntHelpVeryMuch => "Usar maiúsculas não ajuda muito.",
+                AllUppercaseIsAlmostAsEasyToGuessAsAllLowercase => {
+                    "Tudo em maiúsculas é quase tão fácil de adivinhar quanto tudo em minúsculas."
+                }
+                ReversedWordsArentMuchHarderToGuess => {
+                    "Palavras invertidas não são muito mais difíceis de adivinhar."
+                }
+                PredictableSubstitutionsDontHelpVeryMuch => {
+                    "Substituições previsíveis como '@' no lugar de 'a' não ajudam muito."
+                }
+                UseALongerKeyboardPatternWithMoreTurns => {
+                    "Use um padrão de teclado mais longo e com mais curvas."
+                }
+                AvoidRepeatedWordsAndCharacters => "Evite palavras e caracteres repetidos.",
+                AvoidSequences => "Evite sequências.",
+                AvoidRecentYears => "Evite anos recentes.",
+                AvoidYearsThatAreAssociatedWithYou => "Evite anos associados a você.",
+                AvoidDatesAndYearsThatAreAssociatedWithYou => {
+                    "Evite datas e anos associados a você."
+                }
+            },
+            Language::German => match suggestion {
+                UseAFewWordsAvoidCommonPhrases => {
+                    "Verwende mehrere Wörter und vermeide gängige Redewendungen."
+                }
+                NoNeedForSymbolsDigitsOrUppercaseLetters => {
+                    "Sonderzeichen, Ziffern oder Großbuchstaben sind nicht nötig."
+                }
+                AddAnotherWordOrTwo => {
+                    "Füge ein oder zwei weitere Wörter hinzu. Seltene Wörter sind besser."
+                }
+                CapitalizationDoesntHelpVeryMuch => "Großschreibung hilft nicht viel.",
+                AllUppercaseIsAlmostAsEasyToGuessAsAllLowercase => {
+                    "Nur Großbuchstaben sind fast so leicht zu erraten wie nur Kleinbuchstaben."
+                }
+                ReversedWordsArentMuchHarderToGuess => {
+                    "Rückwärts geschriebene Wörter sind kaum schwerer zu erraten."
+                }
+                PredictableSubstitutionsDontHelpVeryMuch => {
+                    "Vorhersehbare Ersetzungen wie '@' statt 'a' helfen nicht viel."
+                }
+                UseALongerKeyboardPatternWithMoreTurns => {
+                    "Verwende ein längeres Tastaturmuster mit mehr Richtungswechseln."
+                }
+                AvoidRepeatedWordsAndCharacters => "Vermeide wiederholte Wörter und Zeichen.",
+                AvoidSequences => "Vermeide Zeichenfolgen.",
+                AvoidRecentYears => "Vermeide Jahreszahlen der letzten Jahre.",
+                AvoidYearsThatAreAssociatedWithYou => {
+                    "Vermeide Jahreszahlen, die mit dir in Verbindung stehen."
+                }
+                AvoidDatesAndYearsThatAreAssociatedWithYou => {
+                    "Vermeide Daten und Jahreszahlen, die mit dir in Verbindung stehen."
+                }
+            },
+        }
+    }
+}
+
+impl PasswordStrengthChecker {
+    /// Returns the translated feedback of a weak password, to show it next to the
+    /// rejection of [`PasswordStrengthChecker::check`], or `None` for the strong passwords.
+    ///
+    /// ```
+    /// use email_pass::{Language, PasswordStrengthChecker};
+    ///
+    /// let feedback = PasswordStrengthChecker::new()
+    ///     .localized_feedback("password", Language::Spanish)
+    ///     .unwrap();
+    /// assert_eq!(feedback.warning, Some("Esta es una de las 10 contraseñas más comunes."));
+    /// ```
+    pub fn localized_feedback(
+        &self,
+        raw_password: &str,
+        language: Language,
+    ) -> Option<LocalizedFeedback> {
+        let entropy = self.entropy(raw_password, None).ok()?;
+        let feedback = entropy.feedback().as_ref()?;
+        Some(language.feedback(feedback))
+    }
+}
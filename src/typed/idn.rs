@@ -0,0 +1,27 @@
+use std::str::FromStr;
+
+use crate::errors::EmailError;
+use crate::typed::email::Email;
+
+impl Email {
+    /// Parses an email with an internationalized domain, like `john@bücher.example`.
+    /// The domain is stored in its punycode form: `john@xn--bcher-kva.example`.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`Result`] with a [`EmailError`] if the domain can't be converted
+    /// to punycode or the email is not valid.
+    ///
+    pub fn parse_idn(email: &str) -> Result<Self, EmailError> {
+        let (username, domain) = email.rsplit_once('@').ok_or(EmailError::Format)?;
+        let domain = idna::domain_to_ascii_strict(domain).map_err(|_| EmailError::Domain)?;
+
+        Self::from_str(&format!("{username}@{domain}")).map(|parsed| parsed.with_original(email))
+    }
+
+    /// Returns the email with the punycode labels of the domain converted to Unicode.
+    pub fn to_unicode(&self) -> String {
+        let (domain, _) = idna::domain_to_unicode(self.domain());
+        format!("{}@{domain}", self.username())
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+use crate::errors::EmailError;
+use crate::typed::canonical::RuleSet;
+use crate::typed::email::Email;
+
+/// Summary of an import made by [`EmailImporter`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Non blank rows read.
+    pub total: usize,
+    /// Valid and unique emails, delivered to the callback.
+    pub valid: usize,
+    /// Valid emails whose canonical form was already imported.
+    pub duplicates: usize,
+    /// Invalid rows grouped by reason.
+    pub invalid: HashMap<EmailError, usize>,
+}
+
+/// Streaming email importer for list cleaning jobs.
+///
+/// Reads one email per line, or one CSV cell per line, validates and canonicalizes
+/// every email and removes the duplicates. See [`Email::canonical`].
+///
+/// The lines that are not valid UTF-8 are reported as [`EmailError::Format`], like the
+/// malformed emails. The quoted CSV cells with embedded newlines are not supported, every
+/// line is read as a row.
+#[derive(Debug, Copy, Clone)]
+pub struct EmailImporter {
+    column: Option<usize>,
+    delimiter: char,
+    has_header: bool,
+}
+
+impl EmailImporter {
+    pub fn new() -> Self {
+        Self {
+            column: None,
+            delimiter: ',',
+            has_header: false,
+        }
+    }
+
+    /// Reads the emails from the CSV column with the given zero-based index,
+    /// instead of using the whole line.
+    pub fn column(mut self, column: usize) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Skips the first line of the input.
+    pub fn has_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    /// Imports the emails from the reader, calling `on_email` with the canonical form
+    /// of every valid and unique email.
+    ///
+    /// # Returns
+    ///
+    /// Returns the [`ImportReport`], or an [`io::Error`] if the reader fails.
+    pub fn import<R, F>(&self, mut reader: R, mut on_email: F) -> io::Result<ImportReport>
+    where
+        R: BufRead,
+        F: FnMut(Email),
+    {
+        let mut report = ImportReport::default();
+        let mut imported = HashSet::new();
+        let rules = RuleSet::current();
+        let mut buf = Vec::new();
+        if self.has_header {
+            reader.read_until(b'\n', &mut buf)?;
+            buf.clear();
+        }
+
+        while reader.read_until(b'\n', &mut buf)? > 0 {
+            let bytes = buf.strip_suffix(b"\n").unwrap_or(&buf);
+            let bytes = bytes.strip_suffix(b"\r").unwrap_or(bytes);
+            let line = std::str::from_utf8(bytes).map_err(|_| EmailError::Format);
+            if line.is_ok_and(|line| line.trim().is_empty()) {
+                buf.clear();
+                continue;
+            }
+            report.total += 1;
+
+            let email = line.and_then(|line| match self.column {
+                Some(column) => csv_cell(line, column, self.delimiter)
+                    .ok_or(EmailError::Format)
+                    .and_then(|cell| Email::from_str(cell.trim())),
+                None => Email::from_str(line.trim()),
+            });
+            buf.clear();
+
+            match email {
+                Ok(email) => {
+                    let canonical = email.canonical_with(&rules);
+                    if imported.insert(canonical.to_string()) {
+                        report.valid += 1;
+                        on_email(canonical);
+                    } else {
+                        report.duplicates += 1;
+                    }
+                }
+                Err(err) => *report.invalid.entry(err).or_default() += 1,
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+impl Default for EmailImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Extracts a cell of a CSV line, resolving the quoted cells.
+fn csv_cell(line: &str, column: usize, delimiter: char) -> Option<String> {
+    let mut cells = 0;
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            '"' => in_quotes = !in_quotes,
+            _ if char == delimiter && !in_quotes => {
+                if cells == column {
+                    return Some(cell);
+                }
+                cells += 1;
+                cell.clear();
+            }
+            _ => cell.push(char),
+        }
+    }
+
+    (cells == column).then_some(cell)
+}
@@ -0,0 +1,63 @@
+/// Minimum length of the keyboard walks and repeats detected.
+const MIN_PATTERN_LEN: usize = 4;
+
+/// Keyboard layouts whose walks are detected by the [`PasswordStrengthChecker`].
+/// The QWERTY walks are already detected by [`zxcvbn`].
+///
+/// [`PasswordStrengthChecker`]: crate::PasswordStrengthChecker
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum KeyboardLayout {
+    /// French layout.
+    Azerty,
+    /// German layout.
+    Qwertz,
+    /// Russian layout.
+    Cyrillic,
+}
+
+impl KeyboardLayout {
+    fn rows(&self) -> &'static [&'static str] {
+        match self {
+            KeyboardLayout::Azerty => &["azertyuiop", "qsdfghjklm", "wxcvbn"],
+            KeyboardLayout::Qwertz => &["qwertzuiopü", "asdfghjklöä", "yxcvbnm"],
+            KeyboardLayout::Cyrillic => &["йцукенгшщзхъ", "фывапролджэ", "ячсмитьбю"],
+        }
+    }
+
+    #[inline]
+    pub(crate) fn bit(&self) -> u8 {
+        1 << (*self as u8)
+    }
+
+    pub(crate) const ALL: [KeyboardLayout; 3] = [
+        KeyboardLayout::Azerty,
+        KeyboardLayout::Qwertz,
+        KeyboardLayout::Cyrillic,
+    ];
+
+    /// Checks if the password contains a walk of at least four adjacent keys of a row,
+    /// in any direction, like `azer`, `фыва` or `куцй`.
+    pub fn has_walk(&self, raw_password: &str) -> bool {
+        let password: Vec<char> = raw_password.to_lowercase().chars().collect();
+
+        self.rows().iter().any(|row| {
+            let row: Vec<char> = row.chars().collect();
+            let reversed: Vec<char> = row.iter().rev().copied().collect();
+
+            row.windows(MIN_PATTERN_LEN)
+                .chain(reversed.windows(MIN_PATTERN_LEN))
+                .any(|walk| password.windows(MIN_PATTERN_LEN).any(|chars| chars == walk))
+        })
+    }
+
+    /// Checks if the password contains a key of the layout repeated at least four times,
+    /// like `йййй`.
+    pub fn has_repeat(&self, raw_password: &str) -> bool {
+        let password: Vec<char> = raw_password.to_lowercase().chars().collect();
+
+        password.windows(MIN_PATTERN_LEN).any(|chars| {
+            chars.iter().all(|char| *char == chars[0])
+                && self.rows().iter().any(|row| row.contains(chars[0]))
+        })
+    }
+}
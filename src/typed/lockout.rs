@@ -0,0 +1,357 @@
+use std::borrow::Borrow;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::errors::PasswordError;
+use crate::typed::password::{Encrypt, HashFamily, Password, Raw};
+
+/// Minimum count of accounts before the expired accounts are pruned.
+const MIN_PRUNE_LEN: usize = 1024;
+
+/// Limits of the failed verifications of an account, used by [`AttemptTracker`].
+///
+/// When an account reaches `max_failures` failures inside the `window`, it is locked
+/// for the `backoff` duration. Each failure after the lock doubles the duration, up to
+/// `max_backoff`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct LockoutPolicy {
+    max_failures: u32,
+    window: Duration,
+    backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl LockoutPolicy {
+    /// Creates a policy locking the account for 1 minute after 5 failures in 15 minutes,
+    /// up to 1 hour.
+    pub fn new() -> Self {
+        Self {
+            max_failures: 5,
+            window: Duration::from_secs(15 * 60),
+            backoff: Duration::from_secs(60),
+            max_backoff: Duration::from_secs(60 * 60),
+        }
+    }
+
+    /// Sets the failures allowed inside the window before locking the account.
+    pub fn max_failures(mut self, max_failures: u32) -> Self {
+        self.max_failures = max_failures.max(1);
+        self
+    }
+
+    /// Sets the duration the failures are remembered.
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Sets the duration of the first lock.
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Sets the maximum duration of a lock.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Returns the lock duration after the given count of failures inside the window.
+    fn lock_duration(&self, failures: u32) -> Option<Duration> {
+        let extra = failures.checked_sub(self.max_failures)?;
+        let factor = 1u32.checked_shl(extra).unwrap_or(u32::MAX);
+        Some(
+            self.backoff
+                .checked_mul(factor)
+                .unwrap_or(self.max_backoff)
+                .min(self.max_backoff),
+        )
+    }
+}
+
+impl Default for LockoutPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decision of an [`AttemptTracker`] about a login attempt.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LockoutDecision {
+    /// The attempt was allowed, and the password was verified with this result.
+    Allowed { verified: bool },
+    /// The account is locked, and the password was not verified.
+    Locked { retry_after: Duration },
+}
+
+impl LockoutDecision {
+    /// Returns `true` only if the attempt was allowed and the password is correct.
+    pub fn is_verified(&self) -> bool {
+        matches!(self, LockoutDecision::Allowed { verified: true })
+    }
+}
+
+#[derive(Debug, Default)]
+struct Attempts {
+    failures: VecDeque<Instant>,
+    locked_until: Option<Instant>,
+}
+
+impl Attempts {
+    /// Checks if the failures are outside the window and the lock is over.
+    fn is_expired(&self, window: Duration, now: Instant) -> bool {
+        let is_unlocked = self
+            .locked_until
+            .is_none_or(|locked_until| locked_until <= now);
+        let has_failures = self
+            .failures
+            .back()
+            .is_some_and(|&newest| now.saturating_duration_since(newest) < window);
+
+        is_unlocked && !has_failures
+    }
+}
+
+/// Failure recorded before verifying a password, rolled back if the verification fails.
+struct Reservation {
+    failure: Instant,
+    locked_until: Option<Instant>,
+    previous_lock: Option<Instant>,
+}
+
+/// Thread-safe record of the failed login attempts by account, to throttle brute-force
+/// attacks with a [`LockoutPolicy`].
+///
+/// The accounts are identified by a key of type `K`, like the user id or the lowercased
+/// email address. The accounts without failures inside the window and without lock are
+/// pruned when the record grows, so it doesn't grow without bound. The clock is not
+/// available in `wasm32-unknown-unknown`, so use the `*_at` methods there.
+///
+/// ```
+/// use email_pass::{AttemptTracker, LockoutDecision, LockoutPolicy, Password};
+///
+/// let tracker = AttemptTracker::new(LockoutPolicy::new().max_failures(3));
+/// let password = Password::new("ThisIsAPassPhrase.And.Secure.Password").to_encrypt(4)?;
+///
+/// let decision = tracker.verify("john", &password, &Password::new("wrong"))?;
+/// assert_eq!(decision, LockoutDecision::Allowed { verified: false });
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct AttemptTracker<K = String> {
+    policy: LockoutPolicy,
+    attempts: Mutex<HashMap<K, Attempts>>,
+    /// Count of accounts that triggers the next pruning, only accessed with the lock.
+    prune_len: AtomicUsize,
+}
+
+impl<K: Hash + Eq> AttemptTracker<K> {
+    pub fn new(policy: LockoutPolicy) -> Self {
+        Self {
+            policy,
+            attempts: Mutex::new(HashMap::new()),
+            prune_len: AtomicUsize::new(MIN_PRUNE_LEN),
+        }
+    }
+
+    pub fn policy(&self) -> &LockoutPolicy {
+        &self.policy
+    }
+
+    /// Verifies the raw password, unless the account is locked.
+    /// A failed verification is recorded, and a successful one clears the failures.
+    ///
+    /// The attempt is recorded as a failure before the slow verification, so the
+    /// concurrent attempts of the account can't exceed the policy, and it's rolled back
+    /// if the password matches or the hash can't be verified.
+    ///
+    /// # Errors
+    /// * `PasswordError::InvalidHash` - If the hash can't be verified. The error is not
+    ///   recorded as a failure.
+    pub fn verify<Q, Family>(
+        &self,
+        key: &Q,
+        encrypt_password: &Password<Encrypt<Family>>,
+        raw_password: &Password<Raw>,
+    ) -> Result<LockoutDecision, PasswordError>
+    where
+        Q: ToOwned<Owned = K> + Hash + Eq + ?Sized,
+        K: Borrow<Q>,
+        Family: HashFamily,
+    {
+        self.verify_at(key, encrypt_password, raw_password, Instant::now())
+    }
+
+    /// Like [`AttemptTracker::verify`], at the given instant.
+    pub fn verify_at<Q, Family>(
+        &self,
+        key: &Q,
+        encrypt_password: &Password<Encrypt<Family>>,
+        raw_password: &Password<Raw>,
+        now: Instant,
+    ) -> Result<LockoutDecision, PasswordError>
+    where
+        Q: ToOwned<Owned = K> + Hash + Eq + ?Sized,
+        K: Borrow<Q>,
+        Family: HashFamily,
+    {
+        let reservation = {
+            let mut attempts = self.lock();
+            if let Some(retry_after) = Self::remaining_lock(&attempts, key, now) {
+                return Ok(LockoutDecision::Locked { retry_after });
+            }
+            self.push_failure(&mut attempts, key, now)
+        };
+
+        let verified = match encrypt_password.verify(raw_password) {
+            Ok(verified) => verified,
+            Err(err) => {
+                self.release(key, reservation);
+                return Err(err);
+            }
+        };
+        if verified {
+            self.reset(key);
+        }
+
+        Ok(LockoutDecision::Allowed { verified })
+    }
+
+    /// Returns the remaining duration of the lock of the account, or `None` if the
+    /// account is not locked.
+    pub fn retry_after_at<Q>(&self, key: &Q, now: Instant) -> Option<Duration>
+    where
+        Q: Hash + Eq + ?Sized,
+        K: Borrow<Q>,
+    {
+        Self::remaining_lock(&self.lock(), key, now)
+    }
+
+    /// Records a failed attempt of the account, like a login with an unknown password
+    /// checked outside the tracker.
+    pub fn record_failure_at<Q>(&self, key: &Q, now: Instant)
+    where
+        Q: ToOwned<Owned = K> + Hash + Eq + ?Sized,
+        K: Borrow<Q>,
+    {
+        self.push_failure(&mut self.lock(), key, now);
+    }
+
+    /// Removes the accounts without failures inside the window and without lock. It's
+    /// called when the record grows, call it periodically to release the memory sooner.
+    pub fn prune_at(&self, now: Instant) {
+        self.prune(&mut self.lock(), now);
+    }
+
+    /// Returns the count of accounts in the record.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Clears the failures and the lock of the account.
+    pub fn reset<Q>(&self, key: &Q)
+    where
+        Q: Hash + Eq + ?Sized,
+        K: Borrow<Q>,
+    {
+        self.lock().remove(key);
+    }
+
+    fn remaining_lock<Q>(attempts: &HashMap<K, Attempts>, key: &Q, now: Instant) -> Option<Duration>
+    where
+        Q: Hash + Eq + ?Sized,
+        K: Borrow<Q>,
+    {
+        let locked_until = attempts.get(key)?.locked_until?;
+        locked_until
+            .checked_duration_since(now)
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Records a failure of the account in the locked record, and locks the account if
+    /// the failures exceed the policy.
+    fn push_failure<Q>(
+        &self,
+        attempts: &mut HashMap<K, Attempts>,
+        key: &Q,
+        now: Instant,
+    ) -> Reservation
+    where
+        Q: ToOwned<Owned = K> + Hash + Eq + ?Sized,
+        K: Borrow<Q>,
+    {
+        if !attempts.contains_key(key) {
+            if attempts.len() >= self.prune_len.load(Ordering::Relaxed) {
+                self.prune(attempts, now);
+            }
+            attempts.insert(key.to_owned(), Attempts::default());
+        }
+        let entry = attempts.get_mut(key).expect("the account was inserted");
+        let previous_lock = entry.locked_until;
+
+        while let Some(&oldest) = entry.failures.front() {
+            if now.saturating_duration_since(oldest) < self.policy.window {
+                break;
+            }
+            entry.failures.pop_front();
+        }
+        entry.failures.push_back(now);
+
+        let failures = u32::try_from(entry.failures.len()).unwrap_or(u32::MAX);
+        if let Some(duration) = self.policy.lock_duration(failures) {
+            entry.locked_until = Some(now + duration);
+        }
+
+        Reservation {
+            failure: now,
+            locked_until: entry.locked_until,
+            previous_lock,
+        }
+    }
+
+    /// Removes the expired accounts of the locked record, and delays the next pruning
+    /// until the record doubles.
+    fn prune(&self, attempts: &mut HashMap<K, Attempts>, now: Instant) {
+        attempts.retain(|_, entry| !entry.is_expired(self.policy.window, now));
+        self.prune_len
+            .store(MIN_PRUNE_LEN.max(attempts.len() * 2), Ordering::Relaxed);
+    }
+
+    /// Rolls back a failure recorded before the verification. The lock is restored only
+    /// if no other failure changed it.
+    fn release<Q>(&self, key: &Q, reservation: Reservation)
+    where
+        Q: Hash + Eq + ?Sized,
+        K: Borrow<Q>,
+    {
+        let mut attempts = self.lock();
+        let Some(entry) = attempts.get_mut(key) else {
+            return;
+        };
+
+        if let Some(position) = entry
+            .failures
+            .iter()
+            .rposition(|&failure| failure == reservation.failure)
+        {
+            entry.failures.remove(position);
+        }
+        if entry.locked_until == reservation.locked_until {
+            entry.locked_until = reservation.previous_lock;
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<K, Attempts>> {
+        self.attempts
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,241 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::errors::MagicLinkError;
+use crate::typed::email::Email;
+use crate::typed::generator::fill_random;
+
+/// Length in bytes of the generated keys.
+const KEY_LEN: usize = 32;
+/// Minimum length in bytes of the keys, the output size of HMAC-SHA256.
+const MIN_KEY_LEN: usize = 32;
+/// Length in bytes of the random nonce of every token.
+const NONCE_LEN: usize = 16;
+/// Length in bytes of the expiration timestamp.
+const EXPIRES_LEN: usize = 8;
+/// Length in bytes of the HMAC-SHA256 signatures.
+const SIGNATURE_LEN: usize = 32;
+
+/// Secret key used to sign and verify the [`MagicLinkToken`]s.
+///
+/// Store it like any other server secret: every token signed with it is valid
+/// until it expires, and rotating the key revokes all of them.
+#[derive(Clone, PartialEq, Eq)]
+pub struct MagicLinkKey {
+    secret: Vec<u8>,
+}
+
+impl MagicLinkKey {
+    /// Generates a random key, using the OS secure random generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS random generator is not available.
+    pub fn generate() -> Self {
+        let mut secret = vec![0; KEY_LEN];
+        fill_random(&mut secret);
+        Self { secret }
+    }
+
+    /// Creates a key from its bytes.
+    ///
+    /// # Errors
+    ///
+    /// * `MagicLinkError::KeyLength` - If the key is shorter than 32 bytes.
+    pub fn from_bytes(secret: &[u8]) -> Result<Self, MagicLinkError> {
+        if secret.len() < MIN_KEY_LEN {
+            return Err(MagicLinkError::KeyLength(MIN_KEY_LEN));
+        }
+
+        Ok(Self {
+            secret: secret.to_vec(),
+        })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.secret
+    }
+
+    fn sign(&self, payload: &[u8]) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts keys of any size");
+        mac.update(payload);
+        mac
+    }
+}
+
+/// The key is never printed.
+impl Debug for MagicLinkKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("MagicLinkKey(***)")
+    }
+}
+
+/// Expiring token of the passwordless login links, bound to an [`Email`] and signed
+/// with HMAC-SHA256.
+///
+/// The token is URL-safe, so it can be added to the link without encoding. It carries
+/// the email, so the verification returns the account to log in. The tokens are
+/// stateless: to accept each token only once, store the used tokens until they expire.
+///
+/// ```
+/// use email_pass::{Email, MagicLinkKey, MagicLinkToken};
+/// use std::str::FromStr;
+/// use std::time::Duration;
+///
+/// let key = MagicLinkKey::generate();
+/// let email = Email::from_str("john@example.com").unwrap();
+///
+/// let token = MagicLinkToken::issue(&key, &email, Duration::from_secs(15 * 60));
+/// let link = format!("https://example.com/login?token={token}");
+///
+/// // Later, with the token received in the link
+/// let token = MagicLinkToken::from_str(token.as_str()).unwrap();
+/// assert_eq!(token.verify(&key), Ok(email));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct MagicLinkToken {
+    value: String,
+}
+
+impl MagicLinkToken {
+    /// Issues a token of the email, valid for `ttl` from now.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS random generator is not available.
+    pub fn issue(key: &MagicLinkKey, email: &Email, ttl: Duration) -> Self {
+        Self::issue_at(key, email, SystemTime::now() + ttl)
+    }
+
+    /// Issues a token of the email, valid until `expires_at`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS random generator is not available.
+    pub fn issue_at(key: &MagicLinkKey, email: &Email, expires_at: SystemTime) -> Self {
+        let expires_at = expires_at
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        let mut nonce = [0; NONCE_LEN];
+        fill_random(&mut nonce);
+
+        let email = email.to_string();
+        let mut payload = Vec::with_capacity(EXPIRES_LEN + NONCE_LEN + email.len());
+        payload.extend_from_slice(&expires_at.to_be_bytes());
+        payload.extend_from_slice(&nonce);
+        payload.extend_from_slice(email.as_bytes());
+
+        let signature = key.sign(&payload).finalize().into_bytes();
+
+        Self {
+            value: format!(
+                "{}.{}",
+                URL_SAFE_NO_PAD.encode(&payload),
+                URL_SAFE_NO_PAD.encode(signature)
+            ),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Verifies the signature and the expiration of the token.
+    ///
+    /// # Returns
+    ///
+    /// The email the token was issued for.
+    ///
+    /// # Errors
+    ///
+    /// * `MagicLinkError::Malformed` - If the token is not well-formed.
+    /// * `MagicLinkError::Signature` - If the token was not signed with the key, or was modified.
+    /// * `MagicLinkError::Expired` - If the token expired.
+    pub fn verify(&self, key: &MagicLinkKey) -> Result<Email, MagicLinkError> {
+        self.verify_at(key, SystemTime::now())
+    }
+
+    /// Like [`MagicLinkToken::verify`], at the given time.
+    pub fn verify_at(&self, key: &MagicLinkKey, time: SystemTime) -> Result<Email, MagicLinkError> {
+        let (payload, signature) = self.decode()?;
+        key.sign(&payload)
+            .verify_slice(&signature)
+            .map_err(|_| MagicLinkError::Signature)?;
+
+        let (expires_at, _) = payload.split_at(EXPIRES_LEN);
+        let expires_at = u64::from_be_bytes(expires_at.try_into().expect("8 bytes"));
+        let now = time
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        if now >= expires_at {
+            return Err(MagicLinkError::Expired);
+        }
+
+        Self::email_of(&payload).ok_or(MagicLinkError::Malformed)
+    }
+
+    /// Returns the email carried by the token, without checking the signature nor the
+    /// expiration, to identify the account of the rejected tokens in the audit trail.
+    #[cfg(feature = "password")]
+    pub(crate) fn claimed_email(&self) -> Option<Email> {
+        let (payload, _) = self.decode().ok()?;
+        Self::email_of(&payload)
+    }
+
+    fn email_of(payload: &[u8]) -> Option<Email> {
+        std::str::from_utf8(&payload[EXPIRES_LEN + NONCE_LEN..])
+            .ok()
+            .and_then(|email| Email::from_str(email).ok())
+    }
+
+    fn decode(&self) -> Result<(Vec<u8>, Vec<u8>), MagicLinkError> {
+        let (payload, signature) = self
+            .value
+            .split_once('.')
+            .ok_or(MagicLinkError::Malformed)?;
+        let payload = URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|_| MagicLinkError::Malformed)?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| MagicLinkError::Malformed)?;
+        if payload.len() <= EXPIRES_LEN + NONCE_LEN || signature.len() != SIGNATURE_LEN {
+            return Err(MagicLinkError::Malformed);
+        }
+
+        Ok((payload, signature))
+    }
+}
+
+/// Parses a token received in a link. The signature is checked by [`MagicLinkToken::verify`].
+impl FromStr for MagicLinkToken {
+    type Err = MagicLinkError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let token = Self {
+            value: value.to_string(),
+        };
+        token.decode()?;
+
+        Ok(token)
+    }
+}
+
+impl Display for MagicLinkToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+/// The token grants access to the account, so it's never printed.
+impl Debug for MagicLinkToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("MagicLinkToken(***)")
+    }
+}
@@ -0,0 +1,132 @@
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::errors::PasswordError;
+use crate::typed::digest::to_hex;
+use crate::typed::password::{Encrypt, Password, PasswordHasher, Raw};
+use crate::typed::rehash::HashAlgorithm;
+use crate::typed::scheme::HashScheme;
+
+/// Unsalted hex digests, stored by old applications instead of password hashes.
+///
+/// They are supported only to verify and migrate the existing hashes with a
+/// [`MigratingVerifier`], never create new hashes with them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum UnsaltedDigest {
+    Md5,
+    Sha1,
+    Sha256,
+}
+
+impl UnsaltedDigest {
+    fn digest(&self, raw_password: &str) -> Vec<u8> {
+        let raw_password = raw_password.as_bytes();
+        match self {
+            UnsaltedDigest::Md5 => Md5::digest(raw_password).to_vec(),
+            UnsaltedDigest::Sha1 => Sha1::digest(raw_password).to_vec(),
+            UnsaltedDigest::Sha256 => Sha256::digest(raw_password).to_vec(),
+        }
+    }
+}
+
+/// The hashes are the lowercase hex digests. The verification accepts uppercase hex.
+impl PasswordHasher for UnsaltedDigest {
+    fn hash(&self, raw_password: &str) -> Result<String, PasswordError> {
+        Ok(to_hex(&self.digest(raw_password)))
+    }
+
+    fn verify(&self, hash: &str, raw_password: &str) -> Result<bool, PasswordError> {
+        let expected = to_hex(&self.digest(raw_password));
+        Ok(expected
+            .as_bytes()
+            .ct_eq(hash.to_ascii_lowercase().as_bytes())
+            .into())
+    }
+}
+
+/// Verifier for the applications adopting the crate with hashes of an old scheme,
+/// like unsalted digests or hashes of a custom [`PasswordHasher`]. After a successful
+/// login, it returns the new hash to replace the stored hash:
+///
+/// * The hashes of the legacy scheme are always replaced.
+/// * The hashes recognized by [`Password::from_encrypt`] are verified like
+///   [`Password::verify`], and replaced if they are below the target scheme, like an
+///   old bcrypt cost. See [`HashScheme::rehash_policy`].
+///
+/// ```
+/// use email_pass::{HashScheme, MigratingVerifier, Password, UnsaltedDigest};
+///
+/// let verifier = MigratingVerifier::new(UnsaltedDigest::Md5, HashScheme::bcrypt(4));
+/// let raw_password = Password::new("password");
+///
+/// // The stored MD5 digest of the old application
+/// let stored = "5f4dcc3b5aa765d61d8327deb882cf99";
+/// let new_password = verifier.verify(stored, &raw_password)?;
+/// assert!(new_password.unwrap().as_str().starts_with("$2b$04$"));
+/// # Ok::<(), email_pass::PasswordError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct MigratingVerifier<L> {
+    legacy: L,
+    scheme: HashScheme,
+}
+
+impl<L: PasswordHasher> MigratingVerifier<L> {
+    /// Creates a verifier of the `legacy` hashes, migrated to the target `scheme`.
+    pub fn new(legacy: L, scheme: HashScheme) -> Self {
+        Self { legacy, scheme }
+    }
+
+    pub fn scheme(&self) -> HashScheme {
+        self.scheme
+    }
+
+    /// Verifies the raw password with the stored hash, of the legacy scheme or of a
+    /// recognized algorithm.
+    ///
+    /// The legacy hashes are verified with the raw value, without the normalization of
+    /// the `nfkc` feature, because the old application didn't apply it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(new_password))` - If the password matches and the stored hash must be
+    ///   replaced.
+    /// * `Ok(None)` - If the password matches and the stored hash is up to date.
+    /// * `Err::PasswordMismatch` - If the password doesn't match.
+    /// * `Err::PasswordVerification` - If the hash can't be verified.
+    /// * The errors of [`HashScheme::hash`], if the password matches.
+    pub fn verify(
+        &self,
+        stored: &str,
+        raw_password: &Password<Raw>,
+    ) -> Result<Option<Password<Encrypt>>, PasswordError> {
+        if HashAlgorithm::detect(stored).is_none() {
+            if !self.legacy.verify(stored, raw_password.expose())? {
+                return Err(PasswordError::PasswordMismatch);
+            }
+
+            return self.scheme.hash(raw_password).map(Some);
+        }
+
+        let encrypt_password = Password::from_encrypt(stored)?;
+        let matches = encrypt_password
+            .verify(raw_password)
+            .map_err(|_| PasswordError::PasswordVerification)?;
+        if !matches {
+            return Err(PasswordError::PasswordMismatch);
+        }
+
+        if !self
+            .scheme
+            .rehash_policy()
+            .requires_rehash(&encrypt_password)
+        {
+            return Ok(None);
+        }
+
+        self.scheme.hash(raw_password).map(Some)
+    }
+}
@@ -1,10 +1,129 @@
+#[cfg(feature = "email")]
+pub mod address_list;
+#[cfg(feature = "password")]
+pub mod api_key;
+#[cfg(feature = "argon2")]
+pub mod argon2_params;
+#[cfg(feature = "password")]
+pub mod audit;
+#[cfg(feature = "email")]
+pub mod canonical;
+#[cfg(feature = "password")]
+pub mod digest;
+#[cfg(feature = "email")]
+pub mod domain;
+#[cfg(feature = "password")]
+pub mod dummy;
+#[cfg(feature = "email")]
 pub mod email;
+#[cfg(feature = "email")]
+pub mod email_set;
+#[cfg(feature = "email")]
+pub mod extract;
+#[cfg(any(
+    all(test, feature = "email", feature = "password"),
+    feature = "fixtures"
+))]
+pub mod fixtures;
+#[cfg(all(fuzzing, feature = "email"))]
+pub mod fuzzing;
+#[cfg(any(feature = "password", feature = "otp", feature = "magic-link"))]
+pub mod generator;
+#[cfg(feature = "password")]
+pub mod hash_parts;
+#[cfg(feature = "password")]
+pub mod hash_report;
+#[cfg(all(feature = "password", not(target_arch = "wasm32")))]
+pub mod hashing_pool;
+#[cfg(feature = "email")]
+pub mod importer;
+#[cfg(feature = "password")]
+pub mod keyboard;
+#[cfg(feature = "password")]
+pub mod lockout;
+#[cfg(feature = "email")]
+pub mod named_email;
+#[cfg(feature = "password")]
+pub mod observer;
+#[cfg(feature = "password")]
 pub mod password;
+#[cfg(feature = "password")]
 pub mod password_checker;
+#[cfg(feature = "password")]
+pub mod recovery_codes;
+#[cfg(feature = "password")]
+pub mod rehash;
+#[cfg(feature = "password")]
+pub mod scheme;
+#[cfg(feature = "password")]
+pub mod secret_source;
+#[cfg(feature = "email")]
+pub mod static_email;
+#[cfg(feature = "email")]
+pub mod stats;
+#[cfg(feature = "password")]
+pub mod strength_meter;
+#[cfg(all(feature = "password", not(feature = "zxcvbn")))]
+pub mod strength_rules;
+#[cfg(feature = "email")]
+pub mod suppression;
+#[cfg(feature = "password")]
+pub mod temporary_password;
+#[cfg(feature = "password")]
+pub mod timed;
+#[cfg(feature = "email")]
+pub mod validator;
+#[cfg(feature = "password")]
+pub mod wordlist;
+
+#[cfg(feature = "serde")]
+pub mod redacted;
 
 #[cfg(feature = "serde")]
 pub mod serde_feature;
 
+#[cfg(feature = "parallel")]
+pub mod parallel;
+
+#[cfg(feature = "idna")]
+pub mod idn;
+
+#[cfg(feature = "i18n")]
+pub mod i18n;
+
+#[cfg(feature = "otp")]
+pub mod otp;
+
+#[cfg(feature = "magic-link")]
+pub mod magic_link;
+
+#[cfg(feature = "pepper")]
+pub mod pepper;
+
+#[cfg(feature = "migration")]
+pub mod migration;
+
+#[cfg(feature = "keyring")]
+pub mod os_keyring;
+
+#[cfg(feature = "prompt")]
+pub mod prompt;
+
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
+#[cfg(feature = "tokio")]
+pub mod tokio_feature;
+
+#[cfg(feature = "valuable")]
+pub mod valuable_feature;
+
+#[cfg(feature = "defmt")]
+pub mod defmt_feature;
+
+#[cfg(feature = "heapless")]
+pub mod fixed_email;
+
 #[cfg(test)]
-#[cfg(not(feature = "legacy"))]
+#[cfg(all(feature = "email", feature = "password", not(feature = "legacy")))]
 mod tests;
@@ -0,0 +1,248 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::errors::EmailError;
+use crate::typed::{address_list::mailbox_address, email::Email};
+
+/// Email with an optional display name, like `John Doe <john@example.com>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedEmail {
+    name: Option<String>,
+    email: Email,
+}
+
+impl NamedEmail {
+    /// Creates the email with the display name. The empty or blank names are
+    /// discarded, like the missing names.
+    ///
+    /// # Errors
+    ///
+    /// * `EmailError::ControlCharacter` - If the name contains a control character, like
+    ///   CR or LF, to prevent header injections.
+    pub fn new(name: Option<&str>, email: Email) -> Result<Self, EmailError> {
+        let name = name.filter(|name| !name.trim().is_empty());
+        if let Some(name) = name {
+            Email::check_control_chars(name)?;
+        }
+
+        Ok(Self {
+            name: name.map(str::to_string),
+            email,
+        })
+    }
+
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    #[inline]
+    pub fn email(&self) -> &Email {
+        &self.email
+    }
+
+    #[inline]
+    pub fn into_email(self) -> Email {
+        self.email
+    }
+}
+
+/// Removes the quotes of a quoted display name, resolving the escaped characters.
+fn unquote(name: &str) -> String {
+    let Some(name) = name
+        .strip_prefix('"')
+        .and_then(|name| name.strip_suffix('"'))
+    else {
+        return name.to_string();
+    };
+
+    let mut unquoted = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => unquoted.extend(chars.next()),
+            _ => unquoted.push(char),
+        }
+    }
+
+    unquoted
+}
+
+fn decode_q(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut chars = text.bytes();
+    while let Some(byte) = chars.next() {
+        match byte {
+            b'_' => bytes.push(b' '),
+            b'=' => {
+                let hex = [chars.next()?, chars.next()?];
+                let hex = std::str::from_utf8(&hex).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            }
+            _ => bytes.push(byte),
+        }
+    }
+
+    Some(bytes)
+}
+
+/// Decodes an RFC 2047 encoded-word at the start of `word`, returning the decoded
+/// text and the length of the encoded-word.
+fn decode_word(word: &str) -> Option<(String, usize)> {
+    let inner = word.strip_prefix("=?")?;
+    let (charset, inner) = inner.split_once('?')?;
+    let (encoding, inner) = inner.split_once('?')?;
+    let text = &inner[..inner.find("?=")?];
+    let len = charset.len() + encoding.len() + text.len() + 6;
+
+    let bytes = match encoding {
+        "B" | "b" => STANDARD.decode(text).ok()?,
+        "Q" | "q" => decode_q(text)?,
+        _ => return None,
+    };
+
+    // RFC 2231 allows a language suffix, like `UTF-8*en`
+    let charset = charset.split('*').next()?.to_ascii_lowercase();
+    let decoded = match charset.as_str() {
+        "utf-8" | "us-ascii" => String::from_utf8(bytes).ok()?,
+        "iso-8859-1" | "latin1" => bytes.into_iter().map(char::from).collect(),
+        _ => return None,
+    };
+
+    Some((decoded, len))
+}
+
+/// Decodes the RFC 2047 encoded-words of a display name, like `=?UTF-8?B?...?=`.
+/// The whitespace between adjacent encoded-words is discarded, and the malformed or
+/// unsupported encoded-words are kept as is.
+pub(crate) fn decode_encoded_words(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut after_word = false;
+
+    while let Some(start) = rest.find("=?") {
+        let (before, candidate) = rest.split_at(start);
+        match decode_word(candidate) {
+            Some((word, len)) => {
+                if !(after_word && before.trim().is_empty()) {
+                    decoded.push_str(before);
+                }
+                decoded.push_str(&word);
+                rest = &candidate[len..];
+                after_word = true;
+            }
+            None => {
+                decoded.push_str(before);
+                decoded.push_str("=?");
+                rest = &candidate[2..];
+                after_word = false;
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+impl FromStr for NamedEmail {
+    type Err = EmailError;
+
+    /// Parses a mailbox, like `John Doe <john@example.com>` or `john@example.com`.
+    /// Encoded display names taken from raw headers are decoded, unless they are
+    /// quoted, as RFC 2047 requires.
+    ///
+    /// The display names with control characters, like a CR LF hidden in an
+    /// encoded-word, are rejected with `EmailError::ControlCharacter`, like [`Email`].
+    fn from_str(mailbox: &str) -> Result<Self, Self::Err> {
+        let mailbox = mailbox.trim();
+        let email = Email::parse_with_comments(mailbox_address(mailbox)?)?;
+        let name = match mailbox.rfind('<').map(|start| mailbox[..start].trim()) {
+            Some(name) if name.starts_with('"') => unquote(name),
+            Some(name) => decode_encoded_words(name),
+            None => String::new(),
+        };
+        Email::check_control_chars(&name)?;
+
+        Ok(Self {
+            name: Some(name).filter(|name| !name.is_empty()),
+            email,
+        })
+    }
+}
+
+/// Checks if the character can be part of an RFC 5322 atom, or separate atoms.
+#[inline]
+fn is_atom_char(char: char) -> bool {
+    char.is_ascii_alphanumeric() || " !#$%&'*+-/=?^_`{|}~".contains(char)
+}
+
+/// Maximum length in bytes of the text of an encoded-word, so the encoded-word fits in
+/// the 75 characters of RFC 2047: `=?UTF-8?B?` and `?=` plus 60 characters of base64.
+const ENCODED_WORD_TEXT_LEN: usize = 45;
+
+/// Writes the name as RFC 2047 encoded-words, like `=?UTF-8?B?...?=`, split at the
+/// character boundaries and separated by spaces.
+fn write_encoded_words(f: &mut Formatter<'_>, name: &str) -> std::fmt::Result {
+    let mut rest = name;
+    while !rest.is_empty() {
+        if rest.len() != name.len() {
+            f.write_str(" ")?;
+        }
+
+        let mut len = rest.len().min(ENCODED_WORD_TEXT_LEN);
+        while !rest.is_char_boundary(len) {
+            len -= 1;
+        }
+        let (chunk, tail) = rest.split_at(len);
+        write!(f, "=?UTF-8?B?{}?=", STANDARD.encode(chunk))?;
+        rest = tail;
+    }
+
+    Ok(())
+}
+
+/// Formats the email as `Name <addr>`. The display names containing special
+/// characters are quoted and escaped, as RFC 5322 requires: `"Doe, John" <john@example.com>`.
+/// The names that look like encoded-words, containing `=?`, are quoted too.
+/// The non-ASCII display names are encoded as RFC 2047 encoded-words, like
+/// `=?UTF-8?B?Sm9zw6kgUMOpcmV6?= <jose@example.com>`.
+///
+/// The alternate flag (`{:#}`) masks the address, like the alternate [`Email`] display.
+impl Display for NamedEmail {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Some(name) = &self.name else {
+            return Display::fmt(&self.email, f);
+        };
+
+        if !name.is_ascii() {
+            write_encoded_words(f, name)?;
+        } else if name.chars().all(is_atom_char) && name.trim() == name && !name.contains("=?") {
+            f.write_str(name)?;
+        } else {
+            f.write_str("\"")?;
+            for char in name.chars() {
+                if matches!(char, '"' | '\\') {
+                    f.write_str("\\")?;
+                }
+                write!(f, "{char}")?;
+            }
+            f.write_str("\"")?;
+        }
+
+        if f.alternate() {
+            write!(f, " <{:#}>", self.email)
+        } else {
+            write!(f, " <{}>", self.email)
+        }
+    }
+}
+
+impl From<Email> for NamedEmail {
+    fn from(email: Email) -> Self {
+        Self { name: None, email }
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::errors::PasswordError;
+use crate::typed::timed::timer_start;
+
+static OBSERVER: OnceLock<Box<dyn VerificationObserver>> = OnceLock::new();
+
+/// Receives the outcome of the password operations, useful to collect metrics
+/// without wrapping every call site.
+///
+/// All the methods have an empty default implementation, so only the relevant
+/// events need to be implemented. The observer never receives the passwords.
+///
+/// The hashes of every backend are notified, with the cost of [`RehashPolicy`]: the
+/// bcrypt cost, the iterations of argon2 and PBKDF2, or the `log_n` of scrypt.
+///
+/// [`RehashPolicy`]: crate::RehashPolicy
+pub trait VerificationObserver: Send + Sync {
+    /// Called after a raw password is successfully encrypted.
+    fn on_hash(&self, _cost: u32) {}
+
+    /// Called when a raw password matches with the encrypted password.
+    fn on_verify_success(&self) {}
+
+    /// Called when a raw password doesn't match with the encrypted password,
+    /// or the verification can't be completed.
+    fn on_verify_failure(&self) {}
+
+    /// Called after every hash, successful or not, with its wall-clock duration.
+    /// Useful to alert when the cost needs a recalibration.
+    fn on_hash_duration(&self, _cost: u32, _duration: Duration) {}
+
+    /// Called after every verification, successful or not, with its wall-clock duration.
+    fn on_verify_duration(&self, _duration: Duration) {}
+
+    /// Called when a raw password is rejected by a [`PasswordStrengthChecker`].
+    ///
+    /// [`PasswordStrengthChecker`]: crate::PasswordStrengthChecker
+    fn on_strength_rejection(&self, _error: &PasswordError) {}
+}
+
+/// Registers the [`VerificationObserver`] used by the whole application.
+///
+/// # Returns
+///
+/// Returns the given observer back if an observer was already registered.
+pub fn set_verification_observer<O>(observer: O) -> Result<(), Box<dyn VerificationObserver>>
+where
+    O: VerificationObserver + 'static,
+{
+    OBSERVER.set(Box::new(observer))
+}
+
+#[inline]
+pub(crate) fn observer() -> Option<&'static dyn VerificationObserver> {
+    OBSERVER.get().map(Box::as_ref)
+}
+
+/// Runs a hash of a backend, notifying its duration and, if successful, the hash to
+/// the registered observer.
+pub(crate) fn observe_hash<T, F>(cost: u32, hash: F) -> Result<T, PasswordError>
+where
+    F: FnOnce() -> Result<T, PasswordError>,
+{
+    let start = timer_start();
+    let result = hash();
+
+    if let Some(observer) = observer() {
+        if let Some(start) = start {
+            observer.on_hash_duration(cost, start.elapsed());
+        }
+        if result.is_ok() {
+            observer.on_hash(cost);
+        }
+    }
+
+    result
+}
@@ -0,0 +1,27 @@
+use keyring::Entry;
+
+use crate::typed::password::{Password, Raw};
+
+impl Password {
+    /// Reads a raw password from the OS credential store.
+    ///
+    /// The platform stores are enabled with the features of the [`keyring`] crate,
+    /// like `apple-native`, `windows-native` or `linux-native`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`keyring::Error::NoEntry`] if the password is not stored, or the error
+    /// of the credential store.
+    pub fn from_keyring(service: &str, account: &str) -> keyring::Result<Password<Raw>> {
+        let raw_password = Entry::new(service, account)?.get_password()?;
+        Ok(Password::new(&raw_password))
+    }
+}
+
+impl Password<Raw> {
+    /// Stores the raw password in the OS credential store, replacing the password
+    /// stored for the same service and account. See [`Password::from_keyring`].
+    pub fn store_in_keyring(&self, service: &str, account: &str) -> keyring::Result<()> {
+        Entry::new(service, account)?.set_password(self.expose())
+    }
+}
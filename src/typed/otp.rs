@@ -0,0 +1,406 @@
+use std::fmt::{Debug, Formatter};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+use crate::errors::OtpError;
+use crate::typed::email::Email;
+use crate::typed::generator::fill_random;
+
+/// Length in bytes of the generated secrets, recommended by RFC 4226.
+const SECRET_LEN: usize = 20;
+/// Minimum length in bytes of the secrets, required by RFC 4226.
+const MIN_SECRET_LEN: usize = 16;
+/// Digits of the codes.
+const DIGITS: u32 = 6;
+/// Duration in seconds of every TOTP time step.
+const PERIOD: u64 = 30;
+/// Time steps accepted before and after the current one, to tolerate clock drift.
+pub const DRIFT_WINDOW: u64 = 1;
+/// Counters accepted after the expected one, to tolerate the codes generated
+/// but not used.
+pub const LOOK_AHEAD_WINDOW: u64 = 10;
+/// Counters searched after the expected one to resynchronize a token.
+pub const RESYNC_WINDOW: u64 = 100;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Shared secret of the time-based one-time passwords (RFC 6238), compatible with
+/// the authenticator apps: HMAC-SHA1, 6 digits and 30 seconds steps.
+///
+/// ```
+/// use email_pass::{Email, TotpSecret};
+/// use std::str::FromStr;
+///
+/// let secret = TotpSecret::generate();
+/// let email = Email::from_str("john@example.com").unwrap();
+///
+/// // Show the URI as a QR code, and store the base32 secret
+/// let uri = secret.to_uri("Example", &email);
+/// assert!(uri.starts_with("otpauth://totp/Example:john%40example.com?secret="));
+///
+/// // Store the matched time step, and reject the codes of the same or earlier steps
+/// let step = secret.verify(&secret.code()).expect("the code is valid");
+/// assert_eq!(secret.verify(&secret.code()), Some(step));
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct TotpSecret {
+    secret: Vec<u8>,
+}
+
+impl TotpSecret {
+    /// Generates a random secret, using the OS secure random generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS random generator is not available.
+    pub fn generate() -> Self {
+        Self {
+            secret: random_secret(),
+        }
+    }
+
+    /// Creates a secret from its bytes.
+    ///
+    /// # Errors
+    ///
+    /// * `OtpError::SecretLength` - If the secret is shorter than 16 bytes.
+    pub fn from_bytes(secret: &[u8]) -> Result<Self, OtpError> {
+        Ok(Self {
+            secret: checked_secret(secret)?,
+        })
+    }
+
+    /// Creates a secret from its base32 representation, ignoring the case, the
+    /// spaces and the padding.
+    ///
+    /// # Errors
+    ///
+    /// * `OtpError::Encoding` - If the value is not valid base32.
+    /// * `OtpError::SecretLength` - If the secret is shorter than 16 bytes.
+    pub fn from_base32(secret: &str) -> Result<Self, OtpError> {
+        Self::from_bytes(&base32_decode(secret).ok_or(OtpError::Encoding)?)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.secret
+    }
+
+    /// Returns the secret encoded in base32, without padding.
+    pub fn to_base32(&self) -> String {
+        base32_encode(&self.secret)
+    }
+
+    /// Returns the `otpauth://` URI used by the authenticator apps to add the account.
+    pub fn to_uri(&self, issuer: &str, account: &Email) -> String {
+        otpauth_uri(
+            "totp",
+            issuer,
+            account,
+            &self.secret,
+            &format!("period={PERIOD}"),
+        )
+    }
+
+    /// Returns the code of the current time step.
+    pub fn code(&self) -> String {
+        self.code_at(SystemTime::now())
+    }
+
+    /// Returns the code of the time step of `time`.
+    pub fn code_at(&self, time: SystemTime) -> String {
+        hotp(&self.secret, time_step(time))
+    }
+
+    /// Verifies a code of the current time step, accepting the [`DRIFT_WINDOW`]
+    /// steps before and after it.
+    ///
+    /// # Returns
+    ///
+    /// The time step of the code, or `None` if the code doesn't match. A code is valid
+    /// during several steps, so store the step of the last accepted code and reject the
+    /// codes of the same or earlier steps, to prevent the replay of the codes.
+    pub fn verify(&self, code: &str) -> Option<u64> {
+        self.verify_at(code, SystemTime::now(), DRIFT_WINDOW)
+    }
+
+    /// Verifies a code of the time step of `time`, accepting the `window` steps
+    /// before and after it. The codes are compared in constant time.
+    ///
+    /// # Returns
+    ///
+    /// The time step of the code, or `None` if the code doesn't match.
+    pub fn verify_at(&self, code: &str, time: SystemTime, window: u64) -> Option<u64> {
+        let step = time_step(time);
+        (step.saturating_sub(window)..=step.saturating_add(window))
+            .find(|step| code_eq(&hotp(&self.secret, *step), code))
+    }
+}
+
+/// The secret is erased from the memory when it's dropped.
+impl Drop for TotpSecret {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+/// The secret is never printed.
+impl Debug for TotpSecret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TotpSecret(***)")
+    }
+}
+
+/// Shared secret of the counter-based one-time passwords (RFC 4226), used by the
+/// hardware tokens and the codes sent by SMS: HMAC-SHA1 and 6 digits.
+///
+/// The application stores the counter expected for the next code, and replaces
+/// it with the counter returned by [`HotpSecret::verify`].
+///
+/// ```
+/// use email_pass::HotpSecret;
+///
+/// let secret = HotpSecret::generate();
+/// let mut counter = 0;
+///
+/// let code = secret.code(counter + 2);
+/// counter = secret.verify(&code, counter).expect("the code is in the window");
+/// assert_eq!(counter, 3);
+///
+/// // The codes can't be reused
+/// assert_eq!(secret.verify(&code, counter), None);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct HotpSecret {
+    secret: Vec<u8>,
+}
+
+impl HotpSecret {
+    /// Generates a random secret, using the OS secure random generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS random generator is not available.
+    pub fn generate() -> Self {
+        Self {
+            secret: random_secret(),
+        }
+    }
+
+    /// Creates a secret from its bytes.
+    ///
+    /// # Errors
+    ///
+    /// * `OtpError::SecretLength` - If the secret is shorter than 16 bytes.
+    pub fn from_bytes(secret: &[u8]) -> Result<Self, OtpError> {
+        Ok(Self {
+            secret: checked_secret(secret)?,
+        })
+    }
+
+    /// Creates a secret from its base32 representation, ignoring the case, the
+    /// spaces and the padding.
+    ///
+    /// # Errors
+    ///
+    /// * `OtpError::Encoding` - If the value is not valid base32.
+    /// * `OtpError::SecretLength` - If the secret is shorter than 16 bytes.
+    pub fn from_base32(secret: &str) -> Result<Self, OtpError> {
+        Self::from_bytes(&base32_decode(secret).ok_or(OtpError::Encoding)?)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.secret
+    }
+
+    /// Returns the secret encoded in base32, without padding.
+    pub fn to_base32(&self) -> String {
+        base32_encode(&self.secret)
+    }
+
+    /// Returns the `otpauth://` URI used by the authenticator apps to add the account,
+    /// starting at `counter`.
+    pub fn to_uri(&self, issuer: &str, account: &Email, counter: u64) -> String {
+        otpauth_uri(
+            "hotp",
+            issuer,
+            account,
+            &self.secret,
+            &format!("counter={counter}"),
+        )
+    }
+
+    /// Returns the code of the counter.
+    pub fn code(&self, counter: u64) -> String {
+        hotp(&self.secret, counter)
+    }
+
+    /// Verifies a code generated with the expected counter or with one of the
+    /// [`LOOK_AHEAD_WINDOW`] counters after it.
+    ///
+    /// # Returns
+    ///
+    /// The counter expected for the next code, or `None` if the code doesn't match.
+    pub fn verify(&self, code: &str, counter: u64) -> Option<u64> {
+        self.verify_with_window(code, counter, LOOK_AHEAD_WINDOW)
+    }
+
+    /// Verifies a code generated with the expected counter or with one of the
+    /// `window` counters after it. The codes are compared in constant time.
+    ///
+    /// # Returns
+    ///
+    /// The counter expected for the next code, or `None` if the code doesn't match or
+    /// was generated with the last counter, `u64::MAX`.
+    pub fn verify_with_window(&self, code: &str, counter: u64, window: u64) -> Option<u64> {
+        (counter..=counter.saturating_add(window))
+            .find(|counter| code_eq(&hotp(&self.secret, *counter), code))
+            .and_then(|counter| counter.checked_add(1))
+    }
+
+    /// Resynchronizes a token whose counter advanced beyond the look-ahead window,
+    /// searching two consecutive codes in the [`RESYNC_WINDOW`] counters after
+    /// the expected one.
+    ///
+    /// # Returns
+    ///
+    /// The counter expected for the next code, or `None` if the codes don't match.
+    pub fn resync(&self, first_code: &str, second_code: &str, counter: u64) -> Option<u64> {
+        let next = self.verify_with_window(first_code, counter, RESYNC_WINDOW)?;
+        self.verify_with_window(second_code, next, 0)
+    }
+}
+
+/// The secret is erased from the memory when it's dropped.
+impl Drop for HotpSecret {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+/// The secret is never printed.
+impl Debug for HotpSecret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HotpSecret(***)")
+    }
+}
+
+fn random_secret() -> Vec<u8> {
+    let mut secret = vec![0; SECRET_LEN];
+    fill_random(&mut secret);
+    secret
+}
+
+fn checked_secret(secret: &[u8]) -> Result<Vec<u8>, OtpError> {
+    if secret.len() < MIN_SECRET_LEN {
+        return Err(OtpError::SecretLength(MIN_SECRET_LEN));
+    }
+
+    Ok(secret.to_vec())
+}
+
+fn otpauth_uri(
+    kind: &str,
+    issuer: &str,
+    account: &Email,
+    secret: &[u8],
+    parameter: &str,
+) -> String {
+    let issuer = percent_encode(issuer);
+    format!(
+        "otpauth://{kind}/{issuer}:{}?secret={}&issuer={issuer}&algorithm=SHA1&digits={DIGITS}&{parameter}",
+        percent_encode(&account.to_string()),
+        base32_encode(secret),
+    )
+}
+
+fn time_step(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() / PERIOD)
+}
+
+/// Calculates the HOTP code of the counter (RFC 4226).
+pub(crate) fn hotp(secret: &[u8], counter: u64) -> String {
+    let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    // Dynamic truncation
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+
+    format!(
+        "{:0width$}",
+        binary % 10u32.pow(DIGITS),
+        width = DIGITS as usize
+    )
+}
+
+/// Compares two codes in constant time.
+pub(crate) fn code_eq(expected: &str, code: &str) -> bool {
+    expected.as_bytes().ct_eq(code.as_bytes()).into()
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let mut buffer = 0u16;
+    let mut bits = 0;
+
+    for byte in bytes {
+        buffer = (buffer << 8) | *byte as u16;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[(buffer >> bits) as usize & 0x1f] as char);
+        }
+    }
+
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[(buffer << (5 - bits)) as usize & 0x1f] as char);
+    }
+
+    encoded
+}
+
+fn base32_decode(encoded: &str) -> Option<Vec<u8>> {
+    let mut decoded = Vec::with_capacity(encoded.len() * 5 / 8);
+    let mut buffer = 0u16;
+    let mut bits = 0;
+
+    for char in encoded
+        .chars()
+        .filter(|char| !char.is_whitespace() && *char != '=')
+    {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|symbol| *symbol as char == char.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | value as u16;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+        }
+    }
+
+    Some(decoded)
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            byte => format!("%{byte:02X}"),
+        })
+        .collect()
+}
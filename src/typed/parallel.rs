@@ -0,0 +1,84 @@
+use std::sync::OnceLock;
+
+#[cfg(feature = "password")]
+use rayon::{prelude::*, ThreadPool};
+
+#[cfg(feature = "email")]
+use crate::{errors::EmailError, typed::email::Email};
+#[cfg(feature = "password")]
+use crate::{
+    errors::PasswordError,
+    typed::{
+        password::{Password, Raw},
+        password_checker::{Entropy, PasswordStrengthChecker},
+    },
+};
+
+static THREAD_POOL: OnceLock<ThreadPool> = OnceLock::new();
+
+/// Sets the thread pool shared by all the bulk operations of the crate.
+/// If no pool is set, the [`rayon`] global pool is used.
+///
+/// # Returns
+///
+/// Returns the given pool back if a pool was already set.
+pub fn set_thread_pool(pool: ThreadPool) -> Result<(), ThreadPool> {
+    THREAD_POOL.set(pool)
+}
+
+fn install<R, F>(op: F) -> R
+where
+    R: Send,
+    F: FnOnce() -> R + Send,
+{
+    match THREAD_POOL.get() {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
+}
+
+#[cfg(feature = "email")]
+impl Email {
+    /// Parses every input in parallel, keeping the results in the same order.
+    pub fn parse_batch<S>(inputs: &[S]) -> Vec<Result<Email, EmailError>>
+    where
+        S: AsRef<str> + Sync,
+    {
+        install(|| {
+            inputs
+                .par_iter()
+                .map(|input| input.as_ref().parse())
+                .collect()
+        })
+    }
+}
+
+#[cfg(feature = "password")]
+impl PasswordStrengthChecker {
+    /// Checks the strength of every password in parallel, keeping the results in the same order.
+    pub fn check_batch<S>(&self, raw_passwords: &[S]) -> Vec<Result<Entropy, PasswordError>>
+    where
+        S: AsRef<str> + Sync,
+    {
+        install(|| {
+            raw_passwords
+                .par_iter()
+                .map(|raw_password| self.check(raw_password.as_ref()))
+                .collect()
+        })
+    }
+}
+
+#[cfg(feature = "password")]
+impl Password {
+    /// Verifies every pair of encrypted and raw passwords in parallel,
+    /// keeping the results in the same order.
+    pub fn verify_batch(pairs: &[(Password, Password<Raw>)]) -> Vec<Result<bool, PasswordError>> {
+        install(|| {
+            pairs
+                .par_iter()
+                .map(|(password, raw_password)| password.verify(raw_password))
+                .collect()
+        })
+    }
+}
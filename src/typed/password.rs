@@ -1,21 +1,154 @@
 use crate::errors::PasswordError;
-use crate::typed::password_checker::PasswordStrengthChecker;
-use bcrypt::{hash, verify, BcryptError};
-use once_cell::sync::Lazy;
+use crate::typed::hash_parts::{normalize_bcrypt_version, split_key_id};
+use crate::typed::observer::{observe_hash, observer};
+use crate::typed::password_checker::{default_policy, PasswordStrengthChecker};
+use crate::typed::rehash::HashAlgorithm;
+use crate::typed::scheme::verify_hash;
+use crate::typed::timed::timer_start;
+use bcrypt::hash;
 use regex::Regex;
+use std::borrow::Cow;
+use std::env;
 use std::fmt::{Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock, OnceLock};
 
-pub const HASHED_PASSWORD_REGEX_VALUE: &str = r"^\$([a-z\d]+)\$([a-z\d]+)\$.*";
+pub const HASHED_PASSWORD_REGEX_VALUE: &str = r"^\$([a-z\d-]+)\$([a-z\d=,]+)\$.*";
 
-static HASHED_PASSWORD_REGEX: Lazy<Regex> =
-    Lazy::new(|| Regex::new(HASHED_PASSWORD_REGEX_VALUE).unwrap());
+/// Minimum cost accepted by bcrypt.
+pub const MIN_COST: u32 = 4;
+/// Maximum cost accepted by bcrypt.
+pub const MAX_COST: u32 = 31;
+
+/// Cost used by [`Password<Raw>::to_encrypt_default`], [`bcrypt::DEFAULT_COST`], if
+/// other cost is not registered with [`set_default_cost`].
+#[cfg(not(target_arch = "wasm32"))]
+pub const DEFAULT_COST: u32 = bcrypt::DEFAULT_COST;
+/// Cost used by [`Password<Raw>::to_encrypt_default`], if other cost is not registered
+/// with [`set_default_cost`]. The WebAssembly runtimes are slower and block the main
+/// thread of the browsers, so the cost is lower.
+#[cfg(target_arch = "wasm32")]
+pub const DEFAULT_COST: u32 = 10;
+
+static REGISTERED_DEFAULT_COST: OnceLock<u32> = OnceLock::new();
+
+/// Registers the bcrypt cost used in the whole application by
+/// [`Password<Raw>::to_encrypt_default`], [`HashScheme::auto`] and
+/// [`RehashPolicy::new`], instead of [`DEFAULT_COST`].
+///
+/// ```
+/// use email_pass::{set_default_cost, Password};
+///
+/// assert!(set_default_cost(4).is_ok());
+/// let encrypt_password = Password::new("password").to_encrypt_default()?;
+/// assert!(encrypt_password.as_str().starts_with("$2b$04$"));
+/// # Ok::<(), email_pass::PasswordError>(())
+/// ```
+///
+/// # Errors
+///
+/// * `PasswordError::InvalidCost` - If the cost is not between [`MIN_COST`] and [`MAX_COST`].
+/// * `PasswordError::DefaultCostAlreadySet` - If a cost was already registered, with
+///   the registered cost.
+///
+/// [`HashScheme::auto`]: crate::HashScheme::auto
+/// [`RehashPolicy::new`]: crate::RehashPolicy::new
+pub fn set_default_cost(cost: u32) -> Result<(), PasswordError> {
+    if !(MIN_COST..=MAX_COST).contains(&cost) {
+        return Err(PasswordError::InvalidCost {
+            given: cost,
+            min: MIN_COST,
+            max: MAX_COST,
+        });
+    }
+
+    REGISTERED_DEFAULT_COST
+        .set(cost)
+        .map_err(|_| PasswordError::DefaultCostAlreadySet(default_cost()))
+}
+
+/// Registers the default cost from the environment variable `name`, like
+/// `BCRYPT_COST=13`. If the variable is not defined, the default cost doesn't change.
+///
+/// # Returns
+///
+/// * `Ok(cost)` - The default cost in use.
+/// * `PasswordError::InvalidCostEnv` - If the variable is not a number.
+/// * The errors of [`set_default_cost`].
+pub fn set_default_cost_from_env(name: &str) -> Result<u32, PasswordError> {
+    let Ok(value) = env::var(name) else {
+        return Ok(default_cost());
+    };
+    let cost = value
+        .trim()
+        .parse()
+        .map_err(|_| PasswordError::InvalidCostEnv(name.to_string()))?;
+
+    set_default_cost(cost)?;
+    Ok(cost)
+}
+
+/// Returns the registered default cost, or [`DEFAULT_COST`].
+#[inline]
+pub fn default_cost() -> u32 {
+    REGISTERED_DEFAULT_COST
+        .get()
+        .copied()
+        .unwrap_or(DEFAULT_COST)
+}
+
+/// Maximum length in bytes of the passwords hashed with bcrypt. Bcrypt ignores the
+/// bytes after the limit.
+pub const MAX_BCRYPT_LEN: usize = 72;
+
+static HASHED_PASSWORD_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(HASHED_PASSWORD_REGEX_VALUE).unwrap());
+
+/// Format of the PBKDF2-HMAC-SHA256 hashes of Django, `pbkdf2_sha256$iterations$salt$hash`.
+static DJANGO_HASH_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^pbkdf2_sha256\$\d+\$[^$]+\$[A-Za-z\d+/]+=*$").unwrap());
 
 #[derive(Clone, Eq, PartialEq)]
 pub struct Raw;
+/// Encrypted state, optionally restricted to a hash family, like
+/// `Password<Encrypt<Bcrypt>>`. The default family, [`Any`], accepts every hash.
 #[derive(Clone, Eq, PartialEq)]
-pub struct Encrypt;
+pub struct Encrypt<Family = Any>(PhantomData<Family>);
+
+/// Type-erased hash family, used to store hashes of any algorithm.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Any;
+/// Bcrypt hash family.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Bcrypt;
+/// Argon2 hash family.
+#[derive(Clone, Eq, PartialEq)]
+pub struct Argon2;
+
+/// Family of hashes allowed in a [`Password<Encrypt<Family>>`].
+pub trait HashFamily {
+    /// Checks if the hash belongs to the family.
+    fn accepts(hash: &str) -> bool;
+}
+
+impl HashFamily for Any {
+    fn accepts(_hash: &str) -> bool {
+        true
+    }
+}
+
+impl HashFamily for Bcrypt {
+    fn accepts(hash: &str) -> bool {
+        HashAlgorithm::detect(hash) == Some(HashAlgorithm::Bcrypt)
+    }
+}
+
+impl HashFamily for Argon2 {
+    fn accepts(hash: &str) -> bool {
+        HashAlgorithm::detect(hash) == Some(HashAlgorithm::Argon2)
+    }
+}
 
 /// Safe-access password abstraction.
 #[derive(Clone, Eq, PartialEq)]
@@ -37,34 +170,147 @@ impl Password {
         Self::new(raw_password)
     }
 
-    /// Create an encrypt password, check if password is really hashed.
+    /// Create an encrypt password, check if password is really hashed. The Django
+    /// PBKDF2 hashes, `pbkdf2_sha256$iterations$salt$hash`, are accepted too.
+    ///
+    /// The bcrypt hashes of PHP, `$2y$`, are normalized to `$2b$`.
+    ///
+    /// # Errors
+    ///
+    /// * `PasswordError::PasswordNotEncrypted` - If the value doesn't have a hash format.
+    /// * `PasswordError::UnknownBcryptVersion` - If the bcrypt version is unknown, like `$2c$`.
     pub fn from_encrypt(encrypted_password: &str) -> Result<Password<Encrypt>, PasswordError> {
-        if !HASHED_PASSWORD_REGEX.is_match(encrypted_password) {
+        let (hash, _) = split_key_id(encrypted_password);
+        if !HASHED_PASSWORD_REGEX.is_match(hash) && !DJANGO_HASH_REGEX.is_match(hash) {
             Err(PasswordError::PasswordNotEncrypted)?
         }
+        let encrypted_password = normalize_bcrypt_version(encrypted_password)?;
 
         Ok(Password {
-            value: Arc::from(encrypted_password),
+            value: Arc::from(encrypted_password.as_ref()),
             state: PhantomData,
         })
     }
 
-    pub fn verify(&self, raw_password: &Password<Raw>) -> Result<bool, BcryptError> {
-        let raw_password: &str = &raw_password.value;
-        verify(raw_password, &self.value)
+    /// Creates an encrypt password from a hash generated by the crate.
+    pub(crate) fn from_hash<Family>(hash: String) -> Password<Encrypt<Family>> {
+        Password {
+            value: Arc::from(hash),
+            state: PhantomData,
+        }
+    }
+
+    /// Restricts the encrypted password to a hash family.
+    ///
+    /// # Returns
+    ///
+    /// Returns the password back if the hash doesn't belong to the family.
+    ///
+    /// ```
+    /// use email_pass::{Bcrypt, Encrypt, Password};
+    ///
+    /// let password = Password::from_encrypt("$2b$04$teRReyH3sVfCd8JA71Sm6xekdy6KhRIzYYERUEUC").unwrap();
+    /// let bcrypt_password: Password<Encrypt<Bcrypt>> = password.downcast().unwrap();
+    /// ```
+    pub fn downcast<Family: HashFamily>(self) -> Result<Password<Encrypt<Family>>, Self> {
+        if !Family::accepts(&self.value) {
+            return Err(self);
+        }
+
+        Ok(Password {
+            value: self.value,
+            state: PhantomData,
+        })
+    }
+}
+
+impl<Family: HashFamily> Password<Encrypt<Family>> {
+    /// Verifies the raw password with the backend of the hash algorithm.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::InvalidHash` - If the hash can't be parsed by the backend. Before
+    ///   the Argon2, scrypt and PBKDF2 backends, this was a `BcryptError`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, ret, err)
+    )]
+    pub fn verify(&self, raw_password: &Password<Raw>) -> Result<bool, PasswordError> {
+        let start = timer_start();
+        let result = verify_hash(&raw_password.hash_input(), &self.value);
+
+        if let Some(observer) = observer() {
+            if let Some(start) = start {
+                observer.on_verify_duration(start.elapsed());
+            }
+            match result {
+                Ok(true) => observer.on_verify_success(),
+                _ => observer.on_verify_failure(),
+            }
+        }
+
+        result
     }
 
     /// Extracts the inner value from [`Password<Encrypt>`].
     pub fn as_str(&self) -> &str {
         &self.value
     }
+
+    /// Returns the bytes of the hash, to be written in binary protocols.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.value.as_bytes()
+    }
+
+    /// Returns the shared hash string. The password stores the hash in an [`Arc`],
+    /// so this is a reference count increment, without copying the hash.
+    pub fn clone_arc(&self) -> Arc<str> {
+        Arc::clone(&self.value)
+    }
+
+    /// Erases the hash family, to store the password with hashes of any algorithm.
+    pub fn erase(self) -> Password<Encrypt> {
+        Password {
+            value: self.value,
+            state: PhantomData,
+        }
+    }
 }
 
 impl Password<Raw> {
-    /// Check the password's strong, use [`PasswordStrengthChecker`] with default values.
+    /// Exposes the raw value, only to the crate internals.
+    #[cfg(any(feature = "migration", feature = "keyring"))]
+    pub(crate) fn expose(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the value to hash: with the `nfkc` feature, the NFKC normalization of the
+    /// raw value, recommended by NIST SP 800-63B, so the composed and decomposed forms
+    /// of the same characters produce the same hash.
+    ///
+    /// The normalization is applied to the hashing and the verification alike, so the
+    /// hashes of non-normalized passwords created before enabling the feature no longer
+    /// verify: enabling it is a one-way migration.
+    pub(crate) fn hash_input(&self) -> Cow<'_, str> {
+        #[cfg(feature = "nfkc")]
+        if !unicode_normalization::is_nfkc(&self.value) {
+            use unicode_normalization::UnicodeNormalization;
+            return Cow::Owned(self.value.nfkc().collect());
+        }
+
+        Cow::Borrowed(&self.value)
+    }
+
+    /// Check the password's strong, use the policy registered with
+    /// [`set_default_password_policy`], or [`PasswordStrengthChecker`] with default values.
     /// If you want change this values, use [`Password<Raw>::custom_check`].
+    ///
+    /// [`set_default_password_policy`]: crate::set_default_password_policy
     pub fn check(self) -> Result<Self, PasswordError> {
-        PasswordStrengthChecker::new().check(&self.value)?;
+        match default_policy() {
+            Some(checker) => checker.check(&self.value)?,
+            None => PasswordStrengthChecker::new().check(&self.value)?,
+        };
         Ok(self)
     }
 
@@ -99,44 +345,175 @@ impl Password<Raw> {
 
     /// Transforms [`Password<Raw>`] to [`Password<Encrypt>`], encrypting the inner value based in a cost value.
     /// This method not checks the password's strong.
-    pub fn to_encrypt(self, cost: u32) -> Result<Password<Encrypt>, BcryptError> {
-        let str_password: &str = &self.value;
-        let encrypt_password = hash(str_password, cost)?;
+    ///
+    /// # Errors
+    ///
+    /// * `PasswordError::InvalidCost` - If the cost is not between [`MIN_COST`] and [`MAX_COST`].
+    /// * `PasswordError::TooLongForBcrypt` - If the password is longer than [`MAX_BCRYPT_LEN`]
+    ///   bytes, instead of truncating it. Hash the longer passphrases with argon2 or scrypt,
+    ///   see [`HashScheme`].
+    /// * `PasswordError::PasswordEncryption` - If bcrypt fails.
+    ///
+    /// [`HashScheme`]: crate::HashScheme
+    pub fn to_encrypt(self, cost: u32) -> Result<Password<Encrypt>, PasswordError> {
+        self.to_bcrypt(cost).map(Password::erase)
+    }
 
-        Ok(Password {
-            value: Arc::from(encrypt_password),
-            state: PhantomData,
-        })
+    /// Transforms [`Password<Raw>`] to [`Password<Encrypt<Bcrypt>>`], keeping the
+    /// hash family in the type. See [`Password<Raw>::to_encrypt`].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(cost = cost), err)
+    )]
+    pub fn to_bcrypt(self, cost: u32) -> Result<Password<Encrypt<Bcrypt>>, PasswordError> {
+        if !(MIN_COST..=MAX_COST).contains(&cost) {
+            return Err(PasswordError::InvalidCost {
+                given: cost,
+                min: MIN_COST,
+                max: MAX_COST,
+            });
+        }
+
+        #[cfg(feature = "test-util")]
+        let cost = crate::typed::test_util::effective_cost(cost);
+
+        let hash_input = self.hash_input();
+        if hash_input.len() > MAX_BCRYPT_LEN {
+            return Err(PasswordError::TooLongForBcrypt(MAX_BCRYPT_LEN));
+        }
+
+        let encrypt_password = observe_hash(cost, || {
+            hash(hash_input.as_ref(), cost).map_err(|_| PasswordError::PasswordEncryption)
+        })?;
+
+        Ok(Password::from_hash(encrypt_password))
     }
 
-    /// Transforms [`Password<Raw>`] to [`Password<Encrypt>`], just encrypting the inner value.
+    /// Transforms [`Password<Raw>`] to [`Password<Encrypt>`], just encrypting the inner value
+    /// with the cost of [`default_cost`].
     /// This method not checks the password's strong.
-    pub fn to_encrypt_default(self) -> Result<Password<Encrypt>, BcryptError> {
-        self.to_encrypt(bcrypt::DEFAULT_COST)
+    pub fn to_encrypt_default(self) -> Result<Password<Encrypt>, PasswordError> {
+        self.to_encrypt(default_cost())
     }
 
     /// Transforms [`Password<Raw>`] to [`Password<Encrypt>`], encrypting the inner value based in a cost value.
     /// This method not checks the password's strong.
     #[inline]
-    pub fn to_encrypt_with_cost(self, cost: u32) -> Result<Password<Encrypt>, BcryptError> {
+    pub fn to_encrypt_with_cost(self, cost: u32) -> Result<Password<Encrypt>, PasswordError> {
         Self::to_encrypt(self, cost)
     }
+
+    /// Transforms [`Password<Raw>`] to [`Password<Encrypt>`] with a custom [`PasswordHasher`].
+    /// This method not checks the password's strong.
+    ///
+    /// The hash is not checked with the format of [`Password::from_encrypt`], so verify it
+    /// with [`Password::verify_with`] and the same hasher.
+    pub fn to_encrypt_with<H>(self, hasher: &H) -> Result<Password<Encrypt>, PasswordError>
+    where
+        H: PasswordHasher + ?Sized,
+    {
+        hasher.hash(&self.hash_input()).map(Password::from_hash)
+    }
 }
 
-impl Display for Password<Encrypt> {
+impl<Family> Password<Encrypt<Family>> {
+    /// Verifies the raw password with a custom [`PasswordHasher`].
+    pub fn verify_with<H>(
+        &self,
+        hasher: &H,
+        raw_password: &Password<Raw>,
+    ) -> Result<bool, PasswordError>
+    where
+        H: PasswordHasher + ?Sized,
+    {
+        hasher.verify(&self.value, &raw_password.hash_input())
+    }
+}
+
+/// Hash backend, to plug in algorithms or devices not supported by the crate, like
+/// PBKDF2 or an HSM. The built-in backends are available with [`HashScheme`], the
+/// default of [`Password<Raw>::to_encrypt`] is bcrypt.
+///
+/// The raw passwords are passed already normalized, like to the built-in backends.
+///
+/// ```
+/// use email_pass::{Password, PasswordError, PasswordHasher};
+///
+/// struct HsmHasher;
+///
+/// impl PasswordHasher for HsmHasher {
+///     fn hash(&self, raw_password: &str) -> Result<String, PasswordError> {
+///         // Send the password to the HSM
+///         Ok(format!("$hsm$1${}", raw_password.len()))
+///     }
+///
+///     fn verify(&self, hash: &str, raw_password: &str) -> Result<bool, PasswordError> {
+///         Ok(self.hash(raw_password)? == hash)
+///     }
+/// }
+///
+/// let raw_password = Password::new("ThisIsAPassPhrase.And.Secure.Password");
+/// let encrypt_password = raw_password.clone().to_encrypt_with(&HsmHasher)?;
+/// assert!(encrypt_password.verify_with(&HsmHasher, &raw_password)?);
+/// # Ok::<(), PasswordError>(())
+/// ```
+///
+/// [`HashScheme`]: crate::HashScheme
+pub trait PasswordHasher {
+    /// Hashes the raw password.
+    fn hash(&self, raw_password: &str) -> Result<String, PasswordError>;
+
+    /// Checks if the raw password matches the hash.
+    fn verify(&self, hash: &str, raw_password: &str) -> Result<bool, PasswordError>;
+}
+
+/// The alternate flag (`{:#}`) masks the hash, keeping only the algorithm and
+/// the cost: `$2b$12$***`, or `pbkdf2_sha256$600000$***` for the Django hashes.
+impl<Family> Display for Password<Encrypt<Family>> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            // The Django hashes have no leading `$`, so their cost ends one `$` earlier.
+            let header_fields = if self.value.starts_with('$') { 2 } else { 1 };
+            let header_len = self
+                .value
+                .match_indices('$')
+                .nth(header_fields)
+                .map_or(0, |(index, _)| index + 1);
+            return write!(f, "{}***", &self.value[..header_len]);
+        }
+
         Display::fmt(&self.value, f)
     }
 }
 
-impl AsRef<str> for Password<Encrypt> {
+impl<Family> AsRef<str> for Password<Encrypt<Family>> {
     fn as_ref(&self) -> &str {
         &self.value
     }
 }
 
-impl Debug for Password<Encrypt> {
+/// Hashes the hash string, so the passwords can be deduplicated, like the accounts
+/// sharing an imported hash.
+impl<Family> Hash for Password<Encrypt<Family>> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<Family> Debug for Password<Encrypt<Family>> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "Password(\"{}\")", self.as_ref())
     }
 }
+
+impl From<Password<Encrypt<Bcrypt>>> for Password {
+    fn from(password: Password<Encrypt<Bcrypt>>) -> Self {
+        password.erase()
+    }
+}
+
+impl From<Password<Encrypt<Argon2>>> for Password {
+    fn from(password: Password<Encrypt<Argon2>>) -> Self {
+        password.erase()
+    }
+}
@@ -1,8 +1,20 @@
 use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
 
-use zxcvbn::Entropy;
+#[cfg(feature = "zxcvbn")]
+pub use zxcvbn::Entropy;
 
 use crate::errors::PasswordError;
+use crate::typed::keyboard::KeyboardLayout;
+use crate::typed::observer::observer;
+#[cfg(not(feature = "zxcvbn"))]
+pub use crate::typed::strength_rules::Entropy;
+use crate::typed::wordlist::{Dictionary, Language};
+
+static DEFAULT_POLICY: OnceLock<DictionaryChecker> = OnceLock::new();
 
 /// Abstraction to [`zxcvbn::Entropy::score`].
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
@@ -33,11 +45,19 @@ impl Display for PasswordStrength {
 
 /// Simplify the raw passwords checking, based in minimum length and explicit strong.
 /// Use the crate [`zxcvbn`] to estimate the strong based in entropy.
+///
+/// Without the `zxcvbn` feature, the strong is estimated with length, character classes
+/// and common passwords rules, which are less accurate but don't embed the dictionaries.
 #[derive(Copy, Clone)]
 pub struct PasswordStrengthChecker {
     min_len: usize,
     /// Corresponds to [`zxcvbn::Entropy::score`]
     strong: PasswordStrength,
+    /// Bit set of the [`KeyboardLayout`]s checked
+    keyboard_layouts: u8,
+    deleet: bool,
+    /// Bit set of the [`Language`] wordlists checked
+    languages: u8,
 }
 
 impl PasswordStrengthChecker {
@@ -45,6 +65,9 @@ impl PasswordStrengthChecker {
         Self {
             min_len: 8,
             strong: PasswordStrength::Default,
+            keyboard_layouts: 0,
+            deleet: false,
+            languages: 0,
         }
     }
 
@@ -58,6 +81,63 @@ impl PasswordStrengthChecker {
         self
     }
 
+    /// Rejects the passwords with walks or repeats of the layout keys, which the
+    /// [`zxcvbn`] dictionaries miss. Can be called once per layout.
+    pub fn keyboard_layout(mut self, layout: KeyboardLayout) -> Self {
+        self.keyboard_layouts |= layout.bit();
+        self
+    }
+
+    /// Checks also the password with the leetspeak substitutions reverted
+    /// (`P@ssw0rd!` becomes `password`), so trivial substitutions don't increase
+    /// the estimated strength.
+    pub fn deleet(mut self, deleet: bool) -> Self {
+        self.deleet = deleet;
+        self
+    }
+
+    /// Checks also the words of the built-in wordlist of a [`Language`]. Can be called
+    /// once per language.
+    pub fn language(mut self, language: Language) -> Self {
+        self.languages |= language.bit();
+        self
+    }
+
+    /// Adds words to a dictionary, returning a [`DictionaryChecker`] with this
+    /// configuration. The passwords containing these words, ignoring the case and the
+    /// leetspeak substitutions, are estimated as weaker, like the words of the [`zxcvbn`]
+    /// dictionaries.
+    ///
+    /// ```
+    /// use email_pass::PasswordStrengthChecker;
+    ///
+    /// let checker = PasswordStrengthChecker::new().dictionary(["acme", "roadrunner"]);
+    /// assert!(checker.check("Acme.RoadRunner").is_err());
+    /// ```
+    pub fn dictionary<I>(self, words: I) -> DictionaryChecker
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        DictionaryChecker::from(self).dictionary(words)
+    }
+
+    /// Adds the words of a newline-delimited wordlist file to a dictionary.
+    /// See [`DictionaryChecker::with_dictionary_file`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if the file can't be opened or read.
+    pub fn with_dictionary_file<P: AsRef<Path>>(self, path: P) -> io::Result<DictionaryChecker> {
+        DictionaryChecker::from(self).with_dictionary_file(path)
+    }
+
+    /// Adds the words of a newline-delimited wordlist to a dictionary.
+    /// See [`DictionaryChecker::with_dictionary_bytes`].
+    pub fn with_dictionary_bytes(self, bytes: &[u8]) -> DictionaryChecker {
+        DictionaryChecker::from(self).with_dictionary_bytes(bytes)
+    }
+
     /// Check the strength of a password.
     ///
     /// # Parameters
@@ -68,23 +148,268 @@ impl PasswordStrengthChecker {
     ///
     /// * `Ok(entropy)` - If the password is strong enough.
     /// * `Error::PasswordLength` - If the password is too short.
+    /// * `Error::KeyboardPattern` - If the password contains a walk or a repeat of
+    ///   the keys of a configured [`KeyboardLayout`].
     /// * `Error::UnsafePassword` - If the password is not strong enough.
     pub fn check(&self, raw_password: &str) -> Result<Entropy, PasswordError> {
+        self.check_with(raw_password, None)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "check",
+            level = "debug",
+            skip_all,
+            fields(min_len = self.min_len, strong = %self.strong),
+            err
+        )
+    )]
+    fn check_with(
+        &self,
+        raw_password: &str,
+        dictionary: Option<&Dictionary>,
+    ) -> Result<Entropy, PasswordError> {
+        let result = self.estimate(raw_password, dictionary);
+
+        if let (Err(err), Some(observer)) = (&result, observer()) {
+            observer.on_strength_rejection(err);
+        }
+
+        result
+    }
+
+    fn estimate(
+        &self,
+        raw_password: &str,
+        dictionary: Option<&Dictionary>,
+    ) -> Result<Entropy, PasswordError> {
         // Check the length of the password
         if raw_password.len() < self.min_len {
             return Err(PasswordError::InvalidLength(self.min_len as u8));
         }
 
-        // Calculate the password strength using zxcvbn
-        let entropy = zxcvbn::zxcvbn(raw_password, &[])?;
+        // Check the walks of the non QWERTY layouts
+        let has_pattern = KeyboardLayout::ALL
+            .iter()
+            .filter(|layout| self.keyboard_layouts & layout.bit() != 0)
+            .any(|layout| layout.has_walk(raw_password) || layout.has_repeat(raw_password));
+        if has_pattern {
+            return Err(PasswordError::KeyboardPattern);
+        }
+
+        // Calculate the password strength
+        let entropy = self.entropy(raw_password, dictionary)?;
 
         // Check if the password is strong enough
         if entropy.score() < self.strong.as_u8() {
             return Err(PasswordError::UnsafePassword(self.strong));
         }
 
+        // Check the password without the leetspeak substitutions
+        if self.deleet {
+            let deleeted = deleet(raw_password);
+            if deleeted != raw_password
+                && self.entropy(&deleeted, dictionary)?.score() < self.strong.as_u8()
+            {
+                return Err(PasswordError::UnsafePassword(self.strong));
+            }
+        }
+
         Ok(entropy)
     }
+
+    /// Returns the dictionaries of the configured languages, and the given one.
+    fn dictionaries<'a>(&self, dictionary: Option<&'a Dictionary>) -> Vec<&'a Dictionary> {
+        Language::ALL
+            .iter()
+            .filter(|language| self.languages & language.bit() != 0)
+            .map(|language| language.dictionary())
+            .chain(dictionary)
+            .collect()
+    }
+
+    #[cfg(feature = "zxcvbn")]
+    pub(crate) fn entropy(
+        &self,
+        raw_password: &str,
+        dictionary: Option<&Dictionary>,
+    ) -> Result<Entropy, PasswordError> {
+        let user_inputs = dictionary_words(&self.dictionaries(dictionary), raw_password);
+        Ok(zxcvbn::zxcvbn(raw_password, &user_inputs)?)
+    }
+
+    #[cfg(not(feature = "zxcvbn"))]
+    pub(crate) fn entropy(
+        &self,
+        raw_password: &str,
+        dictionary: Option<&Dictionary>,
+    ) -> Result<Entropy, PasswordError> {
+        crate::typed::strength_rules::estimate(raw_password, &self.dictionaries(dictionary))
+    }
+}
+
+/// A [`PasswordStrengthChecker`] with a dictionary of words loaded by the application,
+/// returned by [`PasswordStrengthChecker::dictionary`] and the `with_dictionary_*` methods.
+///
+/// The dictionary is shared between the clones of the checker, and copied only when
+/// a clone adds words to it.
+#[derive(Clone)]
+pub struct DictionaryChecker {
+    checker: PasswordStrengthChecker,
+    dictionary: Arc<Dictionary>,
+}
+
+impl DictionaryChecker {
+    /// Adds words to the dictionary. See [`PasswordStrengthChecker::dictionary`].
+    pub fn dictionary<I>(mut self, words: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let dictionary = Arc::make_mut(&mut self.dictionary);
+        for word in words {
+            dictionary.insert(word.as_ref());
+        }
+        self
+    }
+
+    /// Adds the words of a newline-delimited wordlist file to the dictionary. The file
+    /// is read line by line, but all its words are kept in the dictionary.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if the file can't be opened or read.
+    pub fn with_dictionary_file<P: AsRef<Path>>(self, path: P) -> io::Result<Self> {
+        self.with_dictionary_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Adds the words of a newline-delimited wordlist to the dictionary, like the
+    /// output of [`include_bytes!`]. The invalid UTF-8 sequences are replaced.
+    pub fn with_dictionary_bytes(self, bytes: &[u8]) -> Self {
+        self.with_dictionary_reader(bytes)
+            .expect("reading from a slice never fails")
+    }
+
+    fn with_dictionary_reader<R: BufRead>(mut self, mut reader: R) -> io::Result<Self> {
+        let dictionary = Arc::make_mut(&mut self.dictionary);
+        let mut line = Vec::new();
+        while reader.read_until(b'\n', &mut line)? > 0 {
+            dictionary.insert(&String::from_utf8_lossy(&line));
+            line.clear();
+        }
+        Ok(self)
+    }
+
+    /// Returns the configuration of the checker, without the dictionary.
+    pub fn checker(&self) -> PasswordStrengthChecker {
+        self.checker
+    }
+
+    /// Check the strength of a password, also against the words of the dictionary.
+    /// See [`PasswordStrengthChecker::check`].
+    pub fn check(&self, raw_password: &str) -> Result<Entropy, PasswordError> {
+        self.checker
+            .check_with(raw_password, Some(&self.dictionary))
+    }
+
+    pub(crate) fn entropy(&self, raw_password: &str) -> Result<Entropy, PasswordError> {
+        self.checker.entropy(raw_password, Some(&self.dictionary))
+    }
+}
+
+impl From<PasswordStrengthChecker> for DictionaryChecker {
+    fn from(checker: PasswordStrengthChecker) -> Self {
+        Self {
+            checker,
+            dictionary: Arc::default(),
+        }
+    }
+}
+
+/// Returns the dictionary words contained in the password, with and without
+/// the leetspeak substitutions, to be used as [`zxcvbn`] user inputs.
+#[cfg(feature = "zxcvbn")]
+fn dictionary_words<'a>(dictionaries: &[&'a Dictionary], raw_password: &str) -> Vec<&'a str> {
+    let deleeted = deleet(raw_password);
+    let mut words: Vec<&str> = dictionaries
+        .iter()
+        .flat_map(|dictionary| {
+            let mut words = dictionary.find(raw_password);
+            words.extend(dictionary.find(&deleeted));
+            words
+        })
+        .collect();
+    words.sort_unstable();
+    words.dedup();
+    words
+}
+
+/// Reverts the common leetspeak substitutions and removes the trailing symbols.
+/// The characters next to digits are not replaced, so numeric suffixes like years are kept.
+pub(crate) fn deleet(raw_password: &str) -> String {
+    let chars: Vec<char> = raw_password.chars().collect();
+    let is_digit = |index: Option<usize>| {
+        index
+            .and_then(|index| chars.get(index))
+            .is_some_and(char::is_ascii_digit)
+    };
+
+    let deleeted: String = chars
+        .iter()
+        .enumerate()
+        .map(|(index, char)| {
+            let in_word = !is_digit(index.checked_sub(1)) && !is_digit(Some(index + 1));
+            match char {
+                '@' if in_word => 'a',
+                '$' if in_word => 's',
+                '!' | '|' if in_word && index + 1 < chars.len() => 'i',
+                '4' if in_word => 'a',
+                '8' if in_word => 'b',
+                '3' if in_word => 'e',
+                '6' | '9' if in_word => 'g',
+                '1' if in_word => 'i',
+                '0' if in_word => 'o',
+                '5' if in_word => 's',
+                '7' if in_word => 't',
+                char => char.to_ascii_lowercase(),
+            }
+        })
+        .collect();
+
+    deleeted
+        .trim_end_matches(|char: char| !char.is_alphanumeric())
+        .to_string()
+}
+
+/// Registers the [`PasswordStrengthChecker`] or [`DictionaryChecker`] used by
+/// [`Password<Raw>::check`] in the whole application, instead of the default values of
+/// [`PasswordStrengthChecker::new`].
+///
+/// ```
+/// use email_pass::{set_default_password_policy, Password, PasswordStrengthChecker};
+///
+/// let policy = PasswordStrengthChecker::new().min_len(12);
+/// assert!(set_default_password_policy(policy).is_ok());
+/// assert!(Password::new("Ab1.Cd2-Ef").check().is_err());
+/// ```
+///
+/// # Returns
+///
+/// Returns the given checker back if a policy was already registered.
+///
+/// [`Password<Raw>::check`]: crate::Password::check
+pub fn set_default_password_policy<C>(checker: C) -> Result<(), DictionaryChecker>
+where
+    C: Into<DictionaryChecker>,
+{
+    DEFAULT_POLICY.set(checker.into())
+}
+
+/// Returns the registered policy, if any.
+#[inline]
+pub(crate) fn default_policy() -> Option<&'static DictionaryChecker> {
+    DEFAULT_POLICY.get()
 }
 
 impl Default for PasswordStrengthChecker {
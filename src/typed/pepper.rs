@@ -0,0 +1,237 @@
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Formatter};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::errors::PasswordError;
+use crate::typed::generator::fill_random;
+use crate::typed::hash_parts::{split_key_id, KEY_ID_PREFIX};
+use crate::typed::password::{default_cost, Encrypt, Password, PasswordHasher};
+use crate::typed::scheme::{verify_hash, HashScheme};
+
+/// Length in bytes of the generated peppers.
+const PEPPER_LEN: usize = 32;
+/// Minimum length in bytes of the peppers, the output size of HMAC-SHA256.
+const MIN_PEPPER_LEN: usize = 32;
+
+/// Server-side secret mixed into the passwords with HMAC-SHA256 before hashing them,
+/// so the leaked hashes can't be cracked without the pepper.
+///
+/// Store it apart from the database, like in the environment or a secret manager.
+/// Losing the pepper invalidates every hash created with it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Pepper {
+    secret: Vec<u8>,
+}
+
+impl Pepper {
+    /// Generates a random pepper, using the OS secure random generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS random generator is not available.
+    pub fn generate() -> Self {
+        let mut secret = vec![0; PEPPER_LEN];
+        fill_random(&mut secret);
+        Self { secret }
+    }
+
+    /// Creates a pepper from its bytes.
+    ///
+    /// # Errors
+    ///
+    /// * `PasswordError::PepperLength` - If the pepper is shorter than 32 bytes.
+    pub fn from_bytes(secret: &[u8]) -> Result<Self, PasswordError> {
+        if secret.len() < MIN_PEPPER_LEN {
+            return Err(PasswordError::PepperLength(MIN_PEPPER_LEN));
+        }
+
+        Ok(Self {
+            secret: secret.to_vec(),
+        })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.secret
+    }
+
+    /// Returns the base64 of the HMAC-SHA256 of the raw password. The base64 keeps the
+    /// input of bcrypt below its 72 bytes limit and without null bytes.
+    pub(crate) fn apply(&self, raw_password: &str) -> String {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts keys of any size");
+        mac.update(raw_password.as_bytes());
+        STANDARD.encode(mac.finalize().into_bytes())
+    }
+}
+
+/// The pepper is never printed.
+impl Debug for Pepper {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Pepper(***)")
+    }
+}
+
+/// Peppers identified by a key id, to rotate the pepper without resetting the
+/// passwords. The new hashes use the key with the highest id, and store the id after
+/// the hash, like `$2b$12$...$k=2`, so the old hashes are verified with their key.
+///
+/// After a successful login, check [`HashingConfig::requires_rotation`] to hash the
+/// password again with the newest key. Remove a key when no hash uses it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyRing {
+    keys: BTreeMap<u32, Pepper>,
+}
+
+impl KeyRing {
+    /// Creates an empty key ring.
+    pub fn new() -> Self {
+        Self {
+            keys: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a key, replacing the previous key with the same id.
+    pub fn key(mut self, id: u32, pepper: Pepper) -> Self {
+        self.keys.insert(id, pepper);
+        self
+    }
+
+    /// Returns the key used by the new hashes: the key with the highest id.
+    pub fn current(&self) -> Option<(u32, &Pepper)> {
+        self.keys.last_key_value().map(|(id, pepper)| (*id, pepper))
+    }
+
+    pub fn get(&self, id: u32) -> Option<&Pepper> {
+        self.keys.get(&id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+/// Hashing configuration of a deployment: the [`HashScheme`] of the new hashes and
+/// an optional [`Pepper`] or [`KeyRing`]. Without pepper, the hashes are the same of
+/// [`Password<Raw>::to_encrypt_scheme`].
+///
+/// With a key ring, the hashes without key id are verified with the single pepper, if
+/// any, so the existing hashes keep working after adopting the rotation.
+///
+/// Use it with [`Password<Raw>::to_encrypt_with`] and [`Password::verify_with`]:
+/// ```
+/// use email_pass::{HashScheme, HashingConfig, Password, Pepper};
+///
+/// let config = HashingConfig::new()
+///     .scheme(HashScheme::bcrypt(4))
+///     .pepper(Pepper::generate());
+///
+/// let raw_password = Password::new("ThisIsAPassPhrase.And.Secure.Password");
+/// let encrypt_password = raw_password.clone().to_encrypt_with(&config)?;
+/// assert!(encrypt_password.verify_with(&config, &raw_password)?);
+/// # Ok::<(), email_pass::PasswordError>(())
+/// ```
+///
+/// [`Password<Raw>::to_encrypt_with`]: crate::Password::to_encrypt_with
+/// [`Password<Raw>::to_encrypt_scheme`]: crate::Password::to_encrypt_scheme
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashingConfig {
+    scheme: HashScheme,
+    pepper: Option<Pepper>,
+    key_ring: KeyRing,
+}
+
+impl HashingConfig {
+    /// Creates a configuration with bcrypt and the [`default_cost`], without pepper.
+    ///
+    /// [`default_cost`]: crate::default_cost
+    pub fn new() -> Self {
+        Self {
+            scheme: HashScheme::bcrypt(default_cost()),
+            pepper: None,
+            key_ring: KeyRing::new(),
+        }
+    }
+
+    /// Sets the scheme of the new hashes.
+    pub fn scheme(mut self, scheme: HashScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    /// Sets the pepper applied before hashing and verifying.
+    pub fn pepper(mut self, pepper: Pepper) -> Self {
+        self.pepper = Some(pepper);
+        self
+    }
+
+    /// Sets the key ring of the new hashes. It takes precedence over the single pepper,
+    /// kept only to verify the hashes without key id.
+    pub fn key_ring(mut self, key_ring: KeyRing) -> Self {
+        self.key_ring = key_ring;
+        self
+    }
+
+    pub fn hash_scheme(&self) -> HashScheme {
+        self.scheme
+    }
+
+    pub fn is_peppered(&self) -> bool {
+        self.pepper.is_some() || !self.key_ring.is_empty()
+    }
+
+    /// Checks if the hash was not peppered with the current key of the key ring. Hash
+    /// the password again after the next successful login.
+    ///
+    /// Without key ring, the hashes never require a rotation.
+    pub fn requires_rotation<Family>(&self, password: &Password<Encrypt<Family>>) -> bool {
+        match self.key_ring.current() {
+            Some((current_id, _)) => split_key_id(password.as_ref()).1 != Some(current_id),
+            None => false,
+        }
+    }
+
+    fn prepare(pepper: Option<&Pepper>, raw_password: &str) -> String {
+        match pepper {
+            Some(pepper) => pepper.apply(raw_password),
+            None => raw_password.to_string(),
+        }
+    }
+}
+
+impl Default for HashingConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PasswordHasher for HashingConfig {
+    fn hash(&self, raw_password: &str) -> Result<String, PasswordError> {
+        let current = self.key_ring.current();
+        let pepper = current.map(|(_, pepper)| pepper).or(self.pepper.as_ref());
+        let prepared = Self::prepare(pepper, raw_password);
+        let encrypt_password = self.scheme.hash(&Password::new(&prepared))?;
+
+        match current {
+            Some((id, _)) => Ok(format!("{}${KEY_ID_PREFIX}{id}", encrypt_password.as_str())),
+            None => Ok(encrypt_password.as_str().to_string()),
+        }
+    }
+
+    fn verify(&self, hash: &str, raw_password: &str) -> Result<bool, PasswordError> {
+        let (hash, key_id) = split_key_id(hash);
+        let pepper = match key_id {
+            Some(id) => Some(
+                self.key_ring
+                    .get(id)
+                    .ok_or(PasswordError::UnknownPepperKey(id))?,
+            ),
+            None => self.pepper.as_ref(),
+        };
+
+        verify_hash(&Self::prepare(pepper, raw_password), hash)
+            .map_err(|_| PasswordError::PasswordVerification)
+    }
+}
@@ -0,0 +1,30 @@
+use std::io;
+
+use zeroize::Zeroize;
+
+use crate::typed::password::{Password, Raw};
+
+impl Password {
+    /// Reads a raw password from the terminal without echoing it. The buffer of the
+    /// read value is wiped after creating the password.
+    ///
+    /// # Errors
+    ///
+    /// Returns the I/O error if the terminal can't be read.
+    ///
+    /// ```no_run
+    /// use email_pass::Password;
+    ///
+    /// let encrypt_password = Password::prompt("Enter password: ")?
+    ///     .check()
+    ///     .expect("the password is not strong enough")
+    ///     .to_encrypt_default();
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn prompt(prompt: &str) -> io::Result<Password<Raw>> {
+        let mut raw_password = rpassword::prompt_password(prompt)?;
+        let password = Password::new(&raw_password);
+        raw_password.zeroize();
+        Ok(password)
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::typed::generator::random_string;
+use crate::typed::password::Password;
+use crate::typed::scheme::{verify_hash, HashScheme};
+
+/// Characters of the recovery codes, without the ambiguous `0`, `o`, `1`, `l` and `i`.
+const CODE_ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+/// Length of every group of the recovery codes.
+const GROUP_LEN: usize = 5;
+
+/// One-time backup codes of the multi-factor authentication.
+///
+/// The codes are shown once to the user, and only their password hashes are stored,
+/// with [`HashScheme::auto`] or the given scheme. The codes carry about 50 bits of
+/// entropy, so a fast digest could be brute-forced from a leaked table. With the `serde`
+/// feature, the type can be stored as is.
+///
+/// ```
+/// use email_pass::{HashScheme, RecoveryCodes};
+///
+/// let (codes, mut recovery_codes) = RecoveryCodes::generate_with(10, HashScheme::bcrypt(4));
+/// assert_eq!(codes[0].len(), 11); // like "k3m9p-xq2tz"
+///
+/// assert!(recovery_codes.verify(&codes[0]));
+/// assert!(!recovery_codes.verify(&codes[0]));
+/// assert_eq!(recovery_codes.remaining(), 9);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RecoveryCodes {
+    hashes: Vec<String>,
+}
+
+impl RecoveryCodes {
+    /// Generates `count` random codes, using the OS secure random generator, hashed
+    /// with [`HashScheme::auto`].
+    ///
+    /// # Returns
+    ///
+    /// The formatted codes, to be shown to the user, and the [`RecoveryCodes`]
+    /// to be stored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS random generator is not available, or if the hashing fails.
+    pub fn generate(count: usize) -> (Vec<String>, Self) {
+        Self::generate_with(count, HashScheme::auto())
+    }
+
+    /// Generates `count` random codes, hashed with the scheme. See
+    /// [`RecoveryCodes::generate`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the OS random generator is not available, or if the scheme parameters
+    /// are invalid.
+    pub fn generate_with(count: usize, scheme: HashScheme) -> (Vec<String>, Self) {
+        let codes: Vec<String> = (0..count)
+            .map(|_| {
+                let code = random_string(GROUP_LEN * 2, CODE_ALPHABET);
+                format!("{}-{}", &code[..GROUP_LEN], &code[GROUP_LEN..])
+            })
+            .collect();
+        let hashes = codes
+            .iter()
+            .map(|code| {
+                scheme
+                    .hash(&Password::new(&normalize(code)))
+                    .expect("the recovery codes are hashed with a valid scheme")
+                    .as_str()
+                    .to_string()
+            })
+            .collect();
+
+        (codes, Self { hashes })
+    }
+
+    /// Restores the stored hashes of the unused codes.
+    pub fn from_hashes(hashes: Vec<String>) -> Self {
+        Self { hashes }
+    }
+
+    /// Returns the password hashes of the unused codes.
+    pub fn hashes(&self) -> &[String] {
+        &self.hashes
+    }
+
+    /// Returns the count of unused codes.
+    pub fn remaining(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Verifies a code, ignoring the case, the dashes and the spaces, and removes it
+    /// if it matches. The code is verified against every hash until one matches, so
+    /// the cost grows with the remaining codes.
+    pub fn verify(&mut self, code: &str) -> bool {
+        let code = normalize(code);
+        let position = self
+            .hashes
+            .iter()
+            .position(|stored| verify_hash(&code, stored).unwrap_or(false));
+
+        position.map(|index| self.hashes.remove(index)).is_some()
+    }
+}
+
+/// Returns the code without the dashes and the spaces, lowercased.
+fn normalize(code: &str) -> String {
+    code.chars()
+        .filter(|char| *char != '-' && !char.is_whitespace())
+        .map(|char| char.to_ascii_lowercase())
+        .collect()
+}
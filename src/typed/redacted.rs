@@ -0,0 +1,95 @@
+use std::fmt::{Debug, Display, Formatter};
+
+use serde::{Serialize, Serializer};
+
+#[cfg(feature = "email")]
+use crate::typed::email::Email;
+#[cfg(feature = "password")]
+use crate::typed::password::{Encrypt, Password, Raw};
+
+/// Mask of the redacted passwords.
+#[cfg(feature = "password")]
+const MASK: &str = "***";
+
+/// Serialization wrapper that always emits the masked value of the credentials, used
+/// in structured logs and debugging dumps.
+///
+/// The emails keep only the first character of the username, like `j***@example.com`,
+/// and the raw and encrypted passwords are replaced with `***`.
+///
+#[cfg_attr(all(feature = "email", feature = "password"), doc = "```")]
+#[cfg_attr(not(all(feature = "email", feature = "password")), doc = "```ignore")]
+/// use std::str::FromStr;
+/// use email_pass::{Email, Password, Redacted};
+///
+/// #[derive(serde::Serialize)]
+/// struct LoginRequest {
+///     email: Redacted<Email>,
+///     password: Redacted<Password<email_pass::Raw>>,
+/// }
+///
+/// let request = LoginRequest {
+///     email: Redacted(Email::from_str("john@example.com")?),
+///     password: Redacted(Password::new("ThisIsAPassPhrase.And.Secure.Password")),
+/// };
+/// assert_eq!(
+///     serde_json::to_string(&request).unwrap(),
+///     r#"{"email":"j***@example.com","password":"***"}"#
+/// );
+/// # Ok::<(), email_pass::EmailError>(())
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct Redacted<T>(pub T);
+
+/// Value that can be masked by [`Redacted`].
+pub trait Redact {
+    fn fmt_redacted(&self, f: &mut Formatter<'_>) -> std::fmt::Result;
+}
+
+#[cfg(feature = "email")]
+impl Redact for Email {
+    fn fmt_redacted(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:#}")
+    }
+}
+
+#[cfg(feature = "password")]
+impl Redact for Password<Raw> {
+    fn fmt_redacted(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(MASK)
+    }
+}
+
+#[cfg(feature = "password")]
+impl<Family> Redact for Password<Encrypt<Family>> {
+    fn fmt_redacted(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(MASK)
+    }
+}
+
+impl<T: Redact + ?Sized> Redact for &T {
+    fn fmt_redacted(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt_redacted(f)
+    }
+}
+
+impl<T: Redact> Display for Redacted<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt_redacted(f)
+    }
+}
+
+impl<T: Redact> Debug for Redacted<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Redacted(\"{self}\")")
+    }
+}
+
+impl<T: Redact> Serialize for Redacted<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
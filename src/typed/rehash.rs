@@ -0,0 +1,319 @@
+use std::fmt::{Display, Formatter};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::errors::PasswordError;
+#[cfg(feature = "argon2")]
+use crate::typed::argon2_params::Argon2Params;
+#[cfg(feature = "argon2")]
+use crate::typed::hash_parts::phc_param;
+use crate::typed::hash_parts::HashParts;
+use crate::typed::password::{default_cost, Encrypt, HashFamily, Password, Raw};
+use crate::typed::scheme::HashScheme;
+
+/// Prefix of the PBKDF2-HMAC-SHA256 hashes of Django.
+pub(crate) const DJANGO_PBKDF2_PREFIX: &str = "pbkdf2_sha256$";
+
+/// Hash algorithms recognized in the encrypted passwords.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[non_exhaustive]
+pub enum HashAlgorithm {
+    Bcrypt,
+    Argon2,
+    Scrypt,
+    /// PBKDF2-HMAC-SHA256 with the Django format, `pbkdf2_sha256$iterations$salt$hash`.
+    #[cfg_attr(feature = "serde", serde(rename = "pbkdf2_sha256"))]
+    Pbkdf2Sha256,
+}
+
+impl HashAlgorithm {
+    /// Detects the algorithm of a hash by its prefix.
+    pub fn detect(hash: &str) -> Option<Self> {
+        if hash.starts_with(DJANGO_PBKDF2_PREFIX) {
+            return Some(Self::Pbkdf2Sha256);
+        }
+
+        let prefix = hash.strip_prefix('$')?.split('$').next()?;
+        match prefix {
+            "2a" | "2b" | "2x" | "2y" => Some(Self::Bcrypt),
+            "argon2id" | "argon2i" | "argon2d" => Some(Self::Argon2),
+            "scrypt" => Some(Self::Scrypt),
+            _ => None,
+        }
+    }
+}
+
+impl Display for HashAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HashAlgorithm::Bcrypt => f.write_str("bcrypt"),
+            HashAlgorithm::Argon2 => f.write_str("argon2"),
+            HashAlgorithm::Scrypt => f.write_str("scrypt"),
+            HashAlgorithm::Pbkdf2Sha256 => f.write_str("pbkdf2_sha256"),
+        }
+    }
+}
+
+/// Criteria to decide if an encrypted password must be hashed again, for example
+/// after increasing the cost of the new hashes.
+///
+/// With the `serde` feature, the policy can be read from the application config:
+/// ```json
+/// { "algorithm": "bcrypt", "min_cost": 12 }
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RehashPolicy {
+    algorithm: HashAlgorithm,
+    min_cost: u32,
+}
+
+impl RehashPolicy {
+    /// Creates a policy requiring bcrypt hashes with at least the [`default_cost`].
+    ///
+    /// [`default_cost`]: crate::default_cost
+    pub fn new() -> Self {
+        Self {
+            algorithm: HashAlgorithm::Bcrypt,
+            min_cost: default_cost(),
+        }
+    }
+
+    /// Sets the algorithm preferred for the new hashes.
+    pub fn algorithm(mut self, algorithm: HashAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Sets the minimum cost accepted in the stored hashes.
+    pub fn min_cost(mut self, min_cost: u32) -> Self {
+        self.min_cost = min_cost;
+        self
+    }
+
+    pub fn preferred_algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    pub fn target_cost(&self) -> u32 {
+        self.min_cost
+    }
+
+    /// Returns the scheme of the new hashes: the preferred algorithm with the minimum
+    /// cost as the bcrypt cost, the iterations of argon2 and PBKDF2, or the `log_n` of
+    /// scrypt. The other parameters are the recommended of [`HashScheme`].
+    ///
+    /// Returns `None` if the backend of the preferred algorithm is not compiled-in, or
+    /// the cost doesn't fit in its parameter.
+    pub fn hash_scheme(&self) -> Option<HashScheme> {
+        match self.algorithm {
+            HashAlgorithm::Bcrypt => Some(HashScheme::bcrypt(self.min_cost)),
+            #[cfg(feature = "argon2")]
+            HashAlgorithm::Argon2 => {
+                let params = Argon2Params::owasp_minimum();
+                Some(HashScheme::Argon2 {
+                    memory_kib: params.memory_kib(),
+                    iterations: self.min_cost,
+                    parallelism: params.parallelism(),
+                    output_len: params.output_len(),
+                })
+            }
+            #[cfg(feature = "pbkdf2")]
+            HashAlgorithm::Pbkdf2Sha256 => Some(HashScheme::Pbkdf2Sha256 {
+                iterations: self.min_cost,
+            }),
+            #[cfg(feature = "scrypt")]
+            HashAlgorithm::Scrypt => Some(HashScheme::Scrypt {
+                log_n: u8::try_from(self.min_cost).ok()?,
+                r: scrypt::Params::RECOMMENDED_R,
+                p: scrypt::Params::RECOMMENDED_P,
+            }),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    /// Checks if the encrypted password uses other algorithm than the preferred,
+    /// or a cost below the minimum. Unrecognized hashes always require a rehash.
+    ///
+    /// With argon2 as the preferred algorithm, the argon2i and argon2d hashes also
+    /// require a rehash to argon2id, and the memory, the iterations and the parallelism
+    /// are compared with the parameters of [`RehashPolicy::hash_scheme`].
+    pub fn requires_rehash<Family>(&self, password: &Password<Encrypt<Family>>) -> bool {
+        let hash = password.as_ref();
+        if HashAlgorithm::detect(hash) != Some(self.algorithm) {
+            return true;
+        }
+        if self.algorithm == HashAlgorithm::Argon2 && !hash.starts_with("$argon2id$") {
+            return true;
+        }
+
+        #[cfg(feature = "argon2")]
+        if let Some(HashScheme::Argon2 {
+            memory_kib,
+            iterations,
+            parallelism,
+            ..
+        }) = self.hash_scheme()
+        {
+            return [("m", memory_kib), ("t", iterations), ("p", parallelism)]
+                .into_iter()
+                .any(|(name, min)| phc_param(hash, name).is_none_or(|value| value < min));
+        }
+
+        hash_cost(hash).is_none_or(|cost| cost < self.min_cost)
+    }
+}
+
+impl<Family> Password<Encrypt<Family>> {
+    /// Checks if the hash falls below the policy, to hash the password again after the
+    /// next successful login. Same as [`RehashPolicy::requires_rehash`].
+    ///
+    /// ```
+    /// use email_pass::{HashAlgorithm, Password, RehashPolicy};
+    ///
+    /// let policy = RehashPolicy::new().algorithm(HashAlgorithm::Bcrypt).min_cost(12);
+    /// let password = Password::from_encrypt("$2b$04$teRReyH3sVfCd8JA71Sm6xekdy6KhRIzYYERUEUC/.mS2Ms3ByM.q")?;
+    /// assert!(password.needs_rehash(&policy));
+    /// # Ok::<(), email_pass::PasswordError>(())
+    /// ```
+    pub fn needs_rehash(&self, policy: &RehashPolicy) -> bool {
+        policy.requires_rehash(self)
+    }
+}
+
+impl<Family: HashFamily> Password<Encrypt<Family>> {
+    /// Verifies the raw password and, if the hash requires a rehash by the policy,
+    /// hashes the raw password again with [`RehashPolicy::hash_scheme`], so the outdated
+    /// algorithms are upgraded to the preferred one, like bcrypt to argon2.
+    ///
+    /// ```
+    /// use email_pass::{Password, RehashPolicy};
+    ///
+    /// let raw_password = Password::new("ThisIsAPassPhrase.And.Secure.Password");
+    /// let stored_password = raw_password.clone().to_encrypt(4)?;
+    ///
+    /// let policy = RehashPolicy::new().min_cost(5);
+    /// if let Some(new_password) = stored_password.verify_and_rehash(&raw_password, &policy)? {
+    ///     // Store the new hash in the user table
+    ///     assert!(new_password.as_str().starts_with("$2b$05$"));
+    /// }
+    /// # Ok::<(), email_pass::PasswordError>(())
+    /// ```
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some(new_password))` - If the password matches and the hash is outdated.
+    /// * `Ok(None)` - If the password matches and the hash is up to date.
+    /// * `Err::PasswordMismatch` - If the password doesn't match.
+    /// * `Err::PasswordVerification` - If the hash can't be verified.
+    /// * `Err::InvalidCost` - If the password matches, but the minimum cost is an invalid
+    ///   bcrypt cost.
+    /// * `Err::PasswordEncryption` - If the password matches, but the backend of the
+    ///   preferred algorithm is not compiled-in, or the minimum cost is invalid for it.
+    pub fn verify_and_rehash(
+        &self,
+        raw_password: &Password<Raw>,
+        policy: &RehashPolicy,
+    ) -> Result<Option<Password<Encrypt>>, PasswordError> {
+        let matches = self
+            .verify(raw_password)
+            .map_err(|_| PasswordError::PasswordVerification)?;
+        if !matches {
+            return Err(PasswordError::PasswordMismatch);
+        }
+
+        if !policy.requires_rehash(self) {
+            return Ok(None);
+        }
+
+        policy
+            .hash_scheme()
+            .ok_or(PasswordError::PasswordEncryption)?
+            .hash(raw_password)
+            .map(Some)
+    }
+}
+
+/// Extracts the cost of a hash with the modular crypt format, like `$2b$12$...`, of a
+/// PHC string: the `ln` parameter of scrypt, like `$scrypt$ln=17,r=8,p=1$...`, or the
+/// `t` parameter of argon2, like `$argon2id$v=19$m=19456,t=2,p=1$...`, or the iterations
+/// of the Django hashes, like `pbkdf2_sha256$600000$...`.
+pub(crate) fn hash_cost(hash: &str) -> Option<u32> {
+    match HashParts::parse(hash) {
+        Some(parts) => parts.cost,
+        // Other modular crypt formats with a numeric cost
+        None => hash.split('$').nth(2)?.parse().ok(),
+    }
+}
+
+impl Default for RehashPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Verifier that upgrades the hashes below the [`RehashPolicy`] after a successful
+/// verification, the only moment the raw password is available.
+///
+/// ```
+/// use email_pass::{Password, RehashPolicy, UpgradingVerifier};
+///
+/// let verifier = UpgradingVerifier::new(RehashPolicy::new().min_cost(5), |new_password| {
+///     // Store the new hash in the user table
+///     assert!(new_password.as_str().starts_with("$2b$05$"));
+/// });
+///
+/// let raw_password = Password::new("ThisIsAPassPhrase.And.Secure.Password");
+/// let stored_password = raw_password.clone().to_encrypt(4)?;
+/// assert!(verifier.verify(&stored_password, &raw_password).unwrap());
+/// # Ok::<(), email_pass::PasswordError>(())
+/// ```
+pub struct UpgradingVerifier<F> {
+    policy: RehashPolicy,
+    on_upgrade: F,
+}
+
+impl<F> UpgradingVerifier<F>
+where
+    F: Fn(Password<Encrypt>),
+{
+    /// Creates a verifier calling `on_upgrade` with the new hashes, to persist them.
+    pub fn new(policy: RehashPolicy, on_upgrade: F) -> Self {
+        Self { policy, on_upgrade }
+    }
+
+    pub fn policy(&self) -> &RehashPolicy {
+        &self.policy
+    }
+
+    /// Verifies the raw password, like [`Password::verify`]. If the password matches
+    /// and the hash requires a rehash, the raw password is encrypted with the
+    /// [`RehashPolicy::hash_scheme`] and passed to `on_upgrade`.
+    ///
+    /// A failed rehash, or a preferred algorithm without compiled-in backend, doesn't
+    /// change the result of the verification.
+    pub fn verify<Family: HashFamily>(
+        &self,
+        encrypt_password: &Password<Encrypt<Family>>,
+        raw_password: &Password<Raw>,
+    ) -> Result<bool, PasswordError> {
+        let matches = encrypt_password.verify(raw_password)?;
+
+        if matches && self.policy.requires_rehash(encrypt_password) {
+            let new_password = self
+                .policy
+                .hash_scheme()
+                .map(|scheme| scheme.hash(raw_password));
+            if let Some(Ok(new_password)) = new_password {
+                (self.on_upgrade)(new_password);
+            }
+        }
+
+        Ok(matches)
+    }
+}
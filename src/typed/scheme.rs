@@ -0,0 +1,426 @@
+use crate::errors::PasswordError;
+#[cfg(feature = "argon2")]
+use crate::typed::argon2_params::Argon2Params;
+use crate::typed::hash_parts::split_key_id;
+#[cfg(any(feature = "argon2", feature = "pbkdf2", feature = "scrypt"))]
+use crate::typed::observer::observe_hash;
+#[cfg(feature = "argon2")]
+use crate::typed::password::Argon2;
+use crate::typed::password::{Encrypt, Password, PasswordHasher, Raw};
+use crate::typed::rehash::{HashAlgorithm, RehashPolicy};
+
+/// Backend and parameters used to hash the new passwords.
+///
+/// The verification doesn't depend on the scheme: [`Password::verify`] detects the
+/// algorithm of the stored hash, so the hashes of every compiled-in backend can be
+/// stored together.
+///
+/// ```
+/// use email_pass::{HashScheme, Password};
+///
+/// let encrypt_password = Password::new("ThisIsAPassPhrase.And.Secure.Password")
+///     .to_encrypt_scheme(HashScheme::bcrypt(4))?;
+/// assert!(encrypt_password.as_str().starts_with("$2b$04$"));
+/// # Ok::<(), email_pass::PasswordError>(())
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum HashScheme {
+    Bcrypt {
+        cost: u32,
+    },
+    /// Argon2id with the memory in KiB, the iterations, the parallelism and the length
+    /// in bytes of the hashes. Create it from [`Argon2Params`] to validate the parameters.
+    ///
+    /// [`Argon2Params`]: crate::Argon2Params
+    #[cfg(feature = "argon2")]
+    Argon2 {
+        memory_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+        output_len: usize,
+    },
+    /// PBKDF2-HMAC-SHA256 with the iterations, with the Django hash format, to keep
+    /// storing hashes compatible with a Django application.
+    #[cfg(feature = "pbkdf2")]
+    Pbkdf2Sha256 {
+        iterations: u32,
+    },
+    /// Scrypt with the cost parameters, `N = 2^log_n`.
+    #[cfg(feature = "scrypt")]
+    Scrypt {
+        log_n: u8,
+        r: u32,
+        p: u32,
+    },
+}
+
+impl HashScheme {
+    /// Selects the strongest compiled-in backend, with its recommended parameters:
+    /// Argon2id with the `argon2` feature, scrypt with the `scrypt` feature, otherwise
+    /// bcrypt with the [`default_cost`].
+    ///
+    /// Enabling a stronger backend upgrades the new hashes without code changes, and
+    /// the existing hashes are still verified.
+    ///
+    /// [`default_cost`]: crate::default_cost
+    pub fn auto() -> Self {
+        #[cfg(feature = "argon2")]
+        return Self::argon2();
+
+        #[cfg(all(feature = "scrypt", not(feature = "argon2")))]
+        return Self::scrypt();
+
+        #[cfg(not(any(feature = "argon2", feature = "scrypt")))]
+        Self::bcrypt(crate::typed::password::default_cost())
+    }
+
+    pub fn bcrypt(cost: u32) -> Self {
+        Self::Bcrypt { cost }
+    }
+
+    /// Argon2id with the minimum parameters recommended by OWASP, 19 MiB of memory,
+    /// 2 iterations and 1 degree of parallelism. See [`Argon2Params`] for other presets.
+    ///
+    /// [`Argon2Params`]: crate::Argon2Params
+    #[cfg(feature = "argon2")]
+    pub fn argon2() -> Self {
+        Argon2Params::owasp_minimum().into()
+    }
+
+    /// PBKDF2-HMAC-SHA256 with the 600 000 iterations recommended by OWASP.
+    #[cfg(feature = "pbkdf2")]
+    pub fn pbkdf2_sha256() -> Self {
+        Self::Pbkdf2Sha256 {
+            iterations: pbkdf2_backend::DEFAULT_ITERATIONS,
+        }
+    }
+
+    /// Scrypt with the recommended parameters, `N = 2^17`, `r = 8` and `p = 1`.
+    #[cfg(feature = "scrypt")]
+    pub fn scrypt() -> Self {
+        Self::Scrypt {
+            log_n: scrypt::Params::RECOMMENDED_LOG_N,
+            r: scrypt::Params::RECOMMENDED_R,
+            p: scrypt::Params::RECOMMENDED_P,
+        }
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        match self {
+            HashScheme::Bcrypt { .. } => HashAlgorithm::Bcrypt,
+            #[cfg(feature = "argon2")]
+            HashScheme::Argon2 { .. } => HashAlgorithm::Argon2,
+            #[cfg(feature = "pbkdf2")]
+            HashScheme::Pbkdf2Sha256 { .. } => HashAlgorithm::Pbkdf2Sha256,
+            #[cfg(feature = "scrypt")]
+            HashScheme::Scrypt { .. } => HashAlgorithm::Scrypt,
+        }
+    }
+
+    /// Returns the policy that requires a rehash of the hashes with other algorithm, or
+    /// below the cost of the scheme: the bcrypt cost, the iterations of argon2 and
+    /// PBKDF2, or the `log_n` of scrypt.
+    pub fn rehash_policy(&self) -> RehashPolicy {
+        let min_cost = match *self {
+            HashScheme::Bcrypt { cost } => cost,
+            #[cfg(feature = "argon2")]
+            HashScheme::Argon2 { iterations, .. } => iterations,
+            #[cfg(feature = "pbkdf2")]
+            HashScheme::Pbkdf2Sha256 { iterations } => iterations,
+            #[cfg(feature = "scrypt")]
+            HashScheme::Scrypt { log_n, .. } => u32::from(log_n),
+        };
+
+        RehashPolicy::new()
+            .algorithm(self.algorithm())
+            .min_cost(min_cost)
+    }
+
+    /// Hashes the raw password with the scheme.
+    ///
+    /// # Errors
+    ///
+    /// * `PasswordError::InvalidCost` - If the bcrypt cost is not between [`MIN_COST`] and [`MAX_COST`].
+    /// * `PasswordError::PasswordEncryption` - If the parameters are invalid or the backend fails.
+    ///
+    /// [`MIN_COST`]: crate::MIN_COST
+    /// [`MAX_COST`]: crate::MAX_COST
+    pub fn hash(&self, raw_password: &Password<Raw>) -> Result<Password<Encrypt>, PasswordError> {
+        match *self {
+            HashScheme::Bcrypt { cost } => raw_password.clone().to_encrypt(cost),
+            #[cfg(feature = "argon2")]
+            HashScheme::Argon2 {
+                memory_kib,
+                iterations,
+                parallelism,
+                output_len,
+            } => {
+                let hash = observe_hash(iterations, || {
+                    argon2_backend::hash(
+                        &raw_password.hash_input(),
+                        memory_kib,
+                        iterations,
+                        parallelism,
+                        output_len,
+                    )
+                })?;
+                Ok(Password::from_hash(hash))
+            }
+            #[cfg(feature = "pbkdf2")]
+            HashScheme::Pbkdf2Sha256 { iterations } => {
+                let hash = observe_hash(iterations, || {
+                    pbkdf2_backend::hash(&raw_password.hash_input(), iterations)
+                })?;
+                Ok(Password::from_hash(hash))
+            }
+            #[cfg(feature = "scrypt")]
+            HashScheme::Scrypt { log_n, r, p } => {
+                let hash = observe_hash(u32::from(log_n), || {
+                    scrypt_backend::hash(&raw_password.hash_input(), log_n, r, p)
+                })?;
+                Ok(Password::from_hash(hash))
+            }
+        }
+    }
+}
+
+/// The hashes are verified by their detected algorithm, like [`Password::verify`].
+impl PasswordHasher for HashScheme {
+    fn hash(&self, raw_password: &str) -> Result<String, PasswordError> {
+        let encrypt_password = self.hash(&Password::new(raw_password))?;
+        Ok(encrypt_password.as_str().to_string())
+    }
+
+    fn verify(&self, hash: &str, raw_password: &str) -> Result<bool, PasswordError> {
+        verify_hash(raw_password, hash).map_err(|_| PasswordError::PasswordVerification)
+    }
+}
+
+impl Default for HashScheme {
+    fn default() -> Self {
+        Self::auto()
+    }
+}
+
+impl Password<Raw> {
+    /// Transforms [`Password<Raw>`] to [`Password<Encrypt>`] with the backend of the scheme.
+    /// This method not checks the password's strong. See [`HashScheme::hash`].
+    pub fn to_encrypt_scheme(self, scheme: HashScheme) -> Result<Password<Encrypt>, PasswordError> {
+        scheme.hash(&self)
+    }
+
+    /// Transforms [`Password<Raw>`] to [`Password<Encrypt>`] with [`HashScheme::auto`].
+    /// This method not checks the password's strong.
+    pub fn to_encrypt_auto(self) -> Result<Password<Encrypt>, PasswordError> {
+        self.to_encrypt_scheme(HashScheme::auto())
+    }
+
+    /// Transforms [`Password<Raw>`] to [`Password<Encrypt<Argon2>>`] with the parameters
+    /// of [`HashScheme::argon2`], keeping the hash family in the type.
+    /// This method not checks the password's strong.
+    ///
+    /// ```
+    /// use email_pass::{Argon2, Encrypt, Password};
+    ///
+    /// let raw_password = Password::new("ThisIsAPassPhrase.And.Secure.Password");
+    /// let encrypt_password: Password<Encrypt<Argon2>> = raw_password.clone().to_argon2()?;
+    /// assert!(encrypt_password.as_str().starts_with("$argon2id$v=19$m=19456,t=2,p=1$"));
+    /// assert!(encrypt_password.verify(&raw_password).unwrap());
+    /// # Ok::<(), email_pass::PasswordError>(())
+    /// ```
+    #[cfg(feature = "argon2")]
+    pub fn to_argon2(self) -> Result<Password<Encrypt<Argon2>>, PasswordError> {
+        let params = Argon2Params::owasp_minimum();
+        let hash = observe_hash(params.iterations(), || {
+            argon2_backend::hash(
+                &self.hash_input(),
+                params.memory_kib(),
+                params.iterations(),
+                params.parallelism(),
+                params.output_len(),
+            )
+        })?;
+        Ok(Password::from_hash(hash))
+    }
+}
+
+/// Verifies the raw password with the backend of the detected algorithm. The hashes
+/// of unknown algorithms, or of backends not compiled-in, are rejected by bcrypt.
+///
+/// The hashes that can't be parsed by the backend return [`PasswordError::InvalidHash`].
+pub(crate) fn verify_hash(raw_password: &str, hash: &str) -> Result<bool, PasswordError> {
+    // The key id of a `KeyRing` is not part of the hash
+    let (hash, _) = split_key_id(hash);
+    #[cfg(feature = "test-util")]
+    if crate::typed::test_util::is_mock_hash(hash) {
+        return Ok(crate::typed::test_util::verify_mock(raw_password, hash));
+    }
+    match HashAlgorithm::detect(hash) {
+        #[cfg(feature = "argon2")]
+        Some(HashAlgorithm::Argon2) => phc::verify(&argon2::Argon2::default(), raw_password, hash),
+        #[cfg(feature = "scrypt")]
+        Some(HashAlgorithm::Scrypt) => phc::verify(&scrypt::Scrypt, raw_password, hash),
+        #[cfg(feature = "pbkdf2")]
+        Some(HashAlgorithm::Pbkdf2Sha256) => pbkdf2_backend::verify(raw_password, hash),
+        _ => bcrypt::verify(raw_password, hash).map_err(|_| PasswordError::InvalidHash),
+    }
+}
+
+/// Helpers of the backends with PHC string hashes, like `$argon2id$v=19$m=...`.
+#[cfg(any(feature = "argon2", feature = "scrypt"))]
+mod phc {
+    use password_hash::{Error, PasswordHash, PasswordVerifier, SaltString};
+
+    use crate::errors::PasswordError;
+    use crate::typed::generator::fill_random;
+
+    /// Length in bytes of the random salts.
+    const SALT_LEN: usize = 16;
+
+    pub(super) fn random_salt() -> Result<SaltString, PasswordError> {
+        let mut salt = [0; SALT_LEN];
+        fill_random(&mut salt);
+        SaltString::encode_b64(&salt).map_err(|_| PasswordError::PasswordEncryption)
+    }
+
+    pub(super) fn verify<V>(
+        verifier: &V,
+        raw_password: &str,
+        hash: &str,
+    ) -> Result<bool, PasswordError>
+    where
+        V: PasswordVerifier,
+    {
+        let parsed = PasswordHash::new(hash).map_err(|_| PasswordError::InvalidHash)?;
+        if parsed.hash.is_none() {
+            return Err(PasswordError::InvalidHash);
+        }
+
+        match verifier.verify_password(raw_password.as_bytes(), &parsed) {
+            Ok(()) => Ok(true),
+            Err(Error::Password) => Ok(false),
+            Err(_) => Err(PasswordError::InvalidHash),
+        }
+    }
+}
+
+#[cfg(feature = "argon2")]
+mod argon2_backend {
+    use argon2::{Algorithm, Argon2, Params, Version};
+    use password_hash::PasswordHasher;
+
+    use crate::errors::PasswordError;
+
+    pub(super) fn hash(
+        raw_password: &str,
+        memory_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+        output_len: usize,
+    ) -> Result<String, PasswordError> {
+        let params = Params::new(memory_kib, iterations, parallelism, Some(output_len))
+            .map_err(|_| PasswordError::PasswordEncryption)?;
+
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password(raw_password.as_bytes(), &super::phc::random_salt()?)
+            .map(|hash| hash.to_string())
+            .map_err(|_| PasswordError::PasswordEncryption)
+    }
+}
+
+#[cfg(feature = "scrypt")]
+mod scrypt_backend {
+    use password_hash::PasswordHasher;
+    use scrypt::{Params, Scrypt};
+
+    use crate::errors::PasswordError;
+
+    pub(super) fn hash(
+        raw_password: &str,
+        log_n: u8,
+        r: u32,
+        p: u32,
+    ) -> Result<String, PasswordError> {
+        let params = Params::new(log_n, r, p, Params::RECOMMENDED_LEN)
+            .map_err(|_| PasswordError::PasswordEncryption)?;
+
+        Scrypt
+            .hash_password_customized(
+                raw_password.as_bytes(),
+                None,
+                None,
+                params,
+                &super::phc::random_salt()?,
+            )
+            .map(|hash| hash.to_string())
+            .map_err(|_| PasswordError::PasswordEncryption)
+    }
+}
+
+#[cfg(feature = "pbkdf2")]
+mod pbkdf2_backend {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use sha2::Sha256;
+    use subtle::ConstantTimeEq;
+
+    use crate::errors::PasswordError;
+    use crate::typed::generator::random_string;
+    use crate::typed::rehash::DJANGO_PBKDF2_PREFIX;
+
+    /// Iterations recommended by OWASP for PBKDF2-HMAC-SHA256.
+    pub(super) const DEFAULT_ITERATIONS: u32 = 600_000;
+    /// Characters of the salts generated by Django.
+    const SALT_ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+    /// Length of the salts generated by Django.
+    const SALT_LEN: usize = 22;
+    /// Length in bytes of the derived keys, the output size of SHA-256.
+    const KEY_LEN: usize = 32;
+
+    pub(super) fn hash(raw_password: &str, iterations: u32) -> Result<String, PasswordError> {
+        if iterations == 0 {
+            return Err(PasswordError::PasswordEncryption);
+        }
+
+        let salt = random_string(SALT_LEN, SALT_ALPHABET);
+        let key = derive_key(raw_password, &salt, iterations);
+
+        Ok(format!(
+            "{DJANGO_PBKDF2_PREFIX}{iterations}${salt}${}",
+            STANDARD.encode(key)
+        ))
+    }
+
+    pub(super) fn verify(raw_password: &str, hash: &str) -> Result<bool, PasswordError> {
+        let mut parts = hash
+            .strip_prefix(DJANGO_PBKDF2_PREFIX)
+            .ok_or(PasswordError::InvalidHash)?
+            .splitn(3, '$');
+        let (Some(iterations), Some(salt), Some(expected)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(PasswordError::InvalidHash);
+        };
+        let iterations: u32 = iterations.parse().map_err(|_| PasswordError::InvalidHash)?;
+        let expected = STANDARD
+            .decode(expected)
+            .map_err(|_| PasswordError::InvalidHash)?;
+        if iterations == 0 || expected.len() != KEY_LEN {
+            return Err(PasswordError::InvalidHash);
+        }
+
+        let key = derive_key(raw_password, salt, iterations);
+        Ok(key.ct_eq(&expected).into())
+    }
+
+    fn derive_key(raw_password: &str, salt: &str, iterations: u32) -> [u8; KEY_LEN] {
+        let mut key = [0; KEY_LEN];
+        pbkdf2::pbkdf2_hmac::<Sha256>(
+            raw_password.as_bytes(),
+            salt.as_bytes(),
+            iterations,
+            &mut key,
+        );
+        key
+    }
+}
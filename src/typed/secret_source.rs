@@ -0,0 +1,82 @@
+use std::ffi::OsString;
+use std::path::Path;
+use std::{env, fs, io};
+
+use zeroize::Zeroize;
+
+use crate::typed::password::{Password, Raw};
+
+impl Password {
+    /// Reads a raw password from the environment variable `name` or, if it's not
+    /// defined, from the file in the variable `{name}_FILE`, like the Docker secrets.
+    /// The value is trimmed, and the intermediate buffers are wiped.
+    ///
+    /// # Errors
+    ///
+    /// * `io::ErrorKind::NotFound` - If neither variable is defined.
+    /// * `io::ErrorKind::InvalidData` - If the value is not valid UTF-8.
+    /// * The I/O error if the file can't be read.
+    ///
+    /// ```no_run
+    /// use email_pass::Password;
+    ///
+    /// // With DB_PASSWORD_FILE=/run/secrets/db_password
+    /// let password = Password::from_env("DB_PASSWORD")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn from_env(name: &str) -> io::Result<Password<Raw>> {
+        if let Some(value) = env::var_os(name) {
+            return password_from_os_string(value);
+        }
+
+        match env::var_os(format!("{name}_FILE")) {
+            Some(path) => Self::from_file(path),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("neither {name} nor {name}_FILE are defined"),
+            )),
+        }
+    }
+
+    /// Reads a raw password from a file, like the Docker secrets. The value is trimmed,
+    /// and the intermediate buffers are wiped.
+    ///
+    /// # Errors
+    ///
+    /// * `io::ErrorKind::InvalidData` - If the file is not valid UTF-8.
+    /// * The I/O error if the file can't be read.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Password<Raw>> {
+        let mut bytes = fs::read(path)?;
+        let password = password_from_bytes(&bytes);
+        bytes.zeroize();
+        password
+    }
+}
+
+fn password_from_os_string(value: OsString) -> io::Result<Password<Raw>> {
+    match value.into_string() {
+        Ok(mut value) => {
+            let password = Password::new(value.trim());
+            value.zeroize();
+            Ok(password)
+        }
+        Err(value) => {
+            let mut bytes = value.into_encoded_bytes();
+            bytes.zeroize();
+            Err(invalid_data())
+        }
+    }
+}
+
+fn password_from_bytes(bytes: &[u8]) -> io::Result<Password<Raw>> {
+    std::str::from_utf8(bytes)
+        .map(|value| Password::new(value.trim()))
+        .map_err(|_| invalid_data())
+}
+
+fn invalid_data() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "the password is not valid UTF-8",
+    )
+}
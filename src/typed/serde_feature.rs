@@ -1,11 +1,17 @@
+#[cfg(feature = "password")]
 use serde::{
     de::{Error, Unexpected, Visitor},
     Deserialize, Serialize,
 };
 
-use crate::{Password, Raw};
+#[cfg(feature = "password")]
+use std::marker::PhantomData;
 
-impl Serialize for Password {
+#[cfg(feature = "password")]
+use crate::{Any, Encrypt, HashFamily, Password, Raw};
+
+#[cfg(feature = "password")]
+impl<Family> Serialize for Password<Encrypt<Family>> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
@@ -14,10 +20,12 @@ impl Serialize for Password {
     }
 }
 
-pub struct EncryptPasswordVisitor;
+#[cfg(feature = "password")]
+pub struct EncryptPasswordVisitor<Family = Any>(PhantomData<Family>);
 
-impl<'de> Visitor<'de> for EncryptPasswordVisitor {
-    type Value = Password;
+#[cfg(feature = "password")]
+impl<'de, Family: HashFamily> Visitor<'de> for EncryptPasswordVisitor<Family> {
+    type Value = Password<Encrypt<Family>>;
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
         formatter.write_str("an hashed string")
@@ -27,7 +35,10 @@ impl<'de> Visitor<'de> for EncryptPasswordVisitor {
     where
         E: Error,
     {
-        Password::from_encrypt(str).map_err(|_| Error::invalid_value(Unexpected::Str(str), &self))
+        Password::from_encrypt(str)
+            .ok()
+            .and_then(|password| password.downcast().ok())
+            .ok_or_else(|| Error::invalid_value(Unexpected::Str(str), &self))
     }
 
     fn visit_string<E>(self, str: String) -> Result<Self::Value, E>
@@ -38,17 +49,20 @@ impl<'de> Visitor<'de> for EncryptPasswordVisitor {
     }
 }
 
-impl<'de> Deserialize<'de> for Password {
+#[cfg(feature = "password")]
+impl<'de, Family: HashFamily> Deserialize<'de> for Password<Encrypt<Family>> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        deserializer.deserialize_str(EncryptPasswordVisitor)
+        deserializer.deserialize_str(EncryptPasswordVisitor(PhantomData))
     }
 }
 
+#[cfg(feature = "password")]
 pub struct RawPasswordVisitor;
 
+#[cfg(feature = "password")]
 impl<'de> Visitor<'de> for RawPasswordVisitor {
     type Value = Password<Raw>;
 
@@ -75,6 +89,7 @@ impl<'de> Visitor<'de> for RawPasswordVisitor {
     }
 }
 
+#[cfg(feature = "password")]
 impl<'de> Deserialize<'de> for Password<Raw> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -83,3 +98,180 @@ impl<'de> Deserialize<'de> for Password<Raw> {
         deserializer.deserialize_str(RawPasswordVisitor)
     }
 }
+
+/// Lowercases the username and the domain of an [`Email`](crate::Email) when it's
+/// deserialized, so `John@Example.com` and `john@example.com` are equal values.
+///
+/// ```
+/// # use email_pass::Email;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct SignUp {
+///     #[serde(with = "email_pass::serde_helpers::lowercase")]
+///     email: Email,
+/// }
+/// ```
+#[cfg(feature = "email")]
+pub mod lowercase {
+    use std::str::FromStr;
+
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use crate::Email;
+
+    pub fn serialize<S>(email: &Email, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(email)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Email, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let email = String::deserialize(deserializer)?;
+        Email::from_str(&email.to_lowercase()).map_err(Error::custom)
+    }
+}
+
+/// Deserializes an [`Email`](crate::Email) from the query strings and the
+/// `application/x-www-form-urlencoded` bodies, where an unencoded `+` is decoded as a
+/// space: the surrounding spaces are trimmed and the spaces of the username are restored
+/// as `+`, so `john+news@example.com` is not parsed as `news@example.com`.
+///
+/// ```
+/// # use email_pass::Email;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Unsubscribe {
+///     #[serde(with = "email_pass::serde_helpers::form")]
+///     email: Email,
+/// }
+/// ```
+#[cfg(feature = "email")]
+pub mod form {
+    use std::str::FromStr;
+
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use crate::Email;
+
+    pub fn serialize<S>(email: &Email, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(email)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Email, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let email = String::deserialize(deserializer)?;
+        let email = email.trim();
+        let email = match email.rsplit_once('@') {
+            Some((username, domain)) => format!("{}@{domain}", username.replace(' ', "+")),
+            None => email.to_string(),
+        };
+
+        Email::from_str(&email).map_err(Error::custom)
+    }
+}
+
+/// Replaces an [`Email`](crate::Email) with its canonical form when it's deserialized,
+/// so the aliases of the same mailbox are equal values. See [`Email::canonical`].
+///
+/// ```
+/// # use email_pass::Email;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct SignUp {
+///     #[serde(with = "email_pass::serde_helpers::canonical")]
+///     email: Email,
+/// }
+/// ```
+///
+/// [`Email::canonical`]: crate::Email::canonical
+#[cfg(feature = "email")]
+pub mod canonical {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::Email;
+
+    pub fn serialize<S>(email: &Email, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(email)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Email, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Email::deserialize(deserializer).map(|email| email.canonical())
+    }
+}
+
+/// Serializes an [`Email`](crate::Email) with the punycode form of the domain, as
+/// required by SMTP. Deserializes both punycode and Unicode domains.
+///
+/// ```
+/// # use email_pass::Email;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Message {
+///     #[serde(with = "email_pass::serde_helpers::punycode")]
+///     to: Email,
+/// }
+/// ```
+#[cfg(feature = "idna")]
+pub mod punycode {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    use crate::Email;
+
+    pub fn serialize<S>(email: &Email, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(email)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Email, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let email = String::deserialize(deserializer)?;
+        Email::parse_idn(&email).map_err(Error::custom)
+    }
+}
+
+/// Serializes an [`Email`](crate::Email) with the Unicode form of the domain, as
+/// expected by user interfaces. Deserializes both punycode and Unicode domains.
+///
+/// ```
+/// # use email_pass::Email;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Profile {
+///     #[serde(with = "email_pass::serde_helpers::unicode")]
+///     email: Email,
+/// }
+/// ```
+#[cfg(feature = "idna")]
+pub mod unicode {
+    use serde::{Deserializer, Serializer};
+
+    use crate::Email;
+
+    pub fn serialize<S>(email: &Email, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&email.to_unicode())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Email, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        super::punycode::deserialize(deserializer)
+    }
+}
@@ -0,0 +1,126 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::errors::EmailError;
+use crate::typed::email::Email;
+
+#[inline]
+const fn is_username_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'.' | b'+' | b'-')
+}
+
+#[inline]
+const fn is_domain_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.')
+}
+
+/// Checks the email syntax in a `const` context.
+///
+/// Applies the same rules as [`Email::from_str`](std::str::FromStr) with the default
+/// pattern, but the whole input must be the email address.
+pub const fn is_valid_email(email: &str) -> bool {
+    let bytes = email.as_bytes();
+    if bytes.len() < 6 || bytes.len() > 254 {
+        return false;
+    }
+
+    // username: allowed characters, without leading, trailing or consecutive dots
+    let mut index = 0;
+    while index < bytes.len() && bytes[index] != b'@' {
+        let byte = bytes[index];
+        if !is_username_byte(byte) {
+            return false;
+        }
+        if byte == b'.' && (index == 0 || bytes[index - 1] == b'.') {
+            return false;
+        }
+        index += 1;
+    }
+    if index == 0 || index == bytes.len() || bytes[index - 1] == b'.' {
+        return false;
+    }
+
+    // domain: a label, a dot, and at least one more character
+    let domain_start = index + 1;
+    let mut dot = 0;
+    index = domain_start;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        if !is_domain_byte(byte) {
+            return false;
+        }
+        if byte == b'.' && dot == 0 {
+            dot = index;
+        }
+        index += 1;
+    }
+
+    dot > domain_start && dot + 1 < bytes.len()
+}
+
+/// Email known at compile time, validated with [`is_valid_email`].
+///
+/// The `const` validation only checks the syntax, the rules of the `addr` and `idna`
+/// features are applied by [`StaticEmail::to_email`].
+///
+/// ```
+/// use email_pass::{Email, StaticEmail};
+///
+/// const SUPPORT: StaticEmail = StaticEmail::new("support@example.com");
+///
+/// let email: Email = SUPPORT.to_email()?;
+/// assert_eq!(email.domain(), "example.com");
+/// # Ok::<(), email_pass::EmailError>(())
+/// ```
+///
+/// An invalid email doesn't compile:
+/// ```compile_fail
+/// use email_pass::StaticEmail;
+///
+/// const SUPPORT: StaticEmail = StaticEmail::new("example.com");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StaticEmail(&'static str);
+
+impl StaticEmail {
+    /// Creates a new [`StaticEmail`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the email is not valid, which is a compile error in a `const` context.
+    pub const fn new(email: &'static str) -> Self {
+        if !is_valid_email(email) {
+            panic!("invalid email");
+        }
+
+        Self(email)
+    }
+
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        self.0
+    }
+
+    /// Parses the email with [`Email::from_str`], applying the rules of [`Email`].
+    ///
+    /// # Errors
+    ///
+    /// * The errors of [`Email::from_str`], if the email is rejected by its rules.
+    pub fn to_email(&self) -> Result<Email, EmailError> {
+        Email::from_str(self.0)
+    }
+}
+
+impl Display for StaticEmail {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.0, f)
+    }
+}
+
+impl TryFrom<StaticEmail> for Email {
+    type Error = EmailError;
+
+    fn try_from(email: StaticEmail) -> Result<Self, Self::Error> {
+        email.to_email()
+    }
+}
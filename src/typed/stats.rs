@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use crate::typed::email::Email;
+
+/// Counts of a collection of emails, grouped by domain, registrable domain and TLD.
+///
+/// The domains are lowercased, and the registrable domains are approximated
+/// like [`Domain::registrable_domain`].
+///
+/// ```
+/// use std::str::FromStr;
+/// use email_pass::{Email, EmailStats};
+///
+/// let emails = ["john@mail.example.com", "jane@example.com", "joe@example.org"]
+///     .map(|email| Email::from_str(email).unwrap());
+///
+/// let stats = EmailStats::from_iter(&emails);
+/// assert_eq!(stats.total, 3);
+/// assert_eq!(stats.top_registrable_domains(1), [("example.com", 2)]);
+/// assert_eq!(stats.tlds["com"], 2);
+/// ```
+///
+/// [`Domain::registrable_domain`]: crate::Domain::registrable_domain
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EmailStats {
+    pub total: usize,
+    /// Count of emails by domain.
+    pub domains: HashMap<String, usize>,
+    /// Count of emails by registrable domain.
+    pub registrable_domains: HashMap<String, usize>,
+    /// Count of emails by TLD.
+    pub tlds: HashMap<String, usize>,
+}
+
+impl EmailStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an email to the counts.
+    pub fn insert(&mut self, email: &Email) {
+        let domain = email.domain_name();
+
+        self.total += 1;
+        count(&mut self.domains, domain.as_str());
+        count(
+            &mut self.registrable_domains,
+            domain.registrable_domain().as_str(),
+        );
+        count(&mut self.tlds, domain.tld());
+    }
+
+    /// Returns the `n` domains with most emails, in descending order.
+    pub fn top_domains(&self, n: usize) -> Vec<(&str, usize)> {
+        top(&self.domains, n)
+    }
+
+    /// Returns the `n` registrable domains with most emails, in descending order.
+    pub fn top_registrable_domains(&self, n: usize) -> Vec<(&str, usize)> {
+        top(&self.registrable_domains, n)
+    }
+
+    /// Returns the `n` TLDs with most emails, in descending order.
+    pub fn top_tlds(&self, n: usize) -> Vec<(&str, usize)> {
+        top(&self.tlds, n)
+    }
+}
+
+fn count(counts: &mut HashMap<String, usize>, key: &str) {
+    *counts.entry(key.to_lowercase()).or_default() += 1;
+}
+
+/// The ties are sorted by name, so the result is deterministic.
+fn top(counts: &HashMap<String, usize>, n: usize) -> Vec<(&str, usize)> {
+    let mut top: Vec<_> = counts
+        .iter()
+        .map(|(key, count)| (key.as_str(), *count))
+        .collect();
+    top.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    top.truncate(n);
+
+    top
+}
+
+impl<'a> FromIterator<&'a Email> for EmailStats {
+    fn from_iter<I: IntoIterator<Item = &'a Email>>(emails: I) -> Self {
+        let mut stats = Self::new();
+        emails.into_iter().for_each(|email| stats.insert(email));
+
+        stats
+    }
+}
+
+impl FromIterator<Email> for EmailStats {
+    fn from_iter<I: IntoIterator<Item = Email>>(emails: I) -> Self {
+        let mut stats = Self::new();
+        emails.into_iter().for_each(|email| stats.insert(&email));
+
+        stats
+    }
+}
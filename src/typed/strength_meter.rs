@@ -0,0 +1,235 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use std::fmt::{Debug, Formatter};
+
+use crate::errors::PasswordError;
+use crate::typed::password_checker::{DictionaryChecker, Entropy, PasswordStrengthChecker};
+
+/// Level of a [`StrengthMeter`], reached when the password entropy passes both
+/// thresholds.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StrengthLevel {
+    pub label: String,
+    /// Minimum score, from `0` (too guessable) to `4` (very unguessable).
+    pub min_score: u8,
+    /// Minimum estimated guesses to crack the password, in base 10 logarithm.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub min_guesses_log10: f64,
+}
+
+impl StrengthLevel {
+    pub fn new(label: impl Into<String>, min_score: u8) -> Self {
+        Self {
+            label: label.into(),
+            min_score,
+            min_guesses_log10: 0.0,
+        }
+    }
+
+    pub fn min_guesses_log10(mut self, min_guesses_log10: f64) -> Self {
+        self.min_guesses_log10 = min_guesses_log10;
+        self
+    }
+
+    fn is_reached(&self, entropy: &Entropy) -> bool {
+        entropy.score() >= self.min_score && entropy.guesses_log10() >= self.min_guesses_log10
+    }
+
+    fn rank(&self) -> (u8, f64) {
+        (self.min_score, self.min_guesses_log10)
+    }
+}
+
+/// Mapping of the password entropy to user-defined labels, like the levels of the
+/// strength meter of a sign-up form.
+///
+/// With the `serde` feature, the same definition can be sent to the frontend meter and
+/// enforced by the backend with [`StrengthMeter::check`]:
+/// ```json
+/// {
+///   "levels": [
+///     { "label": "Weak", "min_score": 0 },
+///     { "label": "Fair", "min_score": 2 },
+///     { "label": "Good", "min_score": 3 },
+///     { "label": "Excellent", "min_score": 4, "min_guesses_log10": 12.0 }
+///   ],
+///   "required": "Good"
+/// }
+/// ```
+///
+/// ```
+/// use email_pass::{StrengthLevel, StrengthMeter};
+///
+/// let meter = StrengthMeter::empty()
+///     .level(StrengthLevel::new("Weak", 0))
+///     .level(StrengthLevel::new("Strong", 3))
+///     .required("Strong")
+///     .unwrap();
+///
+/// assert_eq!(meter.rate_str("password").unwrap().label, "Weak");
+/// assert!(meter.check("ThisIsAPassPhrase.And.Secure.Password").is_ok());
+/// ```
+///
+/// A deserialized meter whose required label is not in its levels is rejected, like
+/// [`StrengthMeter::required`] does.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "StrengthMeterDef"))]
+pub struct StrengthMeter {
+    levels: Vec<StrengthLevel>,
+    required: Option<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    checker: DictionaryChecker,
+}
+
+/// Fields of a serialized [`StrengthMeter`], validated by [`StrengthMeter::required`].
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct StrengthMeterDef {
+    levels: Vec<StrengthLevel>,
+    #[serde(default)]
+    required: Option<String>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<StrengthMeterDef> for StrengthMeter {
+    type Error = PasswordError;
+
+    fn try_from(def: StrengthMeterDef) -> Result<Self, Self::Error> {
+        let meter = def.levels.into_iter().fold(Self::empty(), Self::level);
+        match def.required {
+            Some(label) => meter.required(label),
+            None => Ok(meter),
+        }
+    }
+}
+
+impl StrengthMeter {
+    /// Creates a meter with the levels `Weak`, `Fair`, `Good` and `Excellent`, for the
+    /// scores `0`, `2`, `3` and `4`, requiring `Good` like [`PasswordStrengthChecker::new`].
+    pub fn new() -> Self {
+        Self::empty()
+            .level(StrengthLevel::new("Weak", 0))
+            .level(StrengthLevel::new("Fair", 2))
+            .level(StrengthLevel::new("Good", 3))
+            .level(StrengthLevel::new("Excellent", 4))
+            .required("Good")
+            .expect("the Good level is added above")
+    }
+
+    /// Creates a meter without levels.
+    pub fn empty() -> Self {
+        Self {
+            levels: Vec::new(),
+            required: None,
+            checker: PasswordStrengthChecker::new().into(),
+        }
+    }
+
+    /// Sets the checker whose languages and dictionary are used to estimate the entropy,
+    /// instead of [`PasswordStrengthChecker::new`]. Its minimum length and strong are
+    /// ignored, the levels are checked instead. Not serialized.
+    ///
+    /// ```
+    /// use email_pass::{Language, PasswordStrengthChecker, StrengthMeter};
+    ///
+    /// let meter = StrengthMeter::new().checker(PasswordStrengthChecker::new().language(Language::German));
+    /// assert!(meter.check("Geheim2024!").is_err());
+    /// ```
+    pub fn checker(mut self, checker: impl Into<DictionaryChecker>) -> Self {
+        self.checker = checker.into();
+        self
+    }
+
+    /// Adds a level. The levels keep the order they were added, to be displayed.
+    pub fn level(mut self, level: StrengthLevel) -> Self {
+        self.levels.push(level);
+        self
+    }
+
+    /// Sets the label of the minimum level accepted by [`StrengthMeter::check`]. Add
+    /// the levels first.
+    ///
+    /// # Errors
+    ///
+    /// * `Error::UnknownStrengthLevel` - If the label is not in the levels.
+    pub fn required(mut self, label: impl Into<String>) -> Result<Self, PasswordError> {
+        let label = label.into();
+        if !self.levels.iter().any(|level| level.label == label) {
+            return Err(PasswordError::UnknownStrengthLevel(label));
+        }
+
+        self.required = Some(label);
+        Ok(self)
+    }
+
+    pub fn levels(&self) -> &[StrengthLevel] {
+        &self.levels
+    }
+
+    /// Returns the required level, if any.
+    pub fn required_level(&self) -> Option<&StrengthLevel> {
+        let required = self.required.as_deref()?;
+        self.levels.iter().find(|level| level.label == required)
+    }
+
+    /// Returns the highest level reached by the entropy, or `None` if no level is reached.
+    pub fn rate(&self, entropy: &Entropy) -> Option<&StrengthLevel> {
+        self.levels
+            .iter()
+            .filter(|level| level.is_reached(entropy))
+            .max_by(|a, b| {
+                a.rank()
+                    .partial_cmp(&b.rank())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+    }
+
+    /// Estimates the entropy of the raw password, and returns the highest level reached.
+    pub fn rate_str(&self, raw_password: &str) -> Option<&StrengthLevel> {
+        let entropy = self.checker.entropy(raw_password).ok()?;
+        self.rate(&entropy)
+    }
+
+    /// Checks that the raw password reaches the required level.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(level)` - The highest level reached by the password.
+    /// * `Error::StrengthLevel` - If the password doesn't reach the required level, or
+    ///   any level.
+    /// * `Error::BlankPassword` - If the password is empty.
+    pub fn check(&self, raw_password: &str) -> Result<&StrengthLevel, PasswordError> {
+        let entropy = self.checker.entropy(raw_password)?;
+        let level = self.rate(&entropy).ok_or(PasswordError::StrengthLevel)?;
+
+        match self.required_level() {
+            Some(required) if !required.is_reached(&entropy) => Err(PasswordError::StrengthLevel),
+            _ => Ok(level),
+        }
+    }
+}
+
+/// Compares the levels and the required label, the checkers are not compared.
+impl PartialEq for StrengthMeter {
+    fn eq(&self, other: &Self) -> bool {
+        self.levels == other.levels && self.required == other.required
+    }
+}
+
+impl Debug for StrengthMeter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StrengthMeter")
+            .field("levels", &self.levels)
+            .field("required", &self.required)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for StrengthMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,153 @@
+use std::sync::LazyLock;
+
+use crate::errors::PasswordError;
+use crate::typed::password_checker::deleet;
+use crate::typed::wordlist::Dictionary;
+
+/// Most common passwords, used instead of the [`zxcvbn`] dictionaries.
+static COMMON_PASSWORDS: LazyLock<Dictionary> =
+    LazyLock::new(|| Dictionary::from_words(include_str!("wordlists/common.txt").lines()));
+
+/// Strength estimation of the length, character classes and common passwords rules,
+/// returned by [`PasswordStrengthChecker::check`] without the `zxcvbn` feature.
+///
+/// The score has the same scale as the `zxcvbn` score, from `0` to `4`.
+///
+/// [`PasswordStrengthChecker::check`]: crate::PasswordStrengthChecker::check
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Entropy {
+    guesses_log10: f64,
+    score: u8,
+}
+
+impl Entropy {
+    /// Returns the estimated count of guesses to crack the password, in base 10 logarithm.
+    pub fn guesses_log10(&self) -> f64 {
+        self.guesses_log10
+    }
+
+    /// Returns the strength score, from `0` (too guessable) to `4` (very unguessable).
+    pub fn score(&self) -> u8 {
+        self.score
+    }
+}
+
+/// Estimates the guesses of a brute force over the character classes of the password.
+///
+/// The common passwords, the dictionary words and the years count as a single character,
+/// the repeated characters and the sequences like `abc` or `321` count once, the words
+/// with leetspeak substitutions count the substituted characters, and a common password
+/// with only a suffix of digits or symbols scores `0`.
+pub(crate) fn estimate(
+    raw_password: &str,
+    dictionaries: &[&Dictionary],
+) -> Result<Entropy, PasswordError> {
+    if raw_password.trim().is_empty() {
+        return Err(PasswordError::BlankPassword);
+    }
+
+    let base = raw_password.trim_end_matches(|char: char| !char.is_alphabetic());
+    if COMMON_PASSWORDS
+        .find_spans(base)
+        .contains(&(0, base.chars().count()))
+    {
+        return Ok(Entropy {
+            guesses_log10: 0.0,
+            score: 0,
+        });
+    }
+
+    let chars: Vec<char> = raw_password.chars().collect();
+    let mut covered = vec![false; chars.len()];
+    let mut words = 0;
+    // The leetspeak substitutions keep the positions of the chars, and the substituted
+    // chars of the words found after reverting them are not covered
+    let deleeted: Vec<char> = deleet(raw_password).chars().collect();
+    let mut spans = Vec::new();
+    for password in [raw_password, &deleeted.iter().collect::<String>()] {
+        spans.extend(COMMON_PASSWORDS.find_spans(password));
+        for dictionary in dictionaries {
+            spans.extend(dictionary.find_spans(password));
+        }
+    }
+    spans.extend(year_spans(&chars));
+
+    for (start, end) in spans.into_iter().filter(|(_, end)| *end <= chars.len()) {
+        if covered[start..end].iter().all(|covered| !covered) {
+            words += 1;
+        }
+        for index in start..end {
+            let is_substituted = deleeted
+                .get(index)
+                .is_some_and(|char| !char.eq_ignore_ascii_case(&chars[index]));
+            covered[index] |= !is_substituted;
+        }
+    }
+
+    let free_chars = chars
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !covered[*index] && !continues_run(&chars, *index))
+        .count();
+    let guesses_log10 = (free_chars + words) as f64 * pool_size(&chars).log10();
+
+    Ok(Entropy {
+        guesses_log10,
+        score: score(guesses_log10),
+    })
+}
+
+/// Returns the spans of the years from 1900 to 2099.
+fn year_spans(chars: &[char]) -> Vec<(usize, usize)> {
+    (0..chars.len().saturating_sub(3))
+        .filter(|start| {
+            let year = &chars[*start..start + 4];
+            matches!(year[..2], ['1', '9'] | ['2', '0'])
+                && year[2..].iter().all(char::is_ascii_digit)
+        })
+        .map(|start| (start, start + 4))
+        .collect()
+}
+
+/// Checks if the character repeats the previous one, or continues a sequence of
+/// consecutive characters with it, like `ab` or `21`.
+fn continues_run(chars: &[char], index: usize) -> bool {
+    let Some(previous) = index.checked_sub(1).map(|index| chars[index] as u32) else {
+        return false;
+    };
+    let current = chars[index] as u32;
+
+    current == previous || (chars[index].is_ascii_alphanumeric() && current.abs_diff(previous) == 1)
+}
+
+/// Sum of the sizes of the character classes used in the password.
+fn pool_size(chars: &[char]) -> f64 {
+    let has = |class: fn(&char) -> bool| chars.iter().any(class);
+    let classes: [(bool, f64); 5] = [
+        (has(char::is_ascii_lowercase), 26.0),
+        (has(char::is_ascii_uppercase), 26.0),
+        (has(char::is_ascii_digit), 10.0),
+        (
+            has(char::is_ascii_punctuation) || has(|char| *char == ' '),
+            33.0,
+        ),
+        (has(|char| !char.is_ascii()), 100.0),
+    ];
+
+    classes
+        .iter()
+        .filter(|(used, _)| *used)
+        .map(|(_, size)| size)
+        .sum()
+}
+
+/// Score thresholds of `zxcvbn`, in guesses.
+fn score(guesses_log10: f64) -> u8 {
+    match guesses_log10 {
+        guesses if guesses < 3.0 => 0,
+        guesses if guesses < 6.0 => 1,
+        guesses if guesses < 8.0 => 2,
+        guesses if guesses < 10.0 => 3,
+        _ => 4,
+    }
+}
@@ -0,0 +1,79 @@
+use std::future::Future;
+use std::str::FromStr;
+
+use crate::errors::EmailError;
+use crate::typed::email::Email;
+use crate::typed::email_set::EmailSet;
+
+/// List of addresses that must not receive emails, like the bounces and the
+/// complaints reported by the email provider.
+pub trait SuppressionList {
+    fn is_suppressed(&self, email: &Email) -> bool;
+}
+
+/// Asynchronous [`SuppressionList`], for lists stored in a database or a remote
+/// service. Every [`SuppressionList`] implements it.
+pub trait AsyncSuppressionList {
+    fn is_suppressed(&self, email: &Email) -> impl Future<Output = bool> + Send;
+}
+
+impl<L> AsyncSuppressionList for L
+where
+    L: SuppressionList + Sync,
+{
+    fn is_suppressed(&self, email: &Email) -> impl Future<Output = bool> + Send {
+        std::future::ready(SuppressionList::is_suppressed(self, email))
+    }
+}
+
+/// In-memory list, the aliases of the stored emails are suppressed too.
+impl SuppressionList for EmailSet {
+    fn is_suppressed(&self, email: &Email) -> bool {
+        self.contains(email)
+    }
+}
+
+impl Email {
+    /// Parses the email like [`Email::from_str`], and checks that it's not suppressed.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(email)` - If the email is valid and not suppressed.
+    /// * `EmailError::Suppressed` - If the email is in the suppression list.
+    /// * The parsing error otherwise.
+    ///
+    /// ```
+    /// use email_pass::{Email, EmailError, EmailSet};
+    /// use std::str::FromStr;
+    ///
+    /// let mut bounces = EmailSet::new();
+    /// bounces.insert(Email::from_str("john@example.com").unwrap());
+    ///
+    /// let result = Email::parse_unsuppressed("John+news@example.com", &bounces);
+    /// assert_eq!(result, Err(EmailError::Suppressed));
+    /// ```
+    pub fn parse_unsuppressed<L>(email: &str, list: &L) -> Result<Self, EmailError>
+    where
+        L: SuppressionList + ?Sized,
+    {
+        let email = Self::from_str(email)?;
+        if list.is_suppressed(&email) {
+            return Err(EmailError::Suppressed);
+        }
+
+        Ok(email)
+    }
+
+    /// Asynchronous version of [`Email::parse_unsuppressed`].
+    pub async fn parse_unsuppressed_async<L>(email: &str, list: &L) -> Result<Self, EmailError>
+    where
+        L: AsyncSuppressionList + ?Sized,
+    {
+        let email = Self::from_str(email)?;
+        if list.is_suppressed(&email).await {
+            return Err(EmailError::Suppressed);
+        }
+
+        Ok(email)
+    }
+}
@@ -0,0 +1,111 @@
+use std::time::{Duration, SystemTime};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::errors::PasswordError;
+use crate::typed::password::{Encrypt, Password, Raw};
+
+/// Length of the generated temporary passwords.
+const TEMPORARY_PASSWORD_LEN: usize = 16;
+
+/// Generated password accepted only once and before its expiry, like the password
+/// assigned by an admin when resetting the password of a user.
+///
+/// Only the hash is stored, together with the expiry and the single-use flag. With
+/// the `serde` feature, the type can be stored as is.
+///
+/// ```
+/// use std::time::Duration;
+/// use email_pass::{PasswordError, TemporaryPassword};
+///
+/// // The admin resets the password, and sends the raw password to the user
+/// let (raw_password, mut temporary) = TemporaryPassword::generate(Duration::from_secs(3600))?;
+///
+/// // The user logs in with the temporary password, and must set a new password
+/// assert_eq!(temporary.verify_once(&raw_password), Ok(true));
+/// assert_eq!(temporary.verify_once(&raw_password), Err(PasswordError::UsedPassword));
+/// # Ok::<(), PasswordError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TemporaryPassword {
+    password: Password<Encrypt>,
+    expires_at: SystemTime,
+    used: bool,
+}
+
+impl TemporaryPassword {
+    /// Generates a random password valid during `ttl`.
+    ///
+    /// # Returns
+    ///
+    /// The raw password, to be delivered to the user, and the [`TemporaryPassword`]
+    /// to be stored.
+    pub fn generate(ttl: Duration) -> Result<(Password<Raw>, Self), PasswordError> {
+        let raw_password = Password::generate(TEMPORARY_PASSWORD_LEN);
+        let password = raw_password.clone().to_encrypt_default()?;
+
+        Ok((
+            raw_password,
+            Self {
+                password,
+                expires_at: SystemTime::now() + ttl,
+                used: false,
+            },
+        ))
+    }
+
+    /// Restores a stored temporary password.
+    pub fn from_parts(password: Password<Encrypt>, expires_at: SystemTime, used: bool) -> Self {
+        Self {
+            password,
+            expires_at,
+            used,
+        }
+    }
+
+    pub fn password(&self) -> &Password<Encrypt> {
+        &self.password
+    }
+
+    pub fn expires_at(&self) -> SystemTime {
+        self.expires_at
+    }
+
+    pub fn is_used(&self) -> bool {
+        self.used
+    }
+
+    pub fn is_expired(&self) -> bool {
+        SystemTime::now() >= self.expires_at
+    }
+
+    /// Verifies the raw password, marking the temporary password as used if it matches.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(matches)` - If the temporary password can be used.
+    /// * `PasswordError::UsedPassword` - If the temporary password was already used.
+    /// * `PasswordError::ExpiredPassword` - If the temporary password has expired.
+    /// * `PasswordError::PasswordVerification` - If bcrypt fails.
+    pub fn verify_once(&mut self, raw_password: &Password<Raw>) -> Result<bool, PasswordError> {
+        if self.used {
+            return Err(PasswordError::UsedPassword);
+        }
+
+        if self.is_expired() {
+            return Err(PasswordError::ExpiredPassword);
+        }
+
+        let matches = self
+            .password
+            .verify(raw_password)
+            .map_err(|_| PasswordError::PasswordVerification)?;
+        if matches {
+            self.used = true;
+        }
+
+        Ok(matches)
+    }
+}
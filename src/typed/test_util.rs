@@ -0,0 +1,94 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use subtle::ConstantTimeEq;
+
+use crate::errors::PasswordError;
+use crate::typed::digest::sha256_hex;
+use crate::typed::password::{Encrypt, Password, PasswordHasher, Raw, MIN_COST};
+
+/// Prefix of the hashes of the [`MockHasher`].
+const MOCK_PREFIX: &str = "$mock$0$";
+
+static FAST_HASHING: AtomicBool = AtomicBool::new(false);
+
+/// Makes every bcrypt hash of the process use the minimum cost, [`MIN_COST`], even
+/// the hashes of [`Password<Raw>::to_encrypt_default`] in the code under test. The
+/// given costs are still validated. Never use it in production code.
+///
+/// The override is global, so enable it before the tests that depend on the cost of
+/// the hashes, or in a separate test binary.
+///
+/// ```
+/// use email_pass::{test_util, Password};
+///
+/// test_util::set_fast_hashing(true);
+/// let encrypt_password = Password::new("password").to_encrypt_default()?;
+/// assert!(encrypt_password.as_str().starts_with("$2b$04$"));
+/// # Ok::<(), email_pass::PasswordError>(())
+/// ```
+pub fn set_fast_hashing(enabled: bool) {
+    FAST_HASHING.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns the cost of the hash, replaced by [`MIN_COST`] with the fast hashing.
+#[inline]
+pub(crate) fn effective_cost(cost: u32) -> u32 {
+    if FAST_HASHING.load(Ordering::Relaxed) {
+        MIN_COST
+    } else {
+        cost
+    }
+}
+
+impl Password<Raw> {
+    /// Encrypts the password with the minimum bcrypt cost, [`MIN_COST`], to keep the
+    /// test suites fast. Never use it in production code.
+    pub fn to_encrypt_fast(self) -> Result<Password<Encrypt>, PasswordError> {
+        self.to_encrypt(MIN_COST)
+    }
+}
+
+/// Deterministic and insecure hasher for tests: the same raw password always
+/// produces the same hash, `$mock$0$` followed by its SHA-256 hex digest.
+///
+/// With the `test-util` feature, [`Password::verify`] recognizes the mock hashes, so
+/// the code under test verifies them like the real hashes.
+///
+/// ```
+/// use email_pass::{test_util::MockHasher, Password};
+///
+/// let raw_password = Password::new("password");
+/// let encrypt_password = raw_password.clone().to_encrypt_with(&MockHasher)?;
+/// assert!(encrypt_password.as_str().starts_with("$mock$0$"));
+/// assert!(encrypt_password.verify(&raw_password).unwrap());
+/// # Ok::<(), email_pass::PasswordError>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MockHasher;
+
+/// Allows [`Password<Raw>::to_encrypt_with`] to use the mock hashes in tests.
+impl PasswordHasher for MockHasher {
+    fn hash(&self, raw_password: &str) -> Result<String, PasswordError> {
+        Ok(format!(
+            "{MOCK_PREFIX}{}",
+            sha256_hex(raw_password.as_bytes())
+        ))
+    }
+
+    fn verify(&self, hash: &str, raw_password: &str) -> Result<bool, PasswordError> {
+        Ok(verify_mock(raw_password, hash))
+    }
+}
+
+/// Checks if the hash was created by the [`MockHasher`].
+pub(crate) fn is_mock_hash(hash: &str) -> bool {
+    hash.starts_with(MOCK_PREFIX)
+}
+
+/// Verifies the raw password with a hash of the [`MockHasher`]. Other hashes never
+/// match.
+pub(crate) fn verify_mock(raw_password: &str, hash: &str) -> bool {
+    let expected = sha256_hex(raw_password.as_bytes());
+    hash.strip_prefix(MOCK_PREFIX)
+        .is_some_and(|digest| expected.as_bytes().ct_eq(digest.as_bytes()).into())
+}
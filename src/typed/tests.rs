@@ -182,4 +182,2989 @@ mod serde_tests {
         let result = serde_json::from_value::<UserRequest>(bad_input);
         assert!(result.is_err())
     }
+
+    #[derive(Deserialize)]
+    struct LoginForm {
+        email: Email,
+        password: Password<Raw>,
+    }
+
+    #[test]
+    fn urlencoded_deserialization_works() {
+        let form: LoginForm = serde_urlencoded::from_str(
+            "email=john%2Bnews%40example.com&password=This+Is%20A%26Pass%3D",
+        )
+        .unwrap();
+        assert!(form.email == "john+news@example.com");
+        assert!(form.password == Password::new("This Is A&Pass="));
+
+        let result = serde_urlencoded::from_str::<LoginForm>("email=example.com&password=x");
+        assert!(result.is_err());
+        let result = serde_urlencoded::from_str::<LoginForm>("email=john%40example.com&password=");
+        assert!(result.is_err());
+    }
+
+    #[derive(Deserialize)]
+    struct Query {
+        #[serde(with = "crate::serde_helpers::form")]
+        email: Email,
+    }
+
+    #[test]
+    fn form_helper_restores_the_plus_signs() {
+        // An unencoded `+` is decoded as a space
+        let query: Query = serde_urlencoded::from_str("email=john+news@example.com").unwrap();
+        assert!(query.email == "john+news@example.com");
+
+        let query: Query = serde_urlencoded::from_str("email=+john%40example.com+").unwrap();
+        assert!(query.email == "john@example.com");
+    }
+
+    #[test]
+    fn redacted_serialization_masks_the_credentials() {
+        use crate::Redacted;
+
+        #[derive(Serialize)]
+        struct Dump<'a> {
+            email: Redacted<&'a Email>,
+            raw: Redacted<Password<Raw>>,
+            hash: Redacted<Password>,
+        }
+
+        let email = Email::from_str("john@example.com").unwrap();
+        let dump = Dump {
+            email: Redacted(&email),
+            raw: Redacted(Password::new("ThisIsAPassPhrase.And.Secure.Password")),
+            hash: Redacted(Password::from_encrypt(GENERIC_HASH).unwrap()),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&dump).unwrap(),
+            json!({"email": "j***@example.com", "raw": "***", "hash": "***"})
+        );
+        assert_eq!(
+            format!("{:?}", dump.email),
+            "Redacted(\"j***@example.com\")"
+        );
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Account {
+        #[serde(with = "crate::serde_helpers::lowercase")]
+        email: Email,
+        #[serde(with = "crate::serde_helpers::canonical")]
+        login: Email,
+    }
+
+    #[test]
+    fn deserialize_normalizes_the_emails() {
+        let account: Account = serde_json::from_value(json!({
+            "email": "John+News@Example.com",
+            "login": "John+News@Example.com",
+        }))
+        .unwrap();
+
+        assert!(account.email == "john+news@example.com");
+        assert!(account.login == "john@example.com");
+        assert_eq!(
+            serde_json::to_value(&account).unwrap(),
+            json!({"email": "john+news@example.com", "login": "john@example.com"})
+        );
+
+        let result = serde_json::from_value::<Account>(json!({
+            "email": "not an email",
+            "login": "john@example.com",
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn strength_meter_is_deserialized_from_config() {
+        use crate::StrengthMeter;
+
+        let meter: StrengthMeter = serde_json::from_value(json!({
+            "levels": [
+                { "label": "Weak", "min_score": 0 },
+                { "label": "Fair", "min_score": 2 },
+                { "label": "Good", "min_score": 3 },
+                { "label": "Excellent", "min_score": 4 }
+            ],
+            "required": "Good"
+        }))
+        .unwrap();
+        assert_eq!(meter, StrengthMeter::new());
+
+        let value = serde_json::to_value(&meter).unwrap();
+        assert_eq!(value["levels"][1]["label"], "Fair");
+        assert_eq!(value["required"], "Good");
+
+        // a typo in the required label is rejected with the config
+        let result = serde_json::from_value::<StrengthMeter>(json!({
+            "levels": [{ "label": "Weak", "min_score": 0 }],
+            "required": "Strng"
+        }));
+        assert!(result.unwrap_err().to_string().contains("Strng"));
+    }
+}
+
+#[test]
+fn email_pattern_requires_named_groups() {
+    let regex = regex::Regex::new(r"([a-z]+)@([a-z.]+)").unwrap();
+    assert!(Email::set_pattern(regex).is_err());
+}
+
+#[cfg(feature = "parallel")]
+mod parallel_tests {
+    use crate::{Password, PasswordStrengthChecker};
+
+    #[test]
+    fn parse_batch_keeps_order() {
+        let results = crate::Email::parse_batch(&["john@example.com", "example.com"]);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+    }
+
+    #[test]
+    fn check_batch_keeps_order() {
+        let results =
+            PasswordStrengthChecker::new().check_batch(&["0123", super::SECURE_PASSWORD_VALUE]);
+        assert!(results[0].is_err());
+        assert!(results[1].is_ok());
+    }
+
+    #[test]
+    fn verify_batch_works() {
+        let encrypt_password = Password::new(super::SECURE_PASSWORD_VALUE)
+            .to_encrypt(4)
+            .unwrap();
+        let pairs = [
+            (
+                encrypt_password.clone(),
+                Password::new(super::SECURE_PASSWORD_VALUE),
+            ),
+            (encrypt_password, Password::new("wrong.password")),
+        ];
+
+        let results = Password::verify_batch(&pairs);
+        assert!(results[0].as_ref().unwrap());
+        assert!(!results[1].as_ref().unwrap());
+    }
+}
+
+mod observer_tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use crate::{set_verification_observer, Password, PasswordError, VerificationObserver};
+
+    static HASHES: AtomicUsize = AtomicUsize::new(0);
+    static SUCCESSES: AtomicUsize = AtomicUsize::new(0);
+    static FAILURES: AtomicUsize = AtomicUsize::new(0);
+    static REJECTIONS: AtomicUsize = AtomicUsize::new(0);
+    static DURATIONS: AtomicUsize = AtomicUsize::new(0);
+    static COSTS: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+    struct CounterObserver;
+
+    impl VerificationObserver for CounterObserver {
+        fn on_hash(&self, cost: u32) {
+            HASHES.fetch_add(1, Ordering::SeqCst);
+            COSTS.lock().unwrap().push(cost);
+        }
+
+        fn on_verify_success(&self) {
+            SUCCESSES.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_verify_failure(&self) {
+            FAILURES.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_strength_rejection(&self, _error: &PasswordError) {
+            REJECTIONS.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_hash_duration(&self, _cost: u32, _duration: Duration) {
+            DURATIONS.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn on_verify_duration(&self, _duration: Duration) {
+            DURATIONS.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn observer_receives_events() {
+        let _ = set_verification_observer(CounterObserver);
+
+        let encrypt_password = Password::new(super::SECURE_PASSWORD_VALUE)
+            .to_encrypt(4)
+            .unwrap();
+        let _ = encrypt_password.verify(&Password::new(super::SECURE_PASSWORD_VALUE));
+        let _ = encrypt_password.verify(&Password::new("wrong.password"));
+        let _ = Password::new("0123").check();
+
+        assert!(HASHES.load(Ordering::SeqCst) >= 1);
+        assert!(SUCCESSES.load(Ordering::SeqCst) >= 1);
+        assert!(FAILURES.load(Ordering::SeqCst) >= 1);
+        assert!(REJECTIONS.load(Ordering::SeqCst) >= 1);
+        assert!(DURATIONS.load(Ordering::SeqCst) >= 3);
+
+        // the other backends are observed too, with their cost
+        #[cfg(feature = "pbkdf2")]
+        {
+            let scheme = crate::HashScheme::Pbkdf2Sha256 { iterations: 1_111 };
+            scheme
+                .hash(&Password::new(super::SECURE_PASSWORD_VALUE))
+                .unwrap();
+            assert!(COSTS.lock().unwrap().contains(&1_111));
+        }
+    }
+}
+
+mod audit_tests {
+    use std::cell::RefCell;
+    use std::str::FromStr;
+
+    use crate::{AuditEvent, AuditOperation, AuditOutcome, AuditSink, Email, Password};
+
+    #[derive(Default)]
+    struct MemorySink(RefCell<Vec<AuditEvent>>);
+
+    impl AuditSink for MemorySink {
+        fn record(&self, event: AuditEvent) {
+            self.0.borrow_mut().push(event);
+        }
+    }
+
+    #[test]
+    fn verify_audited_records_redacted_events() {
+        let sink = MemorySink::default();
+        let email = Email::from_str("John@example.com").unwrap();
+        let encrypt_password = Password::new(super::SECURE_PASSWORD_VALUE)
+            .to_encrypt(4)
+            .unwrap();
+
+        let _ = encrypt_password.verify_audited(
+            &email,
+            &Password::new(super::SECURE_PASSWORD_VALUE),
+            &sink,
+        );
+        let _ = encrypt_password.verify_audited(&email, &Password::new("wrong.password"), &sink);
+
+        let events = sink.0.borrow();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].operation, AuditOperation::Verify);
+        assert_eq!(events[0].outcome, AuditOutcome::Success);
+        assert_eq!(events[1].outcome, AuditOutcome::Failure);
+        assert_eq!(events[0].email_hash.len(), 64);
+        assert!(!events[0].email_hash.contains("john"));
+        assert_eq!(events[0].email_hash, events[1].email_hash);
+    }
+
+    #[test]
+    fn temporary_passwords_record_audit_events() {
+        use std::time::Duration;
+
+        use crate::TemporaryPassword;
+
+        let sink = MemorySink::default();
+        let email = Email::from_str("john@example.com").unwrap();
+        let (raw_password, mut temporary) =
+            TemporaryPassword::generate_audited(&email, Duration::from_secs(60), &sink).unwrap();
+
+        let _ = temporary.verify_once_audited(&email, &Password::new("wrong.password"), &sink);
+        let _ = temporary.verify_once_audited(&email, &raw_password, &sink);
+        let _ = temporary.verify_once_audited(&email, &raw_password, &sink);
+
+        let events = sink.0.borrow();
+        let operations: Vec<_> = events.iter().map(|event| event.operation).collect();
+        let outcomes: Vec<_> = events.iter().map(|event| event.outcome).collect();
+        assert_eq!(
+            operations,
+            [
+                AuditOperation::TemporaryPasswordIssue,
+                AuditOperation::TemporaryPasswordVerify,
+                AuditOperation::TemporaryPasswordVerify,
+                AuditOperation::TemporaryPasswordVerify,
+            ]
+        );
+        assert_eq!(
+            outcomes,
+            [
+                AuditOutcome::Success,
+                AuditOutcome::Failure,
+                AuditOutcome::Success,
+                AuditOutcome::Failure,
+            ]
+        );
+    }
+
+    #[cfg(feature = "magic-link")]
+    #[test]
+    fn magic_link_tokens_record_audit_events() {
+        use std::time::Duration;
+
+        use crate::{MagicLinkKey, MagicLinkToken};
+
+        let sink = MemorySink::default();
+        let key = MagicLinkKey::generate();
+        let email = Email::from_str("john@example.com").unwrap();
+        let token = MagicLinkToken::issue_audited(&key, &email, Duration::from_secs(60), &sink);
+
+        assert_eq!(token.verify_audited(&key, &sink), Ok(email));
+        assert!(token
+            .verify_audited(&MagicLinkKey::generate(), &sink)
+            .is_err());
+
+        let events = sink.0.borrow();
+        assert_eq!(events[0].operation, AuditOperation::MagicLinkIssue);
+        assert_eq!(events[1].operation, AuditOperation::MagicLinkVerify);
+        assert_eq!(events[1].outcome, AuditOutcome::Success);
+        assert_eq!(events[2].outcome, AuditOutcome::Failure);
+        // the rejected token is recorded with the email it claims
+        assert_eq!(events[2].email_hash, events[0].email_hash);
+    }
+}
+
+#[test]
+fn email_local_dots_are_rejected() {
+    for email in [
+        "..john@example.com",
+        ".john@example.com",
+        "john.@example.com",
+        "jo..hn@example.com",
+    ] {
+        assert_eq!(Email::from_str(email), Err(crate::EmailError::Username));
+    }
+    assert!(Email::build("jo..hn", "example.com").is_err());
+    assert!(Email::from_str("jo.hn@example.com").is_ok());
+}
+
+#[test]
+fn email_parse_lossy_cleans_input() {
+    let expected = Email::build("John", "example.com").unwrap();
+    for email in [
+        "  John@Example.COM ",
+        "<John@example.com>",
+        " < John@example.com. > ",
+    ] {
+        assert_eq!(Email::parse_lossy(email).unwrap(), expected);
+    }
+    assert!(Email::parse_lossy("<example.com>").is_err());
+}
+
+#[test]
+fn email_comments_are_discarded() {
+    let expected = Email::build("john", "example.com").unwrap();
+    for email in [
+        "john(work)@example.com",
+        "(note) john@example.com",
+        "john@(nested (comment))example.com",
+        r"john@example.com(escaped \) paren)",
+    ] {
+        assert_eq!(Email::parse_with_comments(email).unwrap(), expected);
+    }
+    assert!(Email::parse_with_comments("john(work@example.com").is_err());
+    assert!(Email::parse_with_comments("john)@example.com").is_err());
+}
+
+#[test]
+fn email_list_with_groups_works() {
+    let list = r#"john@example.com, "Doe, Jane" <jane@example.com>, Team: a@x.com, b@x.com;, Undisclosed recipients:;"#;
+    let emails: Vec<String> = Email::parse_list(list)
+        .unwrap()
+        .iter()
+        .map(Email::to_string)
+        .collect();
+
+    assert_eq!(
+        emails,
+        ["john@example.com", "jane@example.com", "a@x.com", "b@x.com"]
+    );
+    assert!(Email::parse_list("john@example.com, example.com").is_err());
+
+    // the `:` and `,` inside comments don't split the list
+    let list = "(note: vip, cc) john@example.com, Team: jane@example.com (team: a);";
+    let emails: Vec<String> = Email::parse_list(list)
+        .unwrap()
+        .iter()
+        .map(Email::to_string)
+        .collect();
+    assert_eq!(emails, ["john@example.com", "jane@example.com"]);
+}
+
+#[test]
+fn named_email_decodes_encoded_words() {
+    let cases = [
+        (
+            "=?UTF-8?B?Sm9zw6kgUMOpcmV6?= <jose@example.com>",
+            Some("José Pérez"),
+        ),
+        (
+            "=?ISO-8859-1?Q?Jos=E9_P=E9rez?= <jose@example.com>",
+            Some("José Pérez"),
+        ),
+        (
+            "=?UTF-8?Q?Jos=C3=A9?= =?UTF-8?Q?_P=C3=A9rez?= <jose@example.com>",
+            Some("José Pérez"),
+        ),
+        (r#""Pérez, José" <jose@example.com>"#, Some("Pérez, José")),
+        (
+            "=?UTF-8?X?broken?= <jose@example.com>",
+            Some("=?UTF-8?X?broken?="),
+        ),
+        ("jose@example.com", None),
+    ];
+
+    for (mailbox, name) in cases {
+        let named_email = crate::NamedEmail::from_str(mailbox).unwrap();
+        assert_eq!(named_email.name(), name);
+        assert_eq!(named_email.email().to_string(), "jose@example.com");
+    }
+
+    // "Eve\r\nBcc: eve@example.com"
+    let injected = "=?UTF-8?B?RXZlDQpCY2M6IGV2ZUBleGFtcGxlLmNvbQ==?= <jose@example.com>";
+    assert_eq!(
+        crate::NamedEmail::from_str(injected),
+        Err(crate::EmailError::ControlCharacter)
+    );
+    assert_eq!(
+        crate::NamedEmail::from_str("=?UTF-8?Q?Eve=0ABcc:?= <jose@example.com>"),
+        Err(crate::EmailError::ControlCharacter)
+    );
+}
+
+#[test]
+fn email_subdomain_works() {
+    let email = Email::from_str("john@mail.corp.example.com").unwrap();
+    assert!(email.is_subdomain_of("corp.example.com"));
+    assert!(email.is_subdomain_of("Example.COM."));
+    assert!(email.is_subdomain_of("mail.corp.example.com"));
+    assert!(!email.is_subdomain_of("le.com"));
+    assert!(!email.is_subdomain_of("other.example.com"));
+
+    let domain = email.domain_name();
+    assert_eq!(
+        domain.labels().collect::<Vec<_>>(),
+        ["mail", "corp", "example", "com"]
+    );
+    assert_eq!(domain.parent().unwrap().as_str(), "corp.example.com");
+    assert_eq!(crate::Domain::new("com").parent(), None);
+}
+
+#[test]
+fn domains_are_compared_ignoring_case_and_trailing_dot() {
+    use crate::Domain;
+    use std::collections::HashSet;
+
+    assert_eq!(Domain::new("Example.COM"), Domain::new("example.com."));
+    assert_ne!(Domain::new("example.com"), Domain::new("example.org"));
+    assert_ne!(Domain::new("example.com"), Domain::new("mail.example.com"));
+
+    let domains: HashSet<Domain> = ["example.com", "EXAMPLE.com.", "example.org"]
+        .into_iter()
+        .map(Domain::new)
+        .collect();
+    assert_eq!(domains.len(), 2);
+    assert!(domains.contains(&Domain::new("Example.Com")));
+}
+
+#[test]
+fn email_domain_wildcard_works() {
+    let email = Email::from_str("john@cs.mit.edu").unwrap();
+    assert!(email.domain_matches("*.edu"));
+    assert!(email.domain_matches("*.MIT.edu"));
+    assert!(email.domain_matches("cs.*.edu"));
+    assert!(email.domain_matches("cs.mit.edu"));
+    assert!(!email.domain_matches("*.cs.mit.edu"));
+    assert!(!email.domain_matches("*.com"));
+    assert!(!email.domain_matches("mit.edu"));
+    assert!(!email.domain_matches("*.*.*.edu"));
+}
+
+#[test]
+fn email_canonical_works() {
+    let cases = [
+        ("John+News@Example.com", "john@example.com"),
+        ("J.O.H.N+tag@GoogleMail.com", "john@gmail.com"),
+        ("john.doe@example.com", "john.doe@example.com"),
+        ("+tag@example.com", "+tag@example.com"),
+    ];
+    for (email, canonical) in cases {
+        let email = Email::from_str(email).unwrap();
+        assert_eq!(email.canonical().to_string(), canonical);
+    }
+}
+
+#[test]
+fn email_dedup_canonical_reports_collapsed_inputs() {
+    use crate::DedupCanonical;
+
+    let emails = [
+        "john@gmail.com",
+        "jane@example.com",
+        "J.ohn+1@googlemail.com",
+        "john+2@gmail.com",
+    ]
+    .map(|email| Email::from_str(email).unwrap());
+
+    let dedup = emails.clone().dedup_canonical();
+    assert_eq!(dedup.emails, [emails[0].clone(), emails[1].clone()]);
+    assert_eq!(dedup.duplicates.len(), 1);
+    assert_eq!(dedup.duplicates[0].canonical.to_string(), "john@gmail.com");
+    assert_eq!(dedup.duplicates[0].indexes, [0, 2, 3]);
+
+    // the borrowed emails are deduplicated without cloning the input
+    assert_eq!(emails.as_slice().dedup_canonical(), dedup);
+    assert_eq!(emails.iter().dedup_canonical(), dedup);
+}
+
+#[test]
+fn email_parse_many_collects_errors() {
+    let (emails, errors) =
+        Email::parse_many(["john@example.com", "example.com", "jane@example.com", "a@b"]);
+
+    assert_eq!(emails.len(), 2);
+    assert_eq!(
+        errors,
+        [
+            (1, "example.com".to_string(), crate::EmailError::Format),
+            (3, "a@b".to_string(), crate::EmailError::Length),
+        ]
+    );
+}
+
+#[test]
+fn email_importer_reports_summary() {
+    use crate::{EmailError, EmailImporter};
+
+    let csv = "name,email\nJohn,John@example.com\n\"Doe, Jane\",jane@example.com\nBad,example.com\nAlias,john+1@example.com\nMissing\n";
+    let mut emails = Vec::new();
+    let report = EmailImporter::new()
+        .column(1)
+        .has_header(true)
+        .import(csv.as_bytes(), |email| emails.push(email.to_string()))
+        .unwrap();
+
+    assert_eq!(emails, ["john@example.com", "jane@example.com"]);
+    assert_eq!(report.total, 5);
+    assert_eq!(report.valid, 2);
+    assert_eq!(report.duplicates, 1);
+    assert_eq!(report.invalid[&EmailError::Format], 2);
+
+    // the rows that are not UTF-8 don't abort the import
+    let legacy = b"Jos\xe9@example.com\r\njane@example.com\r\n";
+    let report = EmailImporter::new()
+        .import(&legacy[..], |email| emails.push(email.to_string()))
+        .unwrap();
+    assert_eq!(report.total, 2);
+    assert_eq!(report.valid, 1);
+    assert_eq!(report.invalid[&EmailError::Format], 1);
+}
+
+#[test]
+fn email_custom_normalization_rule_works() {
+    use crate::{register_normalization_rule, NormalizationRule};
+
+    /// Corporate convention where `first_last` is an alias of `first.last`.
+    struct UnderscoreAliasRule;
+
+    impl NormalizationRule for UnderscoreAliasRule {
+        fn normalize(&self, username: &mut String, domain: &mut String) {
+            if domain == "corp.example.com" {
+                *username = username.replace('_', ".");
+            }
+        }
+    }
+
+    register_normalization_rule(UnderscoreAliasRule);
+
+    let email = Email::from_str("John_Doe+hr@corp.example.com").unwrap();
+    assert_eq!(email.canonical().to_string(), "john.doe@corp.example.com");
+}
+
+#[test]
+fn email_original_is_preserved() {
+    let email = Email::from_str("John.Doe+News@Example.com").unwrap();
+    assert_eq!(email.original(), "John.Doe+News@Example.com");
+    assert_eq!(email.canonical().original(), "John.Doe+News@Example.com");
+
+    let email = Email::parse_lossy(" <John@Example.COM> ").unwrap();
+    assert_eq!(email.to_string(), "John@example.com");
+    assert_eq!(email.original(), " <John@Example.COM> ");
+    assert_eq!(email, Email::build("John", "example.com").unwrap());
+
+    let mut email = Email::parse_with_comments("john(work)@example.com").unwrap();
+    assert_eq!(email.original(), "john(work)@example.com");
+    email.set_username("jane").unwrap();
+    assert_eq!(email.original(), "jane@example.com");
+}
+
+#[test]
+fn alternate_display_masks_values() {
+    let email = Email::from_str("john@example.com").unwrap();
+    assert_eq!(format!("{email}"), "john@example.com");
+    assert_eq!(format!("{email:#}"), "j***@example.com");
+
+    let password =
+        Password::from_encrypt("$2b$04$teRReyH3sVfCd8JA71Sm6xekdy6KhRIzYYERUEUC").unwrap();
+    assert_eq!(format!("{password:#}"), "$2b$04$***");
+
+    let django = Password::from_encrypt("pbkdf2_sha256$1000$seasalt2024$aGFzaA==").unwrap();
+    assert_eq!(format!("{django:#}"), "pbkdf2_sha256$1000$***");
+}
+
+#[test]
+fn const_email_validation_matches_parser() {
+    use crate::is_valid_email;
+
+    const SUPPORT: crate::StaticEmail = crate::StaticEmail::new("support@example.com");
+    assert_eq!(
+        SUPPORT.to_email().unwrap(),
+        Email::from_str("support@example.com").unwrap()
+    );
+
+    // the syntax is valid, but the hostname rules reject the numeric TLD
+    #[cfg(feature = "addr")]
+    {
+        const NUMERIC_TLD: crate::StaticEmail = crate::StaticEmail::new("support@example.123");
+        assert_eq!(NUMERIC_TLD.to_email(), Err(crate::EmailError::Domain));
+    }
+
+    for email in ["john@example.com", "j.o+h_n-1@mail.example.co", "a@b.co"] {
+        assert!(is_valid_email(email), "{email}");
+        assert!(Email::from_str(email).is_ok(), "{email}");
+    }
+    for email in [
+        "example.com",
+        "a@b.c",
+        "john@example",
+        "john@.example.com",
+        ".john@example.com",
+        "jo..hn@example.com",
+        "john.@example.com",
+        "john@@example.com",
+        "john doe@example.com",
+    ] {
+        assert!(!is_valid_email(email), "{email}");
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "idna"))]
+mod idna_serde_tests {
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    use crate::Email;
+
+    #[derive(Serialize, Deserialize)]
+    struct Message {
+        #[serde(with = "crate::serde_helpers::punycode")]
+        to: Email,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Profile {
+        #[serde(with = "crate::serde_helpers::unicode")]
+        email: Email,
+    }
+
+    #[test]
+    fn idn_serialization_forms_work() {
+        let message: Message =
+            serde_json::from_value(json!({"to": "john@bücher.example"})).unwrap();
+        assert_eq!(message.to.domain(), "xn--bcher-kva.example");
+        assert_eq!(
+            serde_json::to_value(&message).unwrap(),
+            json!({"to": "john@xn--bcher-kva.example"})
+        );
+
+        let profile = Profile { email: message.to };
+        assert_eq!(
+            serde_json::to_value(&profile).unwrap(),
+            json!({"email": "john@bücher.example"})
+        );
+    }
+}
+
+#[cfg(feature = "idna")]
+#[test]
+fn idna_domain_validation_works() {
+    for email in [
+        "john@-example.com",
+        "john@example-.com",
+        "john@example..com",
+    ] {
+        assert_eq!(
+            Email::from_str(email),
+            Err(crate::EmailError::Domain),
+            "{email}"
+        );
+    }
+    assert!(Email::build("john", "mail.example.com").is_ok());
+    assert!(Email::build("john", "localhost").is_err());
+    assert!(Email::parse_idn("john@bücher.example").is_ok());
+
+    // the internationalized domains are accepted by FromStr too
+    let email = Email::from_str("user@bücher.de").unwrap();
+    assert_eq!(email.domain(), "xn--bcher-kva.de");
+    assert_eq!(email, Email::parse_idn("user@bücher.de").unwrap());
+    assert_eq!(
+        Email::from_str("user@bü cher.de"),
+        Err(crate::EmailError::Domain)
+    );
+
+    // the constructors from parts store the punycode domain too
+    assert_eq!(Email::build("user", "bücher.de").unwrap(), email);
+    let mut built = Email::build("user", "example.com").unwrap();
+    built.set_domain("bücher.de").unwrap();
+    assert_eq!(built.domain(), "xn--bcher-kva.de");
+    assert_eq!(built.to_unicode(), "user@bücher.de");
+}
+
+#[test]
+fn email_set_enforces_canonical_uniqueness() {
+    use crate::EmailSet;
+
+    let email = |email: &str| Email::from_str(email).unwrap();
+    let mut team: EmailSet = ["john@example.com", "jane@example.com"]
+        .map(email)
+        .into_iter()
+        .collect();
+    assert!(!team.insert(email("John+news@Example.com")));
+    assert!(team.contains(&email("JANE@example.com")));
+    assert_eq!(team.len(), 2);
+
+    let managers: EmailSet = ["jane+boss@example.com", "ana@example.com"]
+        .map(email)
+        .into_iter()
+        .collect();
+    let names = |set: EmailSet| {
+        set.into_iter()
+            .map(|email| email.to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        names(team.union(&managers)),
+        ["ana@example.com", "jane@example.com", "john@example.com"]
+    );
+    assert_eq!(names(team.difference(&managers)), ["john@example.com"]);
+    assert_eq!(names(team.intersection(&managers)), ["jane@example.com"]);
+    assert_eq!(
+        team.remove(&email("john+1@example.com")),
+        Some(email("john@example.com"))
+    );
+}
+
+#[test]
+fn email_set_keeps_the_rules_of_its_creation() {
+    use crate::{register_normalization_rule, EmailSet, NormalizationRule};
+
+    /// Rule registered after the creation of the set, where `-` separates the
+    /// sub-address.
+    struct DashSubAddressRule;
+
+    impl NormalizationRule for DashSubAddressRule {
+        fn normalize(&self, username: &mut String, domain: &mut String) {
+            if domain == "dash.example.com" {
+                if let Some(index) = username.find('-') {
+                    username.truncate(index);
+                }
+            }
+        }
+    }
+
+    let email = |value: &str| Email::from_str(value).unwrap();
+    let mut set = EmailSet::new();
+    assert!(set.insert(email("john-news@dash.example.com")));
+
+    register_normalization_rule(DashSubAddressRule);
+    assert_eq!(
+        email("john-news@dash.example.com").canonical().to_string(),
+        "john@dash.example.com"
+    );
+
+    // the stored email is still found, and the aliases of the new rule are not
+    assert!(set.contains(&email("john-news@dash.example.com")));
+    assert!(set.insert(email("john@dash.example.com")));
+    assert_eq!(set.len(), 2);
+
+    // the sets created after the registration apply the new rule
+    let new_set: EmailSet = [
+        email("john-news@dash.example.com"),
+        email("john@dash.example.com"),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(new_set.len(), 1);
+}
+
+#[test]
+fn email_set_operations_use_the_rules_of_each_set() {
+    use crate::{register_normalization_rule, EmailSet, NormalizationRule};
+
+    /// Rule registered between the creation of the sets, where `_` separates the
+    /// sub-address.
+    struct UnderscoreSubAddressRule;
+
+    impl NormalizationRule for UnderscoreSubAddressRule {
+        fn normalize(&self, username: &mut String, domain: &mut String) {
+            if domain == "underscore.example.com" {
+                if let Some(index) = username.find('_') {
+                    username.truncate(index);
+                }
+            }
+        }
+    }
+
+    let email = |value: &str| Email::from_str(value).unwrap();
+    let old_set: EmailSet = [email("john_news@underscore.example.com")]
+        .into_iter()
+        .collect();
+
+    register_normalization_rule(UnderscoreSubAddressRule);
+    let new_set: EmailSet = [
+        email("john_work@underscore.example.com"),
+        email("jane@underscore.example.com"),
+    ]
+    .into_iter()
+    .collect();
+
+    // the emails of the new set are aliases only with the new rule
+    let union = old_set.union(&new_set);
+    assert_eq!(union.len(), 3);
+    assert!(union.contains(&email("john_work@underscore.example.com")));
+    assert!(union.contains(&email("jane@underscore.example.com")));
+
+    let intersection = old_set.intersection(&new_set);
+    assert_eq!(
+        intersection.iter().collect::<Vec<_>>(),
+        [&email("john_news@underscore.example.com")]
+    );
+    assert!(old_set.difference(&new_set).is_empty());
+
+    // the new set sees the emails of the old set as aliases, the old set doesn't
+    assert_eq!(new_set.union(&old_set).len(), 2);
+    assert_eq!(new_set.difference(&old_set).len(), 2);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn email_set_serde_discards_aliases() {
+    let set: crate::EmailSet =
+        serde_json::from_str(r#"["john@example.com", "John+1@example.com", "jane@example.com"]"#)
+            .unwrap();
+    assert_eq!(set.len(), 2);
+    assert_eq!(
+        serde_json::to_string(&set).unwrap(),
+        r#"["jane@example.com","john@example.com"]"#
+    );
+}
+
+#[test]
+fn named_email_display_quotes_specials() {
+    use crate::NamedEmail;
+
+    let email = Email::from_str("john@example.com").unwrap();
+    let cases = [
+        ("John Doe", "John Doe <john@example.com>"),
+        (
+            "José Pérez",
+            "=?UTF-8?B?Sm9zw6kgUMOpcmV6?= <john@example.com>",
+        ),
+        ("Doe, John", r#""Doe, John" <john@example.com>"#),
+        (
+            r#"John "Johnny" Doe"#,
+            r#""John \"Johnny\" Doe" <john@example.com>"#,
+        ),
+        (r"C:\Users", r#""C:\\Users" <john@example.com>"#),
+    ];
+
+    for (name, formatted) in cases {
+        let named_email = NamedEmail::new(Some(name), email.clone()).unwrap();
+        assert_eq!(named_email.to_string(), formatted);
+        assert_eq!(NamedEmail::from_str(formatted).unwrap(), named_email);
+    }
+
+    // the long names are split in encoded-words of 75 characters at most
+    let long_name = "Ñ".repeat(40);
+    let named_email = NamedEmail::new(Some(&long_name), email.clone()).unwrap();
+    let formatted = named_email.to_string();
+    assert!(formatted.split(' ').all(|word| word.len() <= 75));
+    assert_eq!(NamedEmail::from_str(&formatted).unwrap(), named_email);
+
+    // the names that look like encoded-words are quoted, so they are not decoded
+    let named_email = NamedEmail::new(Some("=?UTF-8?B?RXZl?="), email.clone()).unwrap();
+    assert_eq!(
+        named_email.to_string(),
+        r#""=?UTF-8?B?RXZl?=" <john@example.com>"#
+    );
+    assert_eq!(
+        NamedEmail::from_str(&named_email.to_string()).unwrap(),
+        named_email
+    );
+
+    for name in ["", "  "] {
+        let named_email = NamedEmail::new(Some(name), email.clone()).unwrap();
+        assert_eq!(named_email.name(), None);
+        assert_eq!(named_email.to_string(), "john@example.com");
+    }
+
+    let named_email = NamedEmail::new(Some("John Doe"), email.clone()).unwrap();
+    assert_eq!(format!("{named_email:#}"), "John Doe <j***@example.com>");
+
+    assert_eq!(
+        NamedEmail::new(Some("Eve\r\nBcc: eve@example.com"), email),
+        Err(crate::EmailError::ControlCharacter)
+    );
+}
+
+#[test]
+fn password_cost_is_validated() {
+    use crate::{PasswordError, MAX_COST, MIN_COST};
+
+    for cost in [0, MIN_COST - 1, MAX_COST + 1] {
+        assert_eq!(
+            Password::new(SECURE_PASSWORD_VALUE).to_encrypt(cost),
+            Err(PasswordError::InvalidCost {
+                given: cost,
+                min: MIN_COST,
+                max: MAX_COST
+            })
+        );
+    }
+    assert!(Password::new(SECURE_PASSWORD_VALUE)
+        .to_encrypt(MIN_COST)
+        .is_ok());
+}
+
+#[test]
+fn dummy_verification_takes_the_time_of_a_verification() {
+    use std::time::Instant;
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let encrypt_password = raw_password.clone().to_encrypt(6).unwrap();
+
+    Password::verify_dummy_with_cost(&raw_password, 6);
+    let start = Instant::now();
+    Password::verify_dummy_with_cost(&raw_password, 6);
+    let dummy_duration = start.elapsed();
+
+    let start = Instant::now();
+    assert!(encrypt_password.verify(&raw_password).unwrap());
+    let duration = start.elapsed();
+
+    // the same cost takes a similar time, loosely checked for the noisy CI runners
+    assert!(dummy_duration * 4 > duration);
+    // the invalid costs fall back to the default dummy hash
+    Password::verify_dummy_with_cost(&raw_password, 99);
+}
+
+#[cfg(feature = "argon2")]
+#[test]
+fn dummy_verification_uses_the_scheme() {
+    use crate::{Argon2Params, HashScheme};
+    use std::time::Instant;
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let scheme = HashScheme::from(Argon2Params::new(7_168, 5, 1).unwrap());
+    let encrypt_password = raw_password.clone().to_encrypt_scheme(scheme).unwrap();
+
+    Password::verify_dummy_with_scheme(&raw_password, scheme);
+    let start = Instant::now();
+    Password::verify_dummy_with_scheme(&raw_password, scheme);
+    let dummy_duration = start.elapsed();
+
+    let start = Instant::now();
+    assert!(encrypt_password.verify(&raw_password).unwrap());
+    let duration = start.elapsed();
+
+    assert!(dummy_duration * 4 > duration);
+}
+
+#[test]
+fn invalid_default_costs_are_rejected() {
+    use crate::{default_cost, set_default_cost, set_default_cost_from_env, PasswordError};
+    use crate::{MAX_COST, MIN_COST};
+
+    // the valid costs are not registered, the default cost is global
+    assert_eq!(
+        set_default_cost(MAX_COST + 1),
+        Err(PasswordError::InvalidCost {
+            given: MAX_COST + 1,
+            min: MIN_COST,
+            max: MAX_COST
+        })
+    );
+
+    std::env::set_var("EMAIL_PASS_TEST_COST", "twelve");
+    assert_eq!(
+        set_default_cost_from_env("EMAIL_PASS_TEST_COST"),
+        Err(PasswordError::InvalidCostEnv(
+            "EMAIL_PASS_TEST_COST".to_string()
+        ))
+    );
+    std::env::set_var("EMAIL_PASS_TEST_COST", "2");
+    assert!(matches!(
+        set_default_cost_from_env("EMAIL_PASS_TEST_COST"),
+        Err(PasswordError::InvalidCost { given: 2, .. })
+    ));
+    assert_eq!(
+        set_default_cost_from_env("EMAIL_PASS_TEST_MISSING_COST"),
+        Ok(default_cost())
+    );
+}
+
+#[test]
+fn long_passwords_are_not_truncated_by_bcrypt() {
+    use crate::{PasswordError, MAX_BCRYPT_LEN};
+
+    let limit = "a".repeat(MAX_BCRYPT_LEN);
+    assert!(Password::new(&limit).to_encrypt(4).is_ok());
+
+    let long = format!("{limit}b");
+    assert_eq!(
+        Password::new(&long).to_encrypt(4),
+        Err(PasswordError::TooLongForBcrypt(MAX_BCRYPT_LEN))
+    );
+    // the limit is in bytes, not characters
+    let multibyte = "\u{f1}".repeat(MAX_BCRYPT_LEN / 2 + 1);
+    assert!(Password::new(&multibyte).to_encrypt(4).is_err());
+}
+
+#[test]
+fn rehash_policy_detects_outdated_hashes() {
+    use crate::{HashAlgorithm, RehashPolicy};
+
+    let policy = RehashPolicy::new()
+        .algorithm(HashAlgorithm::Bcrypt)
+        .min_cost(10);
+    let weak = Password::from_encrypt("$2b$04$teRReyH3sVfCd8JA71Sm6xekdy6KhRIzYYERUEUC").unwrap();
+    let strong = Password::from_encrypt("$2b$12$teRReyH3sVfCd8JA71Sm6xekdy6KhRIzYYERUEUC").unwrap();
+    let unknown = Password::from_encrypt("$1$salt$hash").unwrap();
+
+    assert!(policy.requires_rehash(&weak));
+    assert!(!policy.requires_rehash(&strong));
+    assert!(policy.requires_rehash(&unknown));
+}
+
+#[test]
+fn encrypted_passwords_report_if_they_need_a_rehash() {
+    use crate::{HashAlgorithm, RehashPolicy};
+
+    let policy = RehashPolicy::new().min_cost(10);
+    let weak = Password::new(SECURE_PASSWORD_VALUE).to_encrypt(4).unwrap();
+    let strong = Password::from_encrypt("$2b$12$teRReyH3sVfCd8JA71Sm6xekdy6KhRIzYYERUEUC").unwrap();
+    assert!(weak.needs_rehash(&policy));
+    assert!(!strong.needs_rehash(&policy));
+    assert!(!weak.needs_rehash(&policy.min_cost(4)));
+
+    let policy = RehashPolicy::new()
+        .algorithm(HashAlgorithm::Argon2)
+        .min_cost(2);
+    let argon2i =
+        Password::from_encrypt("$argon2i$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaA").unwrap();
+    let argon2id =
+        Password::from_encrypt("$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaA").unwrap();
+    assert!(strong.needs_rehash(&policy));
+    assert!(argon2i.needs_rehash(&policy));
+    assert!(!argon2id.needs_rehash(&policy));
+}
+
+#[test]
+fn verify_and_rehash_returns_the_upgraded_hash() {
+    use crate::{PasswordError, RehashPolicy};
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let stored = raw_password.clone().to_encrypt(4).unwrap();
+    let policy = RehashPolicy::new().min_cost(5);
+
+    let new_password = stored
+        .verify_and_rehash(&raw_password, &policy)
+        .unwrap()
+        .unwrap();
+    assert!(new_password.as_str().starts_with("$2b$05$"));
+    assert!(new_password.verify(&raw_password).unwrap());
+    assert!(stored
+        .verify_and_rehash(&raw_password, &policy.min_cost(4))
+        .unwrap()
+        .is_none());
+    assert!(new_password
+        .verify_and_rehash(&raw_password, &policy)
+        .unwrap()
+        .is_none());
+    assert!(matches!(
+        stored.verify_and_rehash(&Password::new("wrong"), &policy),
+        Err(PasswordError::PasswordMismatch)
+    ));
+}
+
+#[test]
+fn bcrypt_versions_are_checked_and_normalized() {
+    use crate::PasswordError;
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let encrypt_password = raw_password.clone().to_encrypt(4).unwrap();
+    let php_hash = encrypt_password.as_str().replacen("$2b$", "$2y$", 1);
+
+    let php_password = Password::from_encrypt(&php_hash).unwrap();
+    assert_eq!(php_password, encrypt_password);
+    assert!(php_password.verify(&raw_password).unwrap());
+
+    let openbsd_hash = encrypt_password.as_str().replacen("$2b$", "$2a$", 1);
+    let openbsd_password = Password::from_encrypt(&openbsd_hash).unwrap();
+    assert_eq!(openbsd_password.as_str(), openbsd_hash);
+    assert!(openbsd_password.verify(&raw_password).unwrap());
+
+    let unknown_hash = encrypt_password.as_str().replacen("$2b$", "$2c$", 1);
+    assert_eq!(
+        Password::from_encrypt(&unknown_hash),
+        Err(PasswordError::UnknownBcryptVersion("2c".to_string()))
+    );
+    assert!(Password::from_encrypt("$1$salt$hash").is_ok());
+}
+
+#[test]
+fn hash_metadata_is_parsed_from_the_encrypted_password() {
+    use crate::HashAlgorithm;
+
+    let encrypt_password = Password::new(SECURE_PASSWORD_VALUE).to_encrypt(4).unwrap();
+    assert_eq!(encrypt_password.algorithm(), Some(HashAlgorithm::Bcrypt));
+    assert_eq!(encrypt_password.cost(), Some(4));
+    assert_eq!(encrypt_password.salt().map(str::len), Some(22));
+    assert_eq!(
+        encrypt_password.hash_bytes().map(|hash| hash.len()),
+        Some(23)
+    );
+
+    let argon2 =
+        Password::from_encrypt("$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaGhhc2g").unwrap();
+    assert_eq!(argon2.algorithm(), Some(HashAlgorithm::Argon2));
+    assert_eq!(argon2.cost(), Some(2));
+    assert_eq!(argon2.salt(), Some("c2FsdHNhbHQ"));
+    assert_eq!(argon2.hash_bytes(), Some(b"hashhash".to_vec()));
+
+    let scrypt = Password::from_encrypt("$scrypt$ln=17,r=8,p=1$c2FsdA$aGFzaA").unwrap();
+    assert_eq!(scrypt.cost(), Some(17));
+    assert_eq!(scrypt.hash_bytes(), Some(b"hash".to_vec()));
+
+    let django = Password::from_encrypt("pbkdf2_sha256$1000$seasalt2024$aGFzaA==").unwrap();
+    assert_eq!(django.algorithm(), Some(HashAlgorithm::Pbkdf2Sha256));
+    assert_eq!(django.cost(), Some(1000));
+    assert_eq!(django.salt(), Some("seasalt2024"));
+    assert_eq!(django.hash_bytes(), Some(b"hash".to_vec()));
+
+    // the cost is parsed even if the salt and the hash are malformed
+    let truncated =
+        Password::from_encrypt("$2b$12$teRReyH3sVfCd8JA71Sm6xekdy6KhRIzYYERUEUC").unwrap();
+    assert_eq!(truncated.cost(), Some(12));
+    assert_eq!(truncated.salt(), None);
+    assert_eq!(truncated.hash_bytes(), None);
+
+    let unknown = Password::from_encrypt("$1$salt$hash").unwrap();
+    assert_eq!(unknown.algorithm(), None);
+    assert_eq!(unknown.cost(), None);
+}
+
+#[test]
+fn encrypted_passwords_are_assembled_from_parts() {
+    use crate::{HashAlgorithm, PasswordError};
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let stored = raw_password.clone().to_encrypt(4).unwrap();
+    let (salt, digest) = (stored.salt().unwrap(), stored.hash_bytes().unwrap());
+
+    let password = Password::from_parts(HashAlgorithm::Bcrypt, 4, salt, &digest).unwrap();
+    assert_eq!(password, stored);
+    assert!(password.verify(&raw_password).unwrap());
+    assert!(matches!(
+        Password::from_parts(HashAlgorithm::Bcrypt, 40, salt, &digest),
+        Err(PasswordError::InvalidCost { given: 40, .. })
+    ));
+    assert_eq!(
+        Password::from_parts(HashAlgorithm::Bcrypt, 4, "short", &digest),
+        Err(PasswordError::InvalidHashParts)
+    );
+    assert_eq!(
+        Password::from_parts(HashAlgorithm::Bcrypt, 4, salt, &digest[1..]),
+        Err(PasswordError::InvalidHashParts)
+    );
+
+    let django = Password::from_parts(HashAlgorithm::Pbkdf2Sha256, 1000, "seasalt2024", b"hash");
+    assert_eq!(
+        django.unwrap().as_str(),
+        "pbkdf2_sha256$1000$seasalt2024$aGFzaA=="
+    );
+    assert_eq!(
+        Password::from_parts(HashAlgorithm::Argon2, 2, "c2FsdA", b"hash"),
+        Err(PasswordError::InvalidHashParts)
+    );
+}
+
+#[test]
+fn hash_scheme_selects_the_compiled_in_backend() {
+    use crate::{HashAlgorithm, HashScheme};
+
+    let scheme = HashScheme::auto();
+    if cfg!(feature = "argon2") {
+        assert_eq!(scheme.algorithm(), HashAlgorithm::Argon2);
+    } else if cfg!(feature = "scrypt") {
+        assert_eq!(scheme.algorithm(), HashAlgorithm::Scrypt);
+    } else {
+        assert_eq!(scheme, HashScheme::bcrypt(crate::DEFAULT_COST));
+    }
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let encrypt_password = raw_password
+        .clone()
+        .to_encrypt_scheme(HashScheme::bcrypt(4))
+        .unwrap();
+    assert!(encrypt_password.verify(&raw_password).unwrap());
+    assert_eq!(
+        HashAlgorithm::detect("$scrypt$ln=17,r=8,p=1$c2FsdA$aGFzaA"),
+        Some(HashAlgorithm::Scrypt)
+    );
+}
+
+#[cfg(feature = "scrypt")]
+#[test]
+fn scrypt_hashes_are_verified_with_the_bcrypt_hashes() {
+    use crate::{HashAlgorithm, HashScheme, RehashPolicy};
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let scheme = HashScheme::Scrypt {
+        log_n: 10,
+        r: 8,
+        p: 1,
+    };
+    let scrypt_password = raw_password.clone().to_encrypt_scheme(scheme).unwrap();
+    assert!(scrypt_password
+        .as_str()
+        .starts_with("$scrypt$ln=10,r=8,p=1$"));
+
+    // the stored hashes are parsed and verified by their algorithm
+    let stored = Password::from_encrypt(scrypt_password.as_str()).unwrap();
+    assert!(stored.verify(&raw_password).unwrap());
+    assert!(!stored.verify(&Password::new("wrong")).unwrap());
+
+    let bcrypt_password = raw_password.clone().to_encrypt(4).unwrap();
+    assert!(bcrypt_password.verify(&raw_password).unwrap());
+
+    let policy = RehashPolicy::new()
+        .algorithm(HashAlgorithm::Scrypt)
+        .min_cost(10);
+    assert!(!policy.requires_rehash(&stored));
+    assert!(policy.requires_rehash(&bcrypt_password));
+
+    let malformed = Password::from_encrypt("$scrypt$ln=10,r=8,p=1$salt").unwrap();
+    assert!(malformed.verify(&raw_password).is_err());
+}
+
+#[test]
+fn custom_hashers_are_plugged_in() {
+    use crate::{HashScheme, PasswordError, PasswordHasher};
+
+    struct ReversedHasher;
+
+    impl PasswordHasher for ReversedHasher {
+        fn hash(&self, raw_password: &str) -> Result<String, PasswordError> {
+            if raw_password.is_empty() {
+                return Err(PasswordError::PasswordEncryption);
+            }
+            Ok(format!(
+                "reversed:{}",
+                raw_password.chars().rev().collect::<String>()
+            ))
+        }
+
+        fn verify(&self, hash: &str, raw_password: &str) -> Result<bool, PasswordError> {
+            Ok(self.hash(raw_password)? == hash)
+        }
+    }
+
+    let raw_password = Password::new("secret");
+    let encrypt_password = raw_password
+        .clone()
+        .to_encrypt_with(&ReversedHasher)
+        .unwrap();
+    assert_eq!(encrypt_password.as_str(), "reversed:terces");
+    assert!(encrypt_password
+        .verify_with(&ReversedHasher, &raw_password)
+        .unwrap());
+    assert!(!encrypt_password
+        .verify_with(&ReversedHasher, &Password::new("other"))
+        .unwrap());
+    assert_eq!(
+        Password::new("").to_encrypt_with(&ReversedHasher),
+        Err(PasswordError::PasswordEncryption)
+    );
+
+    // the built-in backends are hashers too
+    let hasher: &dyn PasswordHasher = &HashScheme::bcrypt(4);
+    let encrypt_password = raw_password.clone().to_encrypt_with(hasher).unwrap();
+    assert!(encrypt_password.verify(&raw_password).unwrap());
+    assert!(encrypt_password.verify_with(hasher, &raw_password).unwrap());
+}
+
+#[cfg(feature = "argon2")]
+#[test]
+fn argon2_hashes_are_created_and_verified() {
+    use crate::{Argon2, Bcrypt, HashAlgorithm, HashScheme, PasswordError, RehashPolicy};
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let scheme = HashScheme::Argon2 {
+        memory_kib: 1024,
+        iterations: 1,
+        parallelism: 1,
+        output_len: 32,
+    };
+    let argon2_password = raw_password.clone().to_encrypt_scheme(scheme).unwrap();
+    assert!(argon2_password
+        .as_str()
+        .starts_with("$argon2id$v=19$m=1024,t=1,p=1$"));
+
+    let stored = Password::from_encrypt(argon2_password.as_str()).unwrap();
+    assert!(stored.verify(&raw_password).unwrap());
+    assert!(!stored.verify(&Password::new("wrong")).unwrap());
+    assert!(stored.clone().downcast::<Argon2>().is_ok());
+    assert!(stored.clone().downcast::<Bcrypt>().is_err());
+
+    // the malformed hashes are not reported as bcrypt errors
+    let malformed = Password::from_encrypt("$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ").unwrap();
+    assert_eq!(
+        malformed.verify(&raw_password),
+        Err(PasswordError::InvalidHash)
+    );
+
+    // the memory and the parallelism are compared too
+    let policy = RehashPolicy::new()
+        .algorithm(HashAlgorithm::Argon2)
+        .min_cost(2);
+    assert!(policy.requires_rehash(&stored));
+    assert!(policy.min_cost(1).requires_rehash(&stored));
+    let cases = [
+        ("$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaA", false),
+        ("$argon2id$v=19$m=65536,t=3,p=4$c2FsdHNhbHQ$aGFzaA", false),
+        ("$argon2id$v=19$m=19455,t=2,p=1$c2FsdHNhbHQ$aGFzaA", true),
+        ("$argon2id$v=19$m=19456,t=1,p=1$c2FsdHNhbHQ$aGFzaA", true),
+        ("$argon2id$v=19$m=19456,t=2,p=0$c2FsdHNhbHQ$aGFzaA", true),
+        ("$argon2id$v=19$m=19456,t=2$c2FsdHNhbHQ$aGFzaA", true),
+    ];
+    for (hash, requires_rehash) in cases {
+        let password = Password::from_encrypt(hash).unwrap();
+        assert_eq!(policy.requires_rehash(&password), requires_rehash, "{hash}");
+    }
+
+    let invalid_params = HashScheme::Argon2 {
+        memory_kib: 1,
+        iterations: 1,
+        parallelism: 1,
+        output_len: 32,
+    };
+    assert_eq!(
+        raw_password.to_encrypt_scheme(invalid_params),
+        Err(PasswordError::PasswordEncryption)
+    );
+}
+
+#[cfg(feature = "argon2")]
+#[test]
+fn verify_and_rehash_upgrades_bcrypt_to_argon2() {
+    use crate::{Argon2Params, HashAlgorithm, RehashPolicy};
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let stored = raw_password.clone().to_encrypt(4).unwrap();
+    let policy = RehashPolicy::new()
+        .algorithm(HashAlgorithm::Argon2)
+        .min_cost(2);
+
+    // the bcrypt cost is not compared with the argon2 iterations
+    let new_password = stored
+        .verify_and_rehash(&raw_password, &policy)
+        .unwrap()
+        .unwrap();
+    let params = Argon2Params::owasp_minimum();
+    assert!(new_password.as_str().starts_with(&format!(
+        "$argon2id$v=19$m={},t=2,p={}$",
+        params.memory_kib(),
+        params.parallelism()
+    )));
+    assert!(new_password.verify(&raw_password).unwrap());
+    assert_eq!(
+        new_password.verify_and_rehash(&raw_password, &policy),
+        Ok(None)
+    );
+
+    let new_password = new_password
+        .verify_and_rehash(&raw_password, &policy.min_cost(3))
+        .unwrap()
+        .unwrap();
+    assert_eq!(new_password.cost(), Some(3));
+}
+
+#[cfg(feature = "argon2")]
+#[test]
+fn argon2_params_are_validated() {
+    use crate::{Argon2Params, HashScheme, PasswordError};
+
+    assert_eq!(
+        HashScheme::from(Argon2Params::owasp_minimum()),
+        HashScheme::argon2()
+    );
+    // the presets pass the validation of the custom parameters
+    for preset in [
+        Argon2Params::owasp_minimum(),
+        Argon2Params::interactive(),
+        Argon2Params::high_security(),
+        Argon2Params::rfc9106(),
+        Argon2Params::rfc9106_low_memory(),
+    ] {
+        assert_eq!(
+            Argon2Params::new(
+                preset.memory_kib(),
+                preset.iterations(),
+                preset.parallelism()
+            ),
+            Ok(preset)
+        );
+    }
+    let low_memory = Argon2Params::rfc9106_low_memory();
+    assert_eq!(
+        (
+            low_memory.memory_kib(),
+            low_memory.iterations(),
+            low_memory.parallelism()
+        ),
+        (65_536, 3, 4)
+    );
+    assert_eq!(Argon2Params::rfc9106().memory_kib(), 2 * 1024 * 1024);
+
+    // the OWASP configurations are accepted
+    for (memory_kib, iterations) in [
+        (47_104, 1),
+        (19_456, 2),
+        (12_288, 3),
+        (9_216, 4),
+        (7_168, 5),
+    ] {
+        assert!(Argon2Params::new(memory_kib, iterations, 1).is_ok());
+    }
+    for (memory_kib, iterations, parallelism) in [(4_096, 10, 1), (19_456, 1, 1), (19_456, 2, 0)] {
+        assert_eq!(
+            Argon2Params::new(memory_kib, iterations, parallelism),
+            Err(PasswordError::UnsafeArgon2Params)
+        );
+    }
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let params = Argon2Params::new(7_168, 5, 2).unwrap();
+    let encrypt_password = raw_password
+        .clone()
+        .to_encrypt_scheme(params.into())
+        .unwrap();
+    assert!(encrypt_password
+        .as_str()
+        .starts_with("$argon2id$v=19$m=7168,t=5,p=2$"));
+    assert!(encrypt_password.verify(&raw_password).unwrap());
+    assert_eq!(
+        encrypt_password.hash_bytes().map(|hash| hash.len()),
+        Some(32)
+    );
+
+    // the output length is validated
+    for output_len in [0, 8, 15, 65, 1024] {
+        assert_eq!(
+            params.with_output_len(output_len),
+            Err(PasswordError::UnsafeArgon2Params)
+        );
+    }
+    let params = params.with_output_len(64).unwrap();
+    assert_eq!(params.output_len(), 64);
+    let encrypt_password = raw_password
+        .clone()
+        .to_encrypt_scheme(params.into())
+        .unwrap();
+    assert_eq!(
+        encrypt_password.hash_bytes().map(|hash| hash.len()),
+        Some(64)
+    );
+    assert!(encrypt_password.verify(&raw_password).unwrap());
+}
+
+#[test]
+fn django_hashes_are_recognized() {
+    use crate::{HashAlgorithm, HashStoreReport, RehashPolicy};
+
+    let django_hash = "pbkdf2_sha256$1000$seasalt2024$/ZhgW2csd/R+RHfFuspjr21nNYX8GxHtyqlbyzcNYYM=";
+    let stored = Password::from_encrypt(django_hash).unwrap();
+    assert_eq!(
+        HashAlgorithm::detect(stored.as_str()),
+        Some(HashAlgorithm::Pbkdf2Sha256)
+    );
+    assert!(Password::from_encrypt("pbkdf2_sha256$1000$salt").is_err());
+    assert!(Password::from_encrypt("pbkdf2_sha1$1000$salt$aGFzaA==").is_err());
+
+    let report = HashStoreReport::scan([django_hash], &RehashPolicy::new());
+    assert_eq!(
+        report.algorithms.get(&HashAlgorithm::Pbkdf2Sha256),
+        Some(&1)
+    );
+    assert_eq!(
+        report.costs.get(&(HashAlgorithm::Pbkdf2Sha256, 1000)),
+        Some(&1)
+    );
+}
+
+#[cfg(feature = "pbkdf2")]
+#[test]
+fn pbkdf2_hashes_are_compatible_with_django() {
+    use crate::HashScheme;
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let django_hash = "pbkdf2_sha256$1000$seasalt2024$/ZhgW2csd/R+RHfFuspjr21nNYX8GxHtyqlbyzcNYYM=";
+    let stored = Password::from_encrypt(django_hash).unwrap();
+    assert!(stored.verify(&raw_password).unwrap());
+    assert!(!stored.verify(&Password::new("wrong")).unwrap());
+
+    let scheme = HashScheme::Pbkdf2Sha256 { iterations: 1000 };
+    let encrypt_password = raw_password.clone().to_encrypt_scheme(scheme).unwrap();
+    assert!(encrypt_password.as_str().starts_with("pbkdf2_sha256$1000$"));
+    let stored = Password::from_encrypt(encrypt_password.as_str()).unwrap();
+    assert!(stored.verify(&raw_password).unwrap());
+
+    let truncated = Password::from_encrypt("pbkdf2_sha256$1000$seasalt2024$aGFzaA==").unwrap();
+    assert_eq!(
+        truncated.verify(&raw_password),
+        Err(crate::PasswordError::InvalidHash)
+    );
+    assert_eq!(
+        HashScheme::pbkdf2_sha256(),
+        HashScheme::Pbkdf2Sha256 {
+            iterations: 600_000
+        }
+    );
+}
+
+#[cfg(feature = "nfkc")]
+#[test]
+fn passwords_are_normalized_before_hashing() {
+    let composed = Password::new("Contrase\u{f1}a.Segura.2024");
+    let decomposed = Password::new("Contrasen\u{303}a.Segura.2024");
+
+    let encrypt_password = composed.clone().to_encrypt(4).unwrap();
+    assert!(encrypt_password.verify(&decomposed).unwrap());
+
+    let encrypt_password = decomposed.clone().to_encrypt(4).unwrap();
+    assert!(encrypt_password.verify(&composed).unwrap());
+
+    // the hashes keep the standard format, and verify with the normalized value
+    let stored = bcrypt::hash("Contrase\u{f1}a.Segura.2024", 4).unwrap();
+    let encrypt_password = Password::from_encrypt(&stored).unwrap();
+    assert!(encrypt_password.verify(&decomposed).unwrap());
+    assert!(!encrypt_password.needs_rehash(&crate::RehashPolicy::new().min_cost(4)));
+
+    let encrypt_password = composed.to_encrypt(4).unwrap();
+    assert!(bcrypt::verify("Contrase\u{f1}a.Segura.2024", encrypt_password.as_str()).unwrap());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn rehash_policy_deserializes_from_config() {
+    let policy: crate::RehashPolicy = serde_json::from_str(r#"{"min_cost": 13}"#).unwrap();
+    assert_eq!(policy, crate::RehashPolicy::new().min_cost(13));
+}
+
+#[test]
+fn password_hash_family_typestate_works() {
+    use crate::{Bcrypt, Encrypt};
+
+    let bcrypt_password: Password<Encrypt<Bcrypt>> =
+        Password::new(SECURE_PASSWORD_VALUE).to_bcrypt(4).unwrap();
+    assert!(bcrypt_password
+        .verify(&Password::new(SECURE_PASSWORD_VALUE))
+        .unwrap());
+
+    let stored: Password = bcrypt_password.erase();
+    assert!(stored.clone().downcast::<Bcrypt>().is_ok());
+
+    let unknown = Password::from_encrypt("$1$salt$hash").unwrap();
+    assert_eq!(unknown.clone().downcast::<Bcrypt>(), Err(unknown));
+}
+
+#[test]
+fn keyboard_walks_are_rejected() {
+    use crate::{KeyboardLayout, PasswordError, PasswordStrengthChecker};
+
+    let checker = PasswordStrengthChecker::new()
+        .strong(crate::PasswordStrength::Low)
+        .keyboard_layout(KeyboardLayout::Azerty)
+        .keyboard_layout(KeyboardLayout::Cyrillic);
+
+    for password in [
+        "Azerty.Horse.42",
+        "pass.qsdf.word9",
+        "моя.ЕКУЦ.7391z",
+        "кот.йййй.5820q",
+    ] {
+        assert_eq!(
+            checker.check(password).err(),
+            Some(PasswordError::KeyboardPattern),
+            "{password}"
+        );
+    }
+    assert!(checker.check("qwertz.horse.battery").is_ok());
+    assert!(PasswordStrengthChecker::new()
+        .strong(crate::PasswordStrength::Low)
+        .check("Azerty.Horse.42")
+        .is_ok());
+}
+
+#[test]
+fn cyrillic_keyboard_walks_are_detected() {
+    use crate::KeyboardLayout;
+
+    let layout = KeyboardLayout::Cyrillic;
+    for password in ["йцук", "Пароль.ФЫВА", "куцй.2024", "ждлорпа", "ячсм"]
+    {
+        assert!(layout.has_walk(password), "{password}");
+    }
+    for password in ["купцй", "фвыа", "йфяц", "qwer"] {
+        assert!(!layout.has_walk(password), "{password}");
+    }
+    assert!(!KeyboardLayout::Azerty.has_walk("йцук"));
+
+    assert!(layout.has_repeat("ЖЖЖЖ"));
+    assert!(!layout.has_repeat("жжж"));
+    assert!(!layout.has_repeat("zzzz"));
+}
+
+#[test]
+fn leetspeak_passwords_are_rejected() {
+    use crate::{PasswordError, PasswordStrength, PasswordStrengthChecker};
+
+    let checker = PasswordStrengthChecker::new().strong(PasswordStrength::Low);
+    let leet_password = "P@$$w0rd2024!";
+
+    assert!(checker.check(leet_password).is_ok());
+    assert_eq!(
+        checker.deleet(true).check(leet_password).err(),
+        Some(PasswordError::UnsafePassword(PasswordStrength::Low))
+    );
+    assert!(checker.deleet(true).check(SECURE_PASSWORD_VALUE).is_ok());
+}
+
+#[test]
+fn localized_words_are_rejected() {
+    use crate::{Language, PasswordError, PasswordStrength, PasswordStrengthChecker};
+
+    for (language, password) in [
+        (Language::Spanish, "contraseña2024"),
+        (Language::Portuguese, "Saudade2024!"),
+        (Language::German, "Geheim2024!"),
+    ] {
+        assert!(PasswordStrengthChecker::new().check(password).is_ok());
+        assert_eq!(
+            PasswordStrengthChecker::new()
+                .language(language)
+                .check(password)
+                .err(),
+            Some(PasswordError::UnsafePassword(PasswordStrength::Default))
+        );
+    }
+
+    // the checker is still Copy with the languages
+    let checker = PasswordStrengthChecker::new().language(Language::German);
+    assert!(Password::new(SECURE_PASSWORD_VALUE)
+        .custom_check(checker)
+        .is_ok());
+    assert!(checker.check("Geheim2024!").is_err());
+}
+
+#[test]
+fn dictionary_files_are_loaded() {
+    use crate::PasswordStrengthChecker;
+
+    let password = "Roadrunner.Acme";
+    assert!(PasswordStrengthChecker::new().check(password).is_ok());
+
+    let checker = PasswordStrengthChecker::new().with_dictionary_bytes(b"acme\r\nroadrunner\n");
+    assert!(checker.check(password).is_err());
+
+    // the clones share the dictionary until they add words
+    let clone = checker.clone().dictionary(["coyote"]);
+    assert!(clone.check("Coyote.Acme").is_err());
+    assert!(checker.check("Coyote.Acme").is_ok());
+
+    let path = std::env::temp_dir().join("email_pass_dictionary.txt");
+    std::fs::write(&path, "coyote\nroadrunner\nacme\n").unwrap();
+    let checker = PasswordStrengthChecker::new()
+        .with_dictionary_file(&path)
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(checker.check(password).is_err());
+    assert!(checker.check(SECURE_PASSWORD_VALUE).is_ok());
+
+    assert!(PasswordStrengthChecker::new()
+        .with_dictionary_file(std::env::temp_dir().join("email_pass_missing.txt"))
+        .is_err());
+}
+
+#[test]
+fn generated_passwords_are_random() {
+    let first = Password::generate(24);
+    let second = Password::generate(24);
+
+    assert!(first != second);
+    assert!(first.check().is_ok());
+}
+
+#[test]
+fn temporary_passwords_are_verified_once() {
+    use crate::{PasswordError, TemporaryPassword};
+    use std::time::{Duration, SystemTime};
+
+    let (raw_password, mut temporary) =
+        TemporaryPassword::generate(Duration::from_secs(60)).unwrap();
+    assert_eq!(
+        temporary.verify_once(&Password::new(SECURE_PASSWORD_VALUE)),
+        Ok(false)
+    );
+    assert!(!temporary.is_used());
+    assert_eq!(temporary.verify_once(&raw_password), Ok(true));
+    assert_eq!(
+        temporary.verify_once(&raw_password),
+        Err(PasswordError::UsedPassword)
+    );
+
+    let expired_at = SystemTime::now() - Duration::from_secs(1);
+    let mut expired =
+        TemporaryPassword::from_parts(temporary.password().clone(), expired_at, false);
+    assert!(expired.is_expired());
+    assert_eq!(
+        expired.verify_once(&raw_password),
+        Err(PasswordError::ExpiredPassword)
+    );
+}
+
+#[cfg(feature = "otp")]
+mod otp_tests {
+    use std::str::FromStr;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::{Email, HotpSecret, OtpError, TotpSecret};
+
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn totp_matches_rfc_6238_vectors() {
+        let secret = TotpSecret::from_bytes(RFC_SECRET).unwrap();
+
+        for (seconds, code) in [
+            (59, "287082"),
+            (1111111109, "081804"),
+            (1234567890, "005924"),
+            (2000000000, "279037"),
+        ] {
+            let time = UNIX_EPOCH + Duration::from_secs(seconds);
+            assert_eq!(secret.code_at(time), code);
+            assert_eq!(secret.verify_at(code, time, 0), Some(seconds / 30));
+        }
+    }
+
+    #[test]
+    fn totp_accepts_drift_window() {
+        let secret = TotpSecret::generate();
+        let time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let code = secret.code_at(time - Duration::from_secs(30));
+
+        let step = 1_700_000_000 / 30;
+        assert_eq!(secret.verify_at(&code, time, 1), Some(step - 1));
+        assert_eq!(secret.verify_at(&code, time, 0), None);
+        assert_eq!(
+            secret.verify_at(&code, time + Duration::from_secs(60), 1),
+            None
+        );
+    }
+
+    #[test]
+    fn totp_secret_base32_and_uri() {
+        let secret = TotpSecret::from_bytes(RFC_SECRET).unwrap();
+        let base32 = secret.to_base32();
+        assert_eq!(base32, "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ");
+        assert_eq!(
+            TotpSecret::from_base32(&base32.to_lowercase()).unwrap(),
+            secret
+        );
+
+        let email = Email::from_str("john@example.com").unwrap();
+        assert_eq!(
+            secret.to_uri("Acme Inc", &email),
+            "otpauth://totp/Acme%20Inc:john%40example.com?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ\
+             &issuer=Acme%20Inc&algorithm=SHA1&digits=6&period=30"
+        );
+
+        assert_eq!(TotpSecret::from_base32("GEZ1"), Err(OtpError::Encoding));
+        assert_eq!(
+            TotpSecret::from_base32("GEZDGNBV"),
+            Err(OtpError::SecretLength(16))
+        );
+        assert_eq!(format!("{secret:?}"), "TotpSecret(***)");
+    }
+
+    #[test]
+    fn hotp_matches_rfc_4226_vectors() {
+        let secret = HotpSecret::from_bytes(RFC_SECRET).unwrap();
+        let codes = [
+            "755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583",
+            "399871", "520489",
+        ];
+
+        for (counter, code) in codes.iter().enumerate() {
+            assert_eq!(secret.code(counter as u64), *code);
+        }
+    }
+
+    #[test]
+    fn hotp_verification_advances_counter() {
+        let secret = HotpSecret::generate();
+
+        assert_eq!(secret.verify(&secret.code(5), 5), Some(6));
+        assert_eq!(secret.verify(&secret.code(15), 5), Some(16));
+        assert_eq!(secret.verify(&secret.code(16), 5), None);
+        assert_eq!(secret.verify(&secret.code(4), 5), None);
+        assert_eq!(secret.verify_with_window(&secret.code(6), 5, 0), None);
+        // the last counter has no next counter
+        assert_eq!(secret.verify(&secret.code(u64::MAX), u64::MAX), None);
+    }
+
+    #[test]
+    fn hotp_resynchronizes_counter() {
+        let secret = HotpSecret::generate();
+        let (first, second) = (secret.code(50), secret.code(51));
+
+        assert_eq!(secret.verify(&first, 0), None);
+        assert_eq!(secret.resync(&first, &second, 0), Some(52));
+        assert_eq!(secret.resync(&second, &first, 0), None);
+        assert_eq!(secret.resync(&secret.code(150), &secret.code(151), 0), None);
+
+        let email = Email::from_str("john@example.com").unwrap();
+        assert!(secret
+            .to_uri("Acme", &email, 52)
+            .starts_with("otpauth://hotp/Acme:john%40example.com?secret="));
+        assert!(secret.to_uri("Acme", &email, 52).ends_with("&counter=52"));
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod tokio_tests {
+    use crate::{Password, PasswordError};
+
+    use super::SECURE_PASSWORD_VALUE;
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn passwords_are_hashed_in_the_blocking_pool() {
+        block_on(async {
+            let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+            let encrypt_password = raw_password.clone().to_encrypt_async(4).await.unwrap();
+
+            assert!(encrypt_password.verify_async(&raw_password).await.unwrap());
+            assert!(!encrypt_password
+                .verify_async(&Password::new("wrong"))
+                .await
+                .unwrap());
+            assert!(matches!(
+                raw_password.to_encrypt_async(1).await,
+                Err(PasswordError::InvalidCost { given: 1, .. })
+            ));
+        });
+    }
+}
+
+#[cfg(feature = "migration")]
+mod migration_tests {
+    use crate::{HashScheme, MigratingVerifier, Password, PasswordError, UnsaltedDigest};
+
+    use super::SECURE_PASSWORD_VALUE;
+
+    #[test]
+    fn unsalted_digests_are_migrated_on_login() {
+        let verifier = MigratingVerifier::new(UnsaltedDigest::Sha1, HashScheme::bcrypt(4));
+        let raw_password = Password::new("password");
+
+        let stored = "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8";
+        let new_password = verifier.verify(stored, &raw_password).unwrap().unwrap();
+        assert!(new_password.verify(&raw_password).unwrap());
+        assert_eq!(
+            verifier.verify(stored, &Password::new("wrong")),
+            Err(PasswordError::PasswordMismatch)
+        );
+
+        let verifier = MigratingVerifier::new(UnsaltedDigest::Sha256, HashScheme::bcrypt(4));
+        let stored = "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8";
+        assert!(verifier.verify(stored, &raw_password).unwrap().is_some());
+    }
+
+    #[test]
+    fn outdated_hashes_are_migrated_to_the_scheme() {
+        let verifier = MigratingVerifier::new(UnsaltedDigest::Md5, HashScheme::bcrypt(5));
+        let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+
+        let old_cost = raw_password.clone().to_encrypt(4).unwrap();
+        let new_password = verifier
+            .verify(old_cost.as_str(), &raw_password)
+            .unwrap()
+            .unwrap();
+        assert!(new_password.as_str().starts_with("$2b$05$"));
+        assert_eq!(
+            verifier.verify(new_password.as_str(), &raw_password),
+            Ok(None)
+        );
+        assert_eq!(
+            verifier.verify(new_password.as_str(), &Password::new("wrong")),
+            Err(PasswordError::PasswordMismatch)
+        );
+    }
+}
+
+#[cfg(feature = "pepper")]
+mod pepper_tests {
+    use crate::{HashScheme, HashingConfig, KeyRing, Password, PasswordError, Pepper};
+
+    use super::SECURE_PASSWORD_VALUE;
+
+    #[test]
+    fn peppered_hashes_require_the_pepper() {
+        let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+        let pepper = Pepper::from_bytes(&[7; 32]).unwrap();
+        let config = HashingConfig::new()
+            .scheme(HashScheme::bcrypt(4))
+            .pepper(pepper.clone());
+        assert!(config.is_peppered());
+
+        let encrypt_password = raw_password.clone().to_encrypt_with(&config).unwrap();
+        assert!(encrypt_password
+            .verify_with(&config, &raw_password)
+            .unwrap());
+        assert!(!encrypt_password
+            .verify_with(&config, &Password::new("wrong"))
+            .unwrap());
+        // without the pepper, the hash doesn't match
+        assert!(!encrypt_password.verify(&raw_password).unwrap());
+
+        let other_config = config.clone().pepper(Pepper::from_bytes(&[8; 32]).unwrap());
+        assert!(!encrypt_password
+            .verify_with(&other_config, &raw_password)
+            .unwrap());
+        assert_eq!(format!("{pepper:?}"), "Pepper(***)");
+    }
+
+    #[test]
+    fn unpeppered_config_creates_plain_hashes() {
+        let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+        let config = HashingConfig::new().scheme(HashScheme::bcrypt(4));
+
+        let encrypt_password = raw_password.clone().to_encrypt_with(&config).unwrap();
+        assert!(encrypt_password.verify(&raw_password).unwrap());
+        assert!(encrypt_password
+            .verify_with(&config, &raw_password)
+            .unwrap());
+        assert_eq!(
+            Pepper::from_bytes(&[7; 16]),
+            Err(PasswordError::PepperLength(32))
+        );
+    }
+
+    #[test]
+    fn key_ring_verifies_the_hashes_of_the_old_keys() {
+        let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+        let first = Pepper::from_bytes(&[1; 32]).unwrap();
+        let second = Pepper::from_bytes(&[2; 32]).unwrap();
+        let config = HashingConfig::new()
+            .scheme(HashScheme::bcrypt(4))
+            .key_ring(KeyRing::new().key(1, first.clone()));
+
+        let old_password = raw_password.clone().to_encrypt_with(&config).unwrap();
+        assert!(old_password.as_str().ends_with("$k=1"));
+        assert!(!config.requires_rotation(&old_password));
+
+        let config = config.key_ring(KeyRing::new().key(2, second).key(1, first));
+        let new_password = raw_password.clone().to_encrypt_with(&config).unwrap();
+        assert!(new_password.as_str().ends_with("$k=2"));
+        assert!(old_password.verify_with(&config, &raw_password).unwrap());
+        assert!(new_password.verify_with(&config, &raw_password).unwrap());
+        assert!(config.requires_rotation(&old_password));
+        assert!(!config.requires_rotation(&new_password));
+
+        let stored = Password::from_encrypt(new_password.as_str()).unwrap();
+        assert!(!stored
+            .verify_with(&config, &Password::new("wrong"))
+            .unwrap());
+
+        // the hashes of a removed key can't be verified
+        let config = config.key_ring(KeyRing::new().key(2, Pepper::from_bytes(&[2; 32]).unwrap()));
+        assert_eq!(
+            old_password.verify_with(&config, &raw_password),
+            Err(PasswordError::UnknownPepperKey(1))
+        );
+    }
+
+    #[test]
+    fn key_ring_verifies_the_hashes_without_key_id() {
+        let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+        let pepper = Pepper::from_bytes(&[7; 32]).unwrap();
+        let config = HashingConfig::new()
+            .scheme(HashScheme::bcrypt(4))
+            .pepper(pepper.clone());
+        let peppered = raw_password.clone().to_encrypt_with(&config).unwrap();
+
+        let config = config.key_ring(KeyRing::new().key(1, pepper));
+        assert!(peppered.verify_with(&config, &raw_password).unwrap());
+        assert!(config.requires_rotation(&peppered));
+    }
+
+    #[test]
+    fn key_ring_hashes_keep_their_metadata() {
+        use crate::{HashAlgorithm, HashStoreReport, RehashPolicy};
+
+        let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+        let config = HashingConfig::new()
+            .scheme(HashScheme::bcrypt(4))
+            .key_ring(KeyRing::new().key(3, Pepper::from_bytes(&[3; 32]).unwrap()));
+        let peppered = raw_password.clone().to_encrypt_with(&config).unwrap();
+        assert!(peppered.as_str().ends_with("$k=3"));
+
+        let stored = Password::from_encrypt(peppered.as_str()).unwrap();
+        assert_eq!(stored.algorithm(), Some(HashAlgorithm::Bcrypt));
+        assert_eq!(stored.cost(), Some(4));
+        assert_eq!(stored.salt().map(str::len), Some(22));
+        assert_eq!(stored.hash_bytes().map(|hash| hash.len()), Some(23));
+        // without the pepper the password doesn't match, but the hash is valid
+        assert!(!stored.verify(&raw_password).unwrap());
+
+        let django = Password::from_encrypt("pbkdf2_sha256$1000$seasalt2024$aGFzaA==$k=1").unwrap();
+        assert_eq!(django.cost(), Some(1000));
+        assert_eq!(django.salt(), Some("seasalt2024"));
+
+        let policy = RehashPolicy::new().min_cost(4);
+        let report = HashStoreReport::scan([peppered.as_str(), django.as_str()], &policy);
+        assert!(report.malformed.is_empty());
+        assert_eq!(report.below_policy, [1]);
+    }
+}
+
+#[cfg(feature = "magic-link")]
+mod magic_link_tests {
+    use std::str::FromStr;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::{Email, MagicLinkError, MagicLinkKey, MagicLinkToken};
+
+    #[test]
+    fn magic_link_tokens_are_bound_to_the_email() {
+        let key = MagicLinkKey::from_bytes(&[7; 32]).unwrap();
+        let email = Email::from_str("john+login@example.com").unwrap();
+        let issued_at = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let token = MagicLinkToken::issue_at(&key, &email, issued_at + Duration::from_secs(900));
+
+        assert!(token
+            .as_str()
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || b"-_.".contains(&byte)));
+        assert_eq!(format!("{token:?}"), "MagicLinkToken(***)");
+
+        let parsed = MagicLinkToken::from_str(&token.to_string()).unwrap();
+        assert_eq!(parsed.verify_at(&key, issued_at), Ok(email.clone()));
+        assert_eq!(
+            parsed.verify_at(&key, issued_at + Duration::from_secs(900)),
+            Err(MagicLinkError::Expired)
+        );
+
+        let other_key = MagicLinkKey::generate();
+        assert_eq!(
+            parsed.verify_at(&other_key, issued_at),
+            Err(MagicLinkError::Signature)
+        );
+
+        // every token has a random nonce
+        let other_token =
+            MagicLinkToken::issue_at(&key, &email, issued_at + Duration::from_secs(900));
+        assert_ne!(token, other_token);
+    }
+
+    #[test]
+    fn tampered_magic_link_tokens_are_rejected() {
+        let key = MagicLinkKey::generate();
+        let email = Email::from_str("john@example.com").unwrap();
+        let token = MagicLinkToken::issue(&key, &email, Duration::from_secs(60));
+        let (payload, signature) = token.as_str().split_once('.').unwrap();
+
+        let other_email = Email::from_str("jane@example.com").unwrap();
+        let other_token = MagicLinkToken::issue(&key, &other_email, Duration::from_secs(60));
+        let (other_payload, _) = other_token.as_str().split_once('.').unwrap();
+
+        let forged = MagicLinkToken::from_str(&format!("{other_payload}.{signature}")).unwrap();
+        assert_eq!(forged.verify(&key), Err(MagicLinkError::Signature));
+
+        assert_eq!(
+            MagicLinkToken::from_str(payload),
+            Err(MagicLinkError::Malformed)
+        );
+        assert_eq!(
+            MagicLinkToken::from_str("not.a-token"),
+            Err(MagicLinkError::Malformed)
+        );
+        assert_eq!(
+            MagicLinkKey::from_bytes(&[7; 16]),
+            Err(MagicLinkError::KeyLength(32))
+        );
+    }
+}
+
+#[test]
+fn recovery_codes_are_single_use() {
+    use crate::{HashScheme, Password, RecoveryCodes};
+
+    let (codes, recovery_codes) = RecoveryCodes::generate_with(8, HashScheme::bcrypt(4));
+    assert_eq!(codes.len(), 8);
+    assert_eq!(recovery_codes.remaining(), 8);
+    assert!(!recovery_codes.hashes().contains(&codes[0]));
+
+    let mut recovery_codes = RecoveryCodes::from_hashes(recovery_codes.hashes().to_vec());
+    let formatted = format!(" {} ", codes[3].to_uppercase().replace('-', ""));
+    assert!(recovery_codes.verify(&formatted));
+    assert!(!recovery_codes.verify(&codes[3]));
+    assert!(!recovery_codes.verify("aaaaa-aaaaa"));
+    assert_eq!(recovery_codes.remaining(), 7);
+
+    for code in &codes {
+        recovery_codes.verify(code);
+    }
+    assert_eq!(recovery_codes.remaining(), 0);
+
+    // the codes are stored as password hashes, with a random salt
+    let (codes, recovery_codes) = RecoveryCodes::generate_with(1, HashScheme::bcrypt(4));
+    let hash = Password::from_encrypt(&recovery_codes.hashes()[0]).unwrap();
+    assert!(hash
+        .verify(&Password::new(&codes[0].replace('-', "")))
+        .unwrap());
+
+    // the SHA-256 digests are not accepted
+    let digest = crate::typed::digest::sha256_hex(b"abcdefghjk");
+    let mut recovery_codes = RecoveryCodes::from_hashes(vec![digest]);
+    assert!(!recovery_codes.verify("ABCDE-FGHJK"));
+}
+
+#[test]
+fn api_keys_are_hashed() {
+    use crate::{ApiKey, PasswordError, MIN_COST};
+
+    let raw_key = ApiKey::generate("ep_live_");
+    assert_eq!(raw_key.prefix(), "ep_live_");
+    assert_eq!(format!("{raw_key:?}"), "ApiKey(\"ep_live_***\")");
+
+    let sha256_key = raw_key.to_sha256();
+    let bcrypt_key = raw_key.to_bcrypt(MIN_COST).unwrap();
+    let shown_key = raw_key.reveal();
+    assert_eq!(shown_key.len(), 40);
+
+    for stored_key in [sha256_key, bcrypt_key] {
+        let stored_key = ApiKey::from_hash(stored_key.as_str()).unwrap();
+        assert_eq!(stored_key.verify(&ApiKey::from_raw(&shown_key)), Ok(true));
+        assert_eq!(
+            stored_key.verify(&ApiKey::from_raw("ep_live_other")),
+            Ok(false)
+        );
+    }
+
+    assert_eq!(
+        ApiKey::from_hash("ep_live_plain").err(),
+        Some(PasswordError::PasswordNotEncrypted)
+    );
+}
+
+#[cfg(feature = "keyring")]
+#[test]
+fn keyring_reports_missing_passwords() {
+    // Without the platform features, keyring uses a mock store that doesn't persist
+    let password = Password::new(SECURE_PASSWORD_VALUE);
+    assert!(password.store_in_keyring("email_pass", "john").is_ok());
+    assert!(matches!(
+        Password::from_keyring("email_pass", "john"),
+        Err(keyring::Error::NoEntry)
+    ));
+}
+
+#[test]
+fn passwords_are_loaded_from_env_and_files() {
+    use std::io::ErrorKind;
+
+    let path = std::env::temp_dir().join("email_pass_secret.txt");
+    std::fs::write(&path, format!("{SECURE_PASSWORD_VALUE}\n")).unwrap();
+
+    let password = Password::from_file(&path).unwrap();
+    assert!(password == Password::new(SECURE_PASSWORD_VALUE));
+
+    std::env::set_var("EMAIL_PASS_TEST_SECRET_FILE", &path);
+    let password = Password::from_env("EMAIL_PASS_TEST_SECRET").unwrap();
+    assert!(password == Password::new(SECURE_PASSWORD_VALUE));
+
+    // The variable has priority over the file
+    std::env::set_var("EMAIL_PASS_TEST_SECRET", " other.password ");
+    let password = Password::from_env("EMAIL_PASS_TEST_SECRET").unwrap();
+    assert!(password == Password::new("other.password"));
+
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        Password::from_env("EMAIL_PASS_TEST_MISSING")
+            .err()
+            .map(|err| err.kind()),
+        Some(ErrorKind::NotFound)
+    );
+}
+
+#[test]
+fn hashing_pool_runs_concurrent_jobs() {
+    use crate::HashingPool;
+
+    let pool = HashingPool::new(2, 1);
+    assert_eq!(pool.workers(), 2);
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let results: Vec<bool> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..6)
+            .map(|_| {
+                scope.spawn(|| {
+                    let encrypt_password = pool.hash(raw_password.clone(), 4).unwrap();
+                    pool.verify(&encrypt_password, &raw_password).unwrap()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+
+    assert_eq!(results, vec![true; 6]);
+    assert_eq!(
+        pool.hash(raw_password, 0).err(),
+        Some(crate::PasswordError::InvalidCost {
+            given: 0,
+            min: 4,
+            max: 31
+        })
+    );
+}
+
+#[test]
+fn hashing_pool_survives_panicking_jobs() {
+    use crate::HashingPool;
+
+    let pool = HashingPool::new(1, 1);
+    assert_eq!(pool.run(|| -> bool { panic!("job panicked") }), None);
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let encrypt_password = pool.hash(raw_password.clone(), 4).unwrap();
+    assert_eq!(pool.verify(&encrypt_password, &raw_password), Ok(true));
+}
+
+#[test]
+fn hash_and_verify_are_timed() {
+    let timed = Password::new(SECURE_PASSWORD_VALUE).to_encrypt_timed(4);
+    assert!(timed.duration > std::time::Duration::ZERO);
+
+    let encrypt_password = timed.into_inner().unwrap();
+    let timed = encrypt_password.verify_timed(&Password::new(SECURE_PASSWORD_VALUE));
+    assert!(timed.duration > std::time::Duration::ZERO);
+    assert!(timed.value.unwrap());
+}
+
+#[test]
+fn strength_meter_rates_the_passwords() {
+    use crate::{PasswordError, StrengthLevel, StrengthMeter};
+
+    let meter = StrengthMeter::new();
+    assert_eq!(meter.rate_str("password").unwrap().label, "Weak");
+    assert_eq!(
+        meter.rate_str(SECURE_PASSWORD_VALUE).unwrap().label,
+        "Excellent"
+    );
+    assert_eq!(meter.required_level().unwrap().label, "Good");
+    assert_eq!(meter.check("password"), Err(PasswordError::StrengthLevel));
+    assert_eq!(meter.check(""), Err(PasswordError::BlankPassword));
+    assert_eq!(
+        meter.check(SECURE_PASSWORD_VALUE).unwrap().label,
+        "Excellent"
+    );
+
+    // the levels are compared by their thresholds, not by their order
+    let meter = StrengthMeter::empty()
+        .level(StrengthLevel::new("Unbreakable", 4).min_guesses_log10(100.0))
+        .level(StrengthLevel::new("Strong", 3))
+        .level(StrengthLevel::new("Fair", 1));
+    assert_eq!(
+        meter.rate_str(SECURE_PASSWORD_VALUE).unwrap().label,
+        "Strong"
+    );
+    assert!(meter.rate_str("password").is_none());
+    assert_eq!(meter.check("password"), Err(PasswordError::StrengthLevel));
+}
+
+#[test]
+fn strength_meter_rejects_unknown_required_levels() {
+    use crate::{PasswordError, StrengthLevel, StrengthMeter};
+
+    let meter = StrengthMeter::empty()
+        .level(StrengthLevel::new("Weak", 0))
+        .level(StrengthLevel::new("Strong", 3));
+    assert_eq!(
+        meter.clone().required("Strng"),
+        Err(PasswordError::UnknownStrengthLevel("Strng".to_string()))
+    );
+    assert!(meter.required("Strong").is_ok());
+}
+
+#[test]
+fn strength_meter_uses_the_checker_dictionaries() {
+    use crate::{Language, PasswordStrengthChecker, StrengthMeter};
+
+    let meter = StrengthMeter::new();
+    assert!(meter.check("Geheim2024!").is_ok());
+    assert!(meter.check("Coyote.Acme").is_ok());
+
+    let meter = meter.checker(PasswordStrengthChecker::new().language(Language::German));
+    assert!(meter.check("Geheim2024!").is_err());
+
+    let meter = meter.checker(PasswordStrengthChecker::new().dictionary(["coyote", "acme"]));
+    assert!(meter.check("Coyote.Acme").is_err());
+    assert_eq!(meter, StrengthMeter::new());
+}
+
+#[cfg(feature = "i18n")]
+#[test]
+fn strength_feedback_is_translated() {
+    use crate::{Language, PasswordStrengthChecker};
+    use zxcvbn::feedback::{Suggestion, Warning};
+
+    let checker = PasswordStrengthChecker::new();
+    let feedback = checker
+        .localized_feedback("password", Language::German)
+        .unwrap();
+    assert_eq!(
+        feedback.warning,
+        Some("Dies ist eines der 10 häufigsten Passwörter.")
+    );
+    assert!(feedback
+        .suggestions
+        .contains(&"Füge ein oder zwei weitere Wörter hinzu. Seltene Wörter sind besser."));
+    assert!(checker
+        .localized_feedback(SECURE_PASSWORD_VALUE, Language::Spanish)
+        .is_none());
+
+    assert_eq!(
+        Language::Portuguese.warning(Warning::ThisIsACommonPassword),
+        "Esta é uma senha muito comum."
+    );
+    assert_eq!(
+        Language::Spanish.suggestion(Suggestion::AvoidSequences),
+        "Evita las secuencias."
+    );
+}
+
+#[test]
+fn failed_attempts_lock_the_account() {
+    use crate::{AttemptTracker, LockoutDecision, LockoutPolicy};
+    use std::time::{Duration, Instant};
+
+    let policy = LockoutPolicy::new()
+        .max_failures(2)
+        .backoff(Duration::from_secs(60));
+    let tracker = AttemptTracker::new(policy);
+    let encrypt_password = Password::new(SECURE_PASSWORD_VALUE).to_encrypt(4).unwrap();
+    let wrong_password = Password::new("wrong");
+    let now = Instant::now();
+
+    let decision = tracker.verify_at("john", &encrypt_password, &wrong_password, now);
+    assert_eq!(
+        decision.unwrap(),
+        LockoutDecision::Allowed { verified: false }
+    );
+    let decision = tracker.verify_at("john", &encrypt_password, &wrong_password, now);
+    assert_eq!(
+        decision.unwrap(),
+        LockoutDecision::Allowed { verified: false }
+    );
+
+    // the correct password is not verified while the account is locked
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let decision = tracker.verify_at("john", &encrypt_password, &raw_password, now);
+    assert_eq!(
+        decision.unwrap(),
+        LockoutDecision::Locked {
+            retry_after: Duration::from_secs(60)
+        }
+    );
+    assert!(tracker.retry_after_at("jane", now).is_none());
+
+    // the next failure doubles the lock
+    let later = now + Duration::from_secs(60);
+    tracker.record_failure_at("john", later);
+    assert_eq!(
+        tracker.retry_after_at("john", later),
+        Some(Duration::from_secs(120))
+    );
+
+    let later = later + Duration::from_secs(120);
+    let decision = tracker.verify_at("john", &encrypt_password, &raw_password, later);
+    assert!(decision.unwrap().is_verified());
+    assert!(tracker.retry_after_at("john", later).is_none());
+
+    // the attempts with a hash that can't be verified are not recorded
+    let invalid_hash = Password::from_encrypt("$2b$04$invalid").unwrap();
+    tracker.record_failure_at("john", later);
+    for _ in 0..2 {
+        assert!(tracker
+            .verify_at("john", &invalid_hash, &raw_password, later)
+            .is_err());
+    }
+    assert!(tracker.retry_after_at("john", later).is_none());
+}
+
+#[test]
+fn expired_attempts_are_pruned() {
+    use crate::{AttemptTracker, LockoutPolicy};
+    use std::time::{Duration, Instant};
+
+    let policy = LockoutPolicy::new()
+        .max_failures(2)
+        .window(Duration::from_secs(60))
+        .backoff(Duration::from_secs(600));
+    let tracker = AttemptTracker::new(policy);
+    let now = Instant::now();
+
+    tracker.record_failure_at("john", now);
+    tracker.record_failure_at("jane", now);
+    tracker.record_failure_at("jane", now);
+    assert_eq!(tracker.len(), 2);
+
+    // john's failure is outside the window, jane is still locked
+    let later = now + Duration::from_secs(120);
+    tracker.prune_at(later);
+    assert_eq!(tracker.len(), 1);
+    assert!(tracker.retry_after_at("jane", later).is_some());
+
+    tracker.prune_at(later + Duration::from_secs(1200));
+    assert!(tracker.is_empty());
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn test_util_hashes_are_fast_and_deterministic() {
+    use crate::test_util::MockHasher;
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let encrypt_password = raw_password.clone().to_encrypt_fast().unwrap();
+    assert!(encrypt_password.as_str().starts_with("$2b$04$"));
+    assert!(encrypt_password.verify(&raw_password).unwrap());
+
+    let mock_password = raw_password.clone().to_encrypt_with(&MockHasher).unwrap();
+    assert_eq!(
+        mock_password,
+        raw_password.clone().to_encrypt_with(&MockHasher).unwrap()
+    );
+    assert!(mock_password
+        .verify_with(&MockHasher, &raw_password)
+        .unwrap());
+    assert!(!mock_password
+        .verify_with(&MockHasher, &Password::new("other"))
+        .unwrap());
+
+    // the mock hashes round-trip through the verification of the stored hashes
+    let stored = Password::from_encrypt(mock_password.as_str()).unwrap();
+    assert!(stored.verify(&raw_password).unwrap());
+    assert!(!stored.verify(&Password::new("other")).unwrap());
+}
+
+#[test]
+fn fixtures_match_the_validation() {
+    use crate::typed::fixtures::*;
+    use crate::PasswordStrengthChecker;
+
+    for email in VALID_EMAILS {
+        assert!(Email::from_str(email).is_ok(), "{email}");
+    }
+    for email in INVALID_EMAILS {
+        assert!(Email::from_str(email).is_err(), "{email}");
+    }
+    for (email, valid) in EDGE_CASE_EMAILS {
+        assert_eq!(Email::from_str(email).is_ok(), *valid, "{email}");
+    }
+
+    let checker = PasswordStrengthChecker::new();
+    for password in WEAK_PASSWORDS {
+        assert!(checker.check(password).is_err(), "{password}");
+    }
+    for password in STRONG_PASSWORDS {
+        assert!(checker.check(password).is_ok(), "{password}");
+    }
+}
+
+#[test]
+fn hash_store_report_summarizes_hashes() {
+    use crate::{HashAlgorithm, HashStoreReport, RehashPolicy};
+
+    let strong_hash = Password::new(SECURE_PASSWORD_VALUE).to_encrypt(5).unwrap();
+    let weak_hash = Password::new(SECURE_PASSWORD_VALUE).to_encrypt(4).unwrap();
+    let hashes = [
+        strong_hash.as_str(),
+        weak_hash.as_str(),
+        "$1$salt$md5cryptvalue",
+        "plain-text",
+        "$2b$05$truncated",
+    ];
+
+    let report = HashStoreReport::scan(hashes, &RehashPolicy::new().min_cost(5));
+    assert_eq!(report.total, 5);
+    assert_eq!(report.algorithms.get(&HashAlgorithm::Bcrypt), Some(&2));
+    assert_eq!(
+        report.costs.into_iter().collect::<Vec<_>>(),
+        [
+            ((HashAlgorithm::Bcrypt, 4), 1),
+            ((HashAlgorithm::Bcrypt, 5), 1)
+        ]
+    );
+    assert_eq!(report.malformed, [2, 3, 4]);
+    assert_eq!(report.below_policy, [1]);
+}
+
+#[test]
+fn upgrading_verifier_rehashes_below_policy() {
+    use crate::{RehashPolicy, UpgradingVerifier};
+    use std::cell::RefCell;
+
+    let upgraded = RefCell::new(Vec::new());
+    let verifier = UpgradingVerifier::new(RehashPolicy::new().min_cost(5), |password| {
+        upgraded.borrow_mut().push(password)
+    });
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let weak_password = raw_password.clone().to_encrypt(4).unwrap();
+    assert!(!verifier
+        .verify(&weak_password, &Password::new("wrong.password"))
+        .unwrap());
+    assert!(upgraded.borrow().is_empty());
+
+    assert!(verifier.verify(&weak_password, &raw_password).unwrap());
+    let new_password = upgraded.borrow_mut().pop().unwrap();
+    assert!(new_password.as_str().starts_with("$2b$05$"));
+    assert!(new_password.verify(&raw_password).unwrap());
+
+    assert!(verifier.verify(&new_password, &raw_password).unwrap());
+    assert!(upgraded.borrow().is_empty());
+}
+
+#[cfg(feature = "argon2")]
+#[test]
+fn upgrading_verifier_rehashes_with_the_preferred_algorithm() {
+    use crate::{HashAlgorithm, RehashPolicy, UpgradingVerifier};
+    use std::cell::RefCell;
+
+    let upgraded = RefCell::new(Vec::new());
+    let policy = RehashPolicy::new()
+        .algorithm(HashAlgorithm::Argon2)
+        .min_cost(1);
+    let verifier = UpgradingVerifier::new(policy, |password| upgraded.borrow_mut().push(password));
+
+    let raw_password = Password::new(SECURE_PASSWORD_VALUE);
+    let bcrypt_password = raw_password.clone().to_encrypt(4).unwrap();
+    assert!(verifier.verify(&bcrypt_password, &raw_password).unwrap());
+    let new_password = upgraded.borrow_mut().pop().unwrap();
+    assert!(new_password
+        .as_str()
+        .starts_with("$argon2id$v=19$m=19456,t=1,p=1$"));
+    assert!(new_password.verify(&raw_password).unwrap());
+
+    assert!(verifier.verify(&new_password, &raw_password).unwrap());
+    assert!(upgraded.borrow().is_empty());
+}
+
+#[test]
+fn suppressed_emails_are_rejected() {
+    use crate::{EmailError, EmailSet, SuppressionList};
+    use std::future::Future;
+    use std::pin::pin;
+    use std::task::{Context, Poll, Waker};
+
+    struct BlockedDomain;
+
+    impl SuppressionList for BlockedDomain {
+        fn is_suppressed(&self, email: &Email) -> bool {
+            email.domain() == "blocked.com"
+        }
+    }
+
+    let mut bounces = EmailSet::new();
+    bounces.insert(Email::from_str("john@example.com").unwrap());
+
+    assert_eq!(
+        Email::parse_unsuppressed("John+news@example.com", &bounces),
+        Err(EmailError::Suppressed)
+    );
+    assert!(Email::parse_unsuppressed("jane@example.com", &bounces).is_ok());
+    assert_eq!(
+        Email::parse_unsuppressed("example.com", &bounces),
+        Err(EmailError::Format)
+    );
+
+    let future = pin!(Email::parse_unsuppressed_async(
+        "jane@blocked.com",
+        &BlockedDomain
+    ));
+    let result = future.poll(&mut Context::from_waker(Waker::noop()));
+    assert_eq!(result, Poll::Ready(Err(EmailError::Suppressed)));
+}
+
+#[test]
+fn email_validator_pipeline_runs_the_steps_in_order() {
+    use crate::{AsyncEmailValidator, EmailError, EmailValidatorPipeline};
+    use std::future::Future;
+    use std::pin::pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Waker};
+
+    struct DenyDomain(&'static str, Arc<AtomicUsize>);
+
+    impl AsyncEmailValidator for DenyDomain {
+        async fn validate(&self, email: &Email) -> Result<(), EmailError> {
+            self.1.fetch_add(1, Ordering::SeqCst);
+            if email.domain() == self.0 {
+                return Err(EmailError::Rejected);
+            }
+            Ok(())
+        }
+    }
+
+    fn run(pipeline: &EmailValidatorPipeline, email: &str) -> Result<Email, EmailError> {
+        let future = pin!(pipeline.validate(email));
+        match future.poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(result) => result,
+            Poll::Pending => panic!("the steps are ready"),
+        }
+    }
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let pipeline = EmailValidatorPipeline::new()
+        .step(DenyDomain("first.com", calls.clone()))
+        .step(EmailValidatorPipeline::new().step(DenyDomain("second.com", calls.clone())));
+
+    assert_eq!(
+        run(&pipeline, "john@example.com"),
+        Ok(Email::from_str("john@example.com").unwrap())
+    );
+    assert_eq!(calls.swap(0, Ordering::SeqCst), 2);
+
+    // the first rejection stops the pipeline
+    assert_eq!(run(&pipeline, "john@first.com"), Err(EmailError::Rejected));
+    assert_eq!(calls.swap(0, Ordering::SeqCst), 1);
+    assert_eq!(run(&pipeline, "john@second.com"), Err(EmailError::Rejected));
+    assert_eq!(calls.swap(0, Ordering::SeqCst), 2);
+
+    // the syntax is validated before the steps
+    assert_eq!(run(&pipeline, "example.com"), Err(EmailError::Format));
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn email_control_characters_are_rejected() {
+    use crate::EmailError;
+
+    for input in [
+        "john@example.com\r\nBcc: jane@example.com",
+        "john@example.com\n",
+        "jo\0hn@example.com",
+        "\tjohn@example.com",
+    ] {
+        assert_eq!(Email::from_str(input), Err(EmailError::ControlCharacter));
+    }
+
+    assert_eq!(
+        Email::build("john\r\n", "example.com"),
+        Err(EmailError::ControlCharacter)
+    );
+    let mut email = Email::from_str("john@example.com").unwrap();
+    assert_eq!(
+        email.set_domain("example.com\r\nBcc: x"),
+        Err(EmailError::ControlCharacter)
+    );
+}
+
+#[test]
+fn domain_registrable_domain_and_tld() {
+    use crate::Domain;
+
+    let cases = [
+        ("example.com", "example.com", "com"),
+        ("mail.example.com", "example.com", "com"),
+        ("a.b.example.co.uk", "example.co.uk", "uk"),
+        ("shop.example.com.br", "example.com.br", "br"),
+        ("mail.example.de", "example.de", "de"),
+        ("localhost", "localhost", "localhost"),
+    ];
+    for (domain, registrable, tld) in cases {
+        let domain = Domain::new(domain);
+        assert_eq!(domain.registrable_domain().as_str(), registrable);
+        assert_eq!(domain.tld(), tld);
+    }
+}
+
+#[test]
+fn email_stats_groups_the_emails() {
+    use crate::EmailStats;
+
+    let stats: EmailStats = [
+        "john@example.com",
+        "jane@Example.com",
+        "joe@mail.example.com",
+        "ana@example.co.uk",
+        "luis@other.org",
+    ]
+    .into_iter()
+    .map(|email| Email::from_str(email).unwrap())
+    .collect();
+
+    assert_eq!(stats.total, 5);
+    assert_eq!(stats.domains["example.com"], 2);
+    assert_eq!(
+        stats.top_registrable_domains(2),
+        [("example.com", 3), ("example.co.uk", 1)]
+    );
+    assert_eq!(stats.top_tlds(5), [("com", 3), ("org", 1), ("uk", 1)]);
+    assert_eq!(stats.top_domains(0), []);
+}
+
+#[test]
+fn email_compares_with_strings() {
+    let email = Email::from_str("John@Example.com").unwrap();
+
+    assert!(email == "John@Example.com");
+    assert!(email == *"John@Example.com");
+    assert!(email != "john@example.com");
+    assert!(email != "John");
+    assert!(email.eq_ignore_case("john@EXAMPLE.com"));
+    assert!(!email.eq_ignore_case("jane@example.com"));
+    assert!(!email.eq_ignore_case("john.example.com"));
+}
+
+#[cfg(feature = "valuable")]
+mod valuable_tests {
+    use std::str::FromStr;
+
+    use valuable::{NamedValues, Valuable, Value, Visit};
+
+    use crate::{Email, Password};
+
+    #[derive(Default)]
+    struct Collector(Vec<String>);
+
+    impl Visit for Collector {
+        fn visit_value(&mut self, value: Value<'_>) {
+            match value {
+                Value::String(value) => self.0.push(value.to_string()),
+                Value::Structable(value) => value.visit(self),
+                _ => {}
+            }
+        }
+
+        fn visit_named_fields(&mut self, named_values: &NamedValues<'_>) {
+            for (field, value) in named_values {
+                if let Value::String(value) = value {
+                    self.0.push(format!("{}={value}", field.name()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn valuable_values_are_masked() {
+        let mut collector = Collector::default();
+        valuable::visit(
+            &Email::from_str("john@example.com").unwrap(),
+            &mut collector,
+        );
+        assert_eq!(collector.0, ["username=j***", "domain=example.com"]);
+
+        let raw_password = Password::new("ThisIsAPassPhrase.And.Secure.Password");
+        let encrypt_password = raw_password.clone().to_encrypt(4).unwrap();
+        assert!(matches!(raw_password.as_value(), Value::String("***")));
+        assert!(matches!(encrypt_password.as_value(), Value::String("***")));
+    }
+}
+
+#[cfg(feature = "heapless")]
+mod heapless_tests {
+    use crate::{Email, EmailError, FixedEmail};
+
+    #[test]
+    fn fixed_email_validation_works() {
+        let email = FixedEmail::<254>::parse("john.doe+news@mail.example.com").unwrap();
+        assert_eq!(email.username(), "john.doe+news");
+        assert_eq!(email.domain(), "mail.example.com");
+        assert_eq!(email.to_string(), "john.doe+news@mail.example.com");
+        assert!(Email::try_from(email).unwrap() == "john.doe+news@mail.example.com");
+
+        assert_eq!(
+            FixedEmail::<10>::parse("john@example.com"),
+            Err(EmailError::Length)
+        );
+        assert_eq!(
+            "john@example.com\r\n".parse::<FixedEmail>(),
+            Err(EmailError::Format)
+        );
+        assert_eq!(
+            FixedEmail::<32>::parse("example.com"),
+            Err(EmailError::Format)
+        );
+    }
+}
+
+#[cfg(not(feature = "zxcvbn"))]
+mod strength_rules_tests {
+    use crate::{PasswordError, PasswordStrengthChecker};
+
+    #[test]
+    fn rules_estimate_the_strength() {
+        let checker = PasswordStrengthChecker::new();
+
+        for password in ["Password2024!", "abcdefgh123", "zzzzzzzzzzzz", "1234567890"] {
+            assert!(checker.check(password).is_err(), "{password}");
+        }
+        assert_eq!(
+            checker.check("          ").err(),
+            Some(PasswordError::BlankPassword)
+        );
+
+        let entropy = checker.check(super::SECURE_PASSWORD_VALUE).unwrap();
+        assert_eq!(entropy.score(), 4);
+        assert!(entropy.guesses_log10() > 10.0);
+    }
+}
+
+#[test]
+fn default_password_policy_is_used_by_check() {
+    use crate::{set_default_password_policy, PasswordStrengthChecker};
+
+    // The policy is global, so it only adds a word no other test uses
+    let policy = PasswordStrengthChecker::new().dictionary(["zebracorn"]);
+    assert!(set_default_password_policy(policy.clone()).is_ok());
+    assert!(set_default_password_policy(policy).is_err());
+
+    assert!(Password::new("Zebracorn.2024").check().is_err());
+    assert!(Password::new(SECURE_PASSWORD_VALUE).check().is_ok());
+}
+
+#[test]
+fn email_new_matches_from_str() {
+    assert_eq!(
+        Email::new("john@example.com"),
+        Email::from_str("john@example.com")
+    );
+    assert!(Email::new("example.com").is_err());
+}
+
+#[test]
+fn encrypted_passwords_can_be_deduplicated() {
+    use std::collections::HashSet;
+
+    let hash = "$2b$04$teRReyH3sVfCd8JA71Sm6xekdy6KhRIzYYERUEUC/.mS2Ms3ByM.q";
+    let passwords: HashSet<Password> = [hash, hash, &hash.replace("q", "a")]
+        .into_iter()
+        .map(|hash| Password::from_encrypt(hash).unwrap())
+        .collect();
+
+    assert_eq!(passwords.len(), 2);
+}
+
+#[test]
+fn encrypted_password_shares_the_hash() {
+    use std::sync::Arc;
+
+    let hash = "$2b$04$teRReyH3sVfCd8JA71Sm6xekdy6KhRIzYYERUEUC/.mS2Ms3ByM.q";
+    let password = Password::from_encrypt(hash).unwrap();
+
+    assert_eq!(password.as_bytes(), hash.as_bytes());
+    let shared = password.clone_arc();
+    assert_eq!(&*shared, hash);
+    assert!(std::ptr::eq(shared.as_ptr(), password.as_str().as_ptr()));
+    assert_eq!(Arc::strong_count(&shared), 2);
+}
+
+#[test]
+fn email_converts_to_arc() {
+    use std::sync::Arc;
+
+    let email = Email::from_str(" <John@example.com>").unwrap();
+    assert_eq!(&*email.clone().into_arc(), "John@example.com");
+
+    // the stored address is shared, not copied
+    #[cfg(not(feature = "compact_str"))]
+    assert!(std::ptr::eq(
+        email.clone().into_arc().as_ptr(),
+        email.username().as_ptr()
+    ));
+
+    let address: Arc<str> = email.into();
+    assert_eq!(&*address, "John@example.com");
+}
+
+#[cfg(feature = "addr")]
+mod addr_tests {
+    use std::str::FromStr;
+
+    use crate::{Domain, Email, EmailError};
+
+    #[test]
+    fn hostname_rules_are_enforced() {
+        for email in ["john@example-.com", "john@-example.com", "john@example.123"] {
+            assert_eq!(Email::from_str(email), Err(EmailError::Domain), "{email}");
+        }
+        assert_eq!(Email::build("john", "example.123"), Err(EmailError::Domain));
+        assert!(Email::from_str("john@mail-1.example.com").is_ok());
+    }
+
+    #[test]
+    fn registrable_domain_uses_the_public_suffix_list() {
+        let domain = Domain::new("mail.example.github.io");
+        assert_eq!(domain.registrable_domain().as_str(), "example.github.io");
+    }
+}
+
+#[test]
+fn emails_are_extracted_from_text() {
+    let text = "From: ...john.doe@example.com, cc <jane+news@mail.example.org>.\n\
+                Not emails: john..doe@example.com @example.com john@ x@y.z";
+    let emails: Vec<_> = Email::extract_all(text)
+        .map(|(range, email)| (&text[range], email.to_string()))
+        .collect();
+
+    assert_eq!(
+        emails,
+        [
+            ("john.doe@example.com", "john.doe@example.com".to_string()),
+            (
+                "jane+news@mail.example.org",
+                "jane+news@mail.example.org".to_string()
+            ),
+        ]
+    );
+    assert_eq!(Email::extract_all("no emails here").count(), 0);
 }
@@ -0,0 +1,68 @@
+use std::time::{Duration, Instant};
+
+use crate::errors::PasswordError;
+use crate::typed::password::{Encrypt, HashFamily, Password, Raw};
+
+/// Result of an operation with its wall-clock duration.
+///
+/// The clock is not available in `wasm32-unknown-unknown`, where the duration is
+/// always zero.
+///
+/// ```
+/// use email_pass::Password;
+///
+/// let timed = Password::new("ThisIsAPassPhrase.And.Secure.Password").to_encrypt_timed(4);
+/// println!("hashed in {:?}", timed.duration);
+/// let encrypt_password = timed.value?;
+/// # Ok::<(), email_pass::PasswordError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timed<T> {
+    pub value: T,
+    pub duration: Duration,
+}
+
+impl<T> Timed<T> {
+    /// Runs the operation, measuring its duration.
+    pub fn measure<F>(operation: F) -> Self
+    where
+        F: FnOnce() -> T,
+    {
+        let start = timer_start();
+        let value = operation();
+        Self {
+            value,
+            duration: start.map_or(Duration::ZERO, |start| start.elapsed()),
+        }
+    }
+
+    /// Returns the value, discarding the duration.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+/// Returns the current instant, or `None` where [`Instant::now`] panics, like
+/// `wasm32-unknown-unknown`.
+#[inline]
+pub(crate) fn timer_start() -> Option<Instant> {
+    if cfg!(all(target_arch = "wasm32", target_os = "unknown")) {
+        None
+    } else {
+        Some(Instant::now())
+    }
+}
+
+impl Password<Raw> {
+    /// Like [`Password<Raw>::to_encrypt`], returning also the duration of the hash.
+    pub fn to_encrypt_timed(self, cost: u32) -> Timed<Result<Password<Encrypt>, PasswordError>> {
+        Timed::measure(|| self.to_encrypt(cost))
+    }
+}
+
+impl<Family: HashFamily> Password<Encrypt<Family>> {
+    /// Like [`Password::verify`], returning also the duration of the verification.
+    pub fn verify_timed(&self, raw_password: &Password<Raw>) -> Timed<Result<bool, PasswordError>> {
+        Timed::measure(|| self.verify(raw_password))
+    }
+}
@@ -0,0 +1,58 @@
+use tokio::task;
+
+use crate::errors::PasswordError;
+use crate::typed::password::{Encrypt, HashFamily, Password, Raw};
+
+impl Password<Raw> {
+    /// Encrypts the password like [`Password<Raw>::to_encrypt`], in the blocking thread
+    /// pool of Tokio, so the hash doesn't block the async workers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if it's called outside of a Tokio runtime.
+    ///
+    /// ```
+    /// use email_pass::Password;
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let raw_password = Password::new("ThisIsAPassPhrase.And.Secure.Password");
+    /// let encrypt_password = raw_password.clone().to_encrypt_async(4).await?;
+    /// assert!(encrypt_password.verify_async(&raw_password).await?);
+    /// # Ok::<(), email_pass::PasswordError>(())
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * The errors of [`Password<Raw>::to_encrypt`].
+    /// * `PasswordError::PasswordEncryption` - If the blocking task panicked.
+    pub async fn to_encrypt_async(self, cost: u32) -> Result<Password<Encrypt>, PasswordError> {
+        task::spawn_blocking(move || self.to_encrypt(cost))
+            .await
+            .unwrap_or(Err(PasswordError::PasswordEncryption))
+    }
+}
+
+impl<Family: HashFamily + Send + 'static> Password<Encrypt<Family>> {
+    /// Verifies the raw password like [`Password::verify`], in the blocking thread pool
+    /// of Tokio, so the verification doesn't block the async workers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if it's called outside of a Tokio runtime.
+    ///
+    /// # Errors
+    ///
+    /// * `PasswordError::PasswordVerification` - If bcrypt fails, or the blocking task
+    ///   panicked.
+    pub async fn verify_async(&self, raw_password: &Password<Raw>) -> Result<bool, PasswordError> {
+        let encrypt_password: Self = Password::from_hash(self.as_ref().to_string());
+        let raw_password = raw_password.clone();
+
+        task::spawn_blocking(move || encrypt_password.verify(&raw_password))
+            .await
+            .ok()
+            .and_then(Result::ok)
+            .ok_or(PasswordError::PasswordVerification)
+    }
+}
@@ -0,0 +1,110 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+
+use crate::errors::EmailError;
+use crate::typed::email::Email;
+
+/// Asynchronous step of an [`EmailValidatorPipeline`], like a query to an internal
+/// deny-list service or to a third-party verification API.
+///
+/// The step receives an email already validated by [`Email::from_str`].
+pub trait AsyncEmailValidator {
+    /// Returns `Ok(())` if the email is accepted, or the rejection error, like
+    /// `EmailError::Rejected`.
+    fn validate(&self, email: &Email) -> impl Future<Output = Result<(), EmailError>> + Send;
+}
+
+/// Object-safe version of [`AsyncEmailValidator`], to store different steps together.
+trait DynEmailValidator: Send + Sync {
+    fn validate_boxed<'a>(
+        &'a self,
+        email: &'a Email,
+    ) -> Pin<Box<dyn Future<Output = Result<(), EmailError>> + Send + 'a>>;
+}
+
+impl<V> DynEmailValidator for V
+where
+    V: AsyncEmailValidator + Send + Sync,
+{
+    fn validate_boxed<'a>(
+        &'a self,
+        email: &'a Email,
+    ) -> Pin<Box<dyn Future<Output = Result<(), EmailError>> + Send + 'a>> {
+        Box::pin(self.validate(email))
+    }
+}
+
+/// Chain of the syntax validation and the [`AsyncEmailValidator`] steps, run in the
+/// order they were added. The first rejection stops the pipeline.
+///
+/// ```
+/// use email_pass::{AsyncEmailValidator, Email, EmailError, EmailValidatorPipeline};
+///
+/// struct DenyList;
+///
+/// impl AsyncEmailValidator for DenyList {
+///     async fn validate(&self, email: &Email) -> Result<(), EmailError> {
+///         // Query the deny-list service
+///         match email.domain() {
+///             "mailinator.com" => Err(EmailError::Rejected),
+///             _ => Ok(()),
+///         }
+///     }
+/// }
+///
+/// let pipeline = EmailValidatorPipeline::new().step(DenyList);
+/// # let _ = async {
+/// let email = pipeline.validate("john@example.com").await?;
+/// # Ok::<(), EmailError>(())
+/// # };
+/// ```
+#[derive(Default)]
+pub struct EmailValidatorPipeline {
+    steps: Vec<Box<dyn DynEmailValidator>>,
+}
+
+impl EmailValidatorPipeline {
+    /// Creates a pipeline with only the syntax validation.
+    pub fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    /// Adds a step after the previous ones.
+    pub fn step<V>(mut self, validator: V) -> Self
+    where
+        V: AsyncEmailValidator + Send + Sync + 'static,
+    {
+        self.steps.push(Box::new(validator));
+        self
+    }
+
+    /// Parses the email like [`Email::from_str`], and runs the steps.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(email)` - If the email is valid and every step accepts it.
+    /// * The parsing error, or the error of the first step that rejects the email.
+    pub async fn validate(&self, email: &str) -> Result<Email, EmailError> {
+        let email = Email::from_str(email)?;
+        self.validate_email(&email).await?;
+
+        Ok(email)
+    }
+
+    /// Runs the steps with an email already parsed.
+    pub async fn validate_email(&self, email: &Email) -> Result<(), EmailError> {
+        for step in &self.steps {
+            step.validate_boxed(email).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A pipeline can be a step of another pipeline.
+impl AsyncEmailValidator for EmailValidatorPipeline {
+    fn validate(&self, email: &Email) -> impl Future<Output = Result<(), EmailError>> + Send {
+        self.validate_email(email)
+    }
+}
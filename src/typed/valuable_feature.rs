@@ -0,0 +1,65 @@
+#[cfg(feature = "email")]
+use valuable::{Fields, NamedField, NamedValues, StructDef, Structable};
+use valuable::{Valuable, Value, Visit};
+
+#[cfg(feature = "email")]
+use crate::typed::email::Email;
+#[cfg(feature = "password")]
+use crate::typed::password::{Encrypt, Password, Raw};
+
+/// Value of the redacted passwords.
+const MASK: &str = "***";
+
+#[cfg(feature = "email")]
+static EMAIL_FIELDS: &[NamedField<'static>] =
+    &[NamedField::new("username"), NamedField::new("domain")];
+
+/// The email is a structure with the masked username, keeping only its first
+/// character, and the domain: `Email { username: "j***", domain: "example.com" }`.
+#[cfg(feature = "email")]
+impl Valuable for Email {
+    fn as_value(&self) -> Value<'_> {
+        Value::Structable(self)
+    }
+
+    fn visit(&self, visitor: &mut dyn Visit) {
+        let first_len = self.username().chars().next().map_or(0, char::len_utf8);
+        let username = format!("{}{MASK}", &self.username()[..first_len]);
+
+        visitor.visit_named_fields(&NamedValues::new(
+            EMAIL_FIELDS,
+            &[Value::String(&username), Value::String(self.domain())],
+        ));
+    }
+}
+
+#[cfg(feature = "email")]
+impl Structable for Email {
+    fn definition(&self) -> StructDef<'_> {
+        StructDef::new_static("Email", Fields::Named(EMAIL_FIELDS))
+    }
+}
+
+/// The password is always `***`.
+#[cfg(feature = "password")]
+impl Valuable for Password<Raw> {
+    fn as_value(&self) -> Value<'_> {
+        Value::String(MASK)
+    }
+
+    fn visit(&self, visitor: &mut dyn Visit) {
+        visitor.visit_value(self.as_value());
+    }
+}
+
+/// The hash is always `***`.
+#[cfg(feature = "password")]
+impl<Family> Valuable for Password<Encrypt<Family>> {
+    fn as_value(&self) -> Value<'_> {
+        Value::String(MASK)
+    }
+
+    fn visit(&self, visitor: &mut dyn Visit) {
+        visitor.visit_value(self.as_value());
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+/// Minimum length of the dictionary words, the shorter words are ignored.
+const MIN_WORD_LEN: usize = 3;
+
+static SPANISH: LazyLock<Dictionary> =
+    LazyLock::new(|| Dictionary::from_words(Language::Spanish.words()));
+static PORTUGUESE: LazyLock<Dictionary> =
+    LazyLock::new(|| Dictionary::from_words(Language::Portuguese.words()));
+static GERMAN: LazyLock<Dictionary> =
+    LazyLock::new(|| Dictionary::from_words(Language::German.words()));
+
+/// Languages of the built-in wordlists, with common words and names missed
+/// by the English-centric [`zxcvbn`] dictionaries.
+///
+/// See [`PasswordStrengthChecker::language`].
+///
+/// [`PasswordStrengthChecker::language`]: crate::PasswordStrengthChecker::language
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Language {
+    Spanish,
+    Portuguese,
+    German,
+}
+
+impl Language {
+    pub(crate) fn bit(&self) -> u8 {
+        1 << (*self as u8)
+    }
+
+    pub(crate) const ALL: [Language; 3] =
+        [Language::Spanish, Language::Portuguese, Language::German];
+
+    fn wordlist(&self) -> &'static str {
+        match self {
+            Language::Spanish => include_str!("wordlists/es.txt"),
+            Language::Portuguese => include_str!("wordlists/pt.txt"),
+            Language::German => include_str!("wordlists/de.txt"),
+        }
+    }
+
+    /// Returns the words of the built-in wordlist.
+    pub fn words(&self) -> impl Iterator<Item = &'static str> {
+        self.wordlist().lines()
+    }
+
+    /// Returns the dictionary of the built-in wordlist, built on first use.
+    pub(crate) fn dictionary(&self) -> &'static Dictionary {
+        match self {
+            Language::Spanish => &SPANISH,
+            Language::Portuguese => &PORTUGUESE,
+            Language::German => &GERMAN,
+        }
+    }
+}
+
+/// Lowercased words that lower the strength of the passwords containing them.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Dictionary {
+    words: HashSet<String>,
+    /// Length in chars of the longest word
+    max_len: usize,
+}
+
+impl Dictionary {
+    pub(crate) fn from_words<'a>(words: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut dictionary = Self::default();
+        for word in words {
+            dictionary.insert(word);
+        }
+        dictionary
+    }
+
+    pub(crate) fn insert(&mut self, word: &str) {
+        let word = word.trim().to_lowercase();
+        let len = word.chars().count();
+        if len < MIN_WORD_LEN {
+            return;
+        }
+
+        self.max_len = self.max_len.max(len);
+        self.words.insert(word);
+    }
+
+    /// Returns the spans of chars of the words contained in the password, ignoring the case.
+    pub(crate) fn find_spans(&self, raw_password: &str) -> Vec<(usize, usize)> {
+        let chars: Vec<char> = raw_password.to_lowercase().chars().collect();
+        let mut spans = Vec::new();
+
+        for start in 0..chars.len() {
+            let max_end = chars.len().min(start + self.max_len);
+            for end in (start + MIN_WORD_LEN)..=max_end {
+                let candidate: String = chars[start..end].iter().collect();
+                if self.words.contains(&candidate) {
+                    spans.push((start, end));
+                }
+            }
+        }
+
+        spans
+    }
+
+    /// Returns the words contained in the password, ignoring the case.
+    #[cfg(feature = "zxcvbn")]
+    pub(crate) fn find(&self, raw_password: &str) -> Vec<&str> {
+        let chars: Vec<char> = raw_password.to_lowercase().chars().collect();
+
+        self.find_spans(raw_password)
+            .into_iter()
+            .filter_map(|(start, end)| {
+                let candidate: String = chars[start..end].iter().collect();
+                self.words.get(&candidate).map(String::as_str)
+            })
+            .collect()
+    }
+}